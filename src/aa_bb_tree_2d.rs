@@ -24,7 +24,7 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use crate::*;
 
-use std::marker::PhantomData;
+use std::{cmp::Ordering, marker::PhantomData};
 
 //------------------------------------------------------------------------------
 
@@ -47,7 +47,25 @@ where
     HB: HasBoundingBox2D + Clone,
 {
     pub fn new(data: Vec<HB>, maxdepth: usize, allowed_bucket_size: usize) -> Self {
-        Self::new_rec(data, maxdepth, allowed_bucket_size, 0)
+        Self::new_with_strategy(data, maxdepth, allowed_bucket_size, SplitStrategy::Center)
+    }
+
+    /// Creates a new tree, splitting the elements according to the given strategy
+    pub fn new_with_strategy(
+        data: Vec<HB>,
+        maxdepth: usize,
+        allowed_bucket_size: usize,
+        strategy: SplitStrategy,
+    ) -> Self {
+        Self::new_rec(data, maxdepth, allowed_bucket_size, &strategy, 0)
+    }
+
+    /// Returns the number of branches along the longest path from the root to a leaf
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Empty | Self::Leaf(_) => 0,
+            Self::Branch(branch) => 1 + branch.left.depth().max(branch.right.depth()),
+        }
     }
 
     pub fn any<'a>(&'a self, f: &dyn Fn(&HB) -> bool) -> bool {
@@ -90,7 +108,13 @@ where
         }
     }
 
-    fn new_rec(data: Vec<HB>, maxdepth: usize, allowed_bucket_size: usize, depth: usize) -> Self {
+    fn new_rec(
+        data: Vec<HB>,
+        maxdepth: usize,
+        allowed_bucket_size: usize,
+        strategy: &SplitStrategy,
+        depth: usize,
+    ) -> Self {
         match data.len() {
             0 => AABBTree2D::Empty,
             1 => {
@@ -104,42 +128,99 @@ where
                 } else {
                     let compx = depth % 2 != 0;
                     let bb = Self::bb_of(&data).unwrap(); //unwrap fine due to early return in new and data not empty
-                    let center = bb.center_bb();
-
-                    let dleft = data
-                        .iter()
-                        .cloned()
-                        .filter(|x| Self::is_left_of(compx, &x.bounding_box(), &center))
-                        .collect::<Vec<_>>();
-                    let dright = data
-                        .iter()
-                        .cloned()
-                        .filter(|x| Self::is_right_of(compx, &x.bounding_box(), &center))
-                        .collect::<Vec<_>>();
-
-                    if (dleft.len() == dright.len()) && dleft.len() == data.len() {
-                        AABBTree2D::Leaf(AABBTree2DLeaf::new(data, bb))
-                    } else {
-                        let left = Box::new(Self::new_rec(
-                            dleft,
-                            maxdepth,
-                            allowed_bucket_size,
-                            depth + 1,
-                        ));
-                        let right = Box::new(Self::new_rec(
-                            dright,
-                            maxdepth,
-                            allowed_bucket_size,
-                            depth + 1,
-                        ));
-
-                        AABBTree2D::Branch(AABBTree2DBranch::new(left, right, bb))
+
+                    let split = match strategy {
+                        SplitStrategy::Center => Self::split_center(&data, compx, &bb),
+                        SplitStrategy::Median => Self::split_median(&data, compx),
+                    };
+
+                    match split {
+                        None => AABBTree2D::Leaf(AABBTree2DLeaf::new(data, bb)),
+                        Some((dleft, dright)) => {
+                            let left = Box::new(Self::new_rec(
+                                dleft,
+                                maxdepth,
+                                allowed_bucket_size,
+                                strategy,
+                                depth + 1,
+                            ));
+                            let right = Box::new(Self::new_rec(
+                                dright,
+                                maxdepth,
+                                allowed_bucket_size,
+                                strategy,
+                                depth + 1,
+                            ));
+
+                            AABBTree2D::Branch(AABBTree2DBranch::new(left, right, bb))
+                        }
                     }
                 }
             }
         }
     }
 
+    fn split_center(data: &[HB], compx: bool, bb: &BoundingBox2D) -> Option<(Vec<HB>, Vec<HB>)> {
+        let center = bb.center_bb();
+
+        let dleft = data
+            .iter()
+            .filter(|x| Self::is_left_of(compx, &x.bounding_box(), &center))
+            .cloned()
+            .collect::<Vec<_>>();
+        let dright = data
+            .iter()
+            .filter(|x| Self::is_right_of(compx, &x.bounding_box(), &center))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if (dleft.len() == dright.len()) && dleft.len() == data.len() {
+            None
+        } else {
+            Some((dleft, dright))
+        }
+    }
+
+    fn split_median(data: &[HB], compx: bool) -> Option<(Vec<HB>, Vec<HB>)> {
+        // if the centers coincide along one axis, try the other one
+        Self::split_median_along(data, compx).or_else(|| Self::split_median_along(data, !compx))
+    }
+
+    fn split_median_along(data: &[HB], compx: bool) -> Option<(Vec<HB>, Vec<HB>)> {
+        let mut sorted = data
+            .iter()
+            .map(|x| (Self::center_value(compx, &x.bounding_box()), x))
+            .collect::<Vec<_>>();
+
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        // all centers coincide along this axis, there's no meaningful split
+        if sorted[0].0 == sorted[sorted.len() - 1].0 {
+            return None;
+        }
+
+        let median = sorted.len() / 2;
+        let dleft = sorted[..median]
+            .iter()
+            .map(|(_, x)| (*x).clone())
+            .collect::<Vec<_>>();
+        let dright = sorted[median..]
+            .iter()
+            .map(|(_, x)| (*x).clone())
+            .collect::<Vec<_>>();
+
+        Some((dleft, dright))
+    }
+
+    fn center_value(compx: bool, bb: &BoundingBox2D) -> f64 {
+        let center = bb.center_bb();
+        if compx {
+            center.x()
+        } else {
+            center.y()
+        }
+    }
+
     fn is_left_of(compx: bool, bb: &BoundingBox2D, center: &Point2D) -> bool {
         if compx {
             bb.min_p().x() < center.x()
@@ -24,7 +24,7 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use crate::*;
 
-use std::marker::PhantomData;
+use std::{cmp::Ordering, marker::PhantomData};
 
 //------------------------------------------------------------------------------
 
@@ -47,7 +47,25 @@ where
     HB: HasBoundingBox3D + Clone,
 {
     pub fn new(data: Vec<HB>, maxdepth: usize, allowed_bucket_size: usize) -> Self {
-        Self::new_rec(data, maxdepth, allowed_bucket_size, 0)
+        Self::new_with_strategy(data, maxdepth, allowed_bucket_size, SplitStrategy::Center)
+    }
+
+    /// Creates a new tree, splitting the elements according to the given strategy
+    pub fn new_with_strategy(
+        data: Vec<HB>,
+        maxdepth: usize,
+        allowed_bucket_size: usize,
+        strategy: SplitStrategy,
+    ) -> Self {
+        Self::new_rec(data, maxdepth, allowed_bucket_size, &strategy, 0)
+    }
+
+    /// Returns the number of branches along the longest path from the root to a leaf
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Empty | Self::Leaf(_) => 0,
+            Self::Branch(branch) => 1 + branch.left.depth().max(branch.right.depth()),
+        }
     }
 
     pub fn any<'a>(&'a self, f: &dyn Fn(&HB) -> bool) -> bool {
@@ -59,10 +77,36 @@ where
     }
 
     pub fn for_each_intersection_candidate<'a>(&'a self, line: &Line3D, f: &mut dyn FnMut(&HB)) {
+        self.for_each_intersection_candidate_traced(line, f, None)
+    }
+
+    /// Same as for_each_intersection_candidate, but additionally collects the bounding boxes of all traversed nodes (e.g. for debugging)
+    pub fn for_each_intersection_candidate_traced<'a>(
+        &'a self,
+        line: &Line3D,
+        f: &mut dyn FnMut(&HB),
+        traversed: Option<&mut Vec<BoundingBox3D>>,
+    ) {
         match self {
             Self::Empty => (),
-            Self::Leaf(leaf) => leaf.for_each_intersection_candidate(line, f),
-            Self::Branch(branch) => branch.for_each_intersection_candidate(line, f),
+            Self::Leaf(leaf) => leaf.for_each_intersection_candidate_traced(line, f, traversed),
+            Self::Branch(branch) => {
+                branch.for_each_intersection_candidate_traced(line, f, traversed)
+            }
+        }
+    }
+
+    /// Visits the intersection candidates in near to far order of the tree nodes along the ray.
+    /// The traversal stops as soon as f returns false
+    pub fn for_each_intersection_candidate_near_to_far<'a>(
+        &'a self,
+        ray: &Ray3D,
+        f: &mut dyn FnMut(&HB) -> bool,
+    ) -> bool {
+        match self {
+            Self::Empty => true,
+            Self::Leaf(leaf) => leaf.for_each_intersection_candidate_near_to_far(ray, f),
+            Self::Branch(branch) => branch.for_each_intersection_candidate_near_to_far(ray, f),
         }
     }
 
@@ -82,6 +126,62 @@ where
         }
     }
 
+    /// Collects all elements whose bounding box isn't completely outside the frustum, pruning whole subtrees outside of it
+    pub fn bb_in_frustum<'a>(&'a self, frustum: &Frustum, result: &mut Vec<&'a HB>) {
+        match self {
+            Self::Empty => (),
+            Self::Leaf(leaf) => leaf.bb_in_frustum(frustum, result),
+            Self::Branch(branch) => branch.bb_in_frustum(frustum, result),
+        }
+    }
+
+    /// Returns the element whose bounding box is closest to p (distance 0 if p is within the box)
+    pub fn nearest<'a>(&'a self, p: &Point3D) -> Option<&'a HB> {
+        let mut best = None;
+        self.nearest_rec(p, &mut best);
+        best.map(|(_, x)| x)
+    }
+
+    /// Returns the parameter t and the element of the first hit along the ray (anchor + t * dir with t > 0)
+    pub fn first_hit<'a>(&'a self, ray: &Line3D) -> Option<(f64, &'a HB)>
+    where
+        HB: HasTriangle3D,
+    {
+        let mut best = None;
+        self.first_hit_rec(&Ray3D::new(ray.clone()), &mut best);
+        best
+    }
+
+    fn first_hit_rec<'a>(&'a self, ray: &Ray3D, best: &mut Option<(f64, &'a HB)>)
+    where
+        HB: HasTriangle3D,
+    {
+        match self {
+            Self::Empty => (),
+            Self::Leaf(leaf) => leaf.first_hit(ray, best),
+            Self::Branch(branch) => branch.first_hit(ray, best),
+        }
+    }
+
+    fn nearest_rec<'a>(&'a self, p: &Point3D, best: &mut Option<(f64, &'a HB)>) {
+        match self {
+            Self::Empty => (),
+            Self::Leaf(leaf) => leaf.nearest(p, best),
+            Self::Branch(branch) => branch.nearest(p, best),
+        }
+    }
+
+    fn push_all<'a>(&'a self, result: &mut Vec<&'a HB>) {
+        match self {
+            Self::Empty => (),
+            Self::Leaf(leaf) => result.extend(leaf.data.iter()),
+            Self::Branch(branch) => {
+                branch.left.push_all(result);
+                branch.right.push_all(result);
+            }
+        }
+    }
+
     pub fn bb_crossing_x_value<'a>(&'a self, x: f64, result: &mut Vec<&'a HB>) {
         match self {
             Self::Empty => (),
@@ -106,7 +206,48 @@ where
         }
     }
 
-    fn new_rec(data: Vec<HB>, maxdepth: usize, allowed_bucket_size: usize, depth: usize) -> Self {
+    fn bb(&self) -> Option<&BoundingBox3D> {
+        match self {
+            Self::Empty => None,
+            Self::Leaf(leaf) => Some(&leaf.bb),
+            Self::Branch(branch) => Some(&branch.bb),
+        }
+    }
+
+    fn new_rec(
+        data: Vec<HB>,
+        maxdepth: usize,
+        allowed_bucket_size: usize,
+        strategy: &SplitStrategy,
+        depth: usize,
+    ) -> Self {
+        Self::new_node(
+            data,
+            maxdepth,
+            allowed_bucket_size,
+            strategy,
+            depth,
+            |dleft, dright| {
+                (
+                    Self::new_rec(dleft, maxdepth, allowed_bucket_size, strategy, depth + 1),
+                    Self::new_rec(dright, maxdepth, allowed_bucket_size, strategy, depth + 1),
+                )
+            },
+        )
+    }
+
+    /// Creates a leaf or splits the data and creates the children via build_children
+    fn new_node<F>(
+        data: Vec<HB>,
+        maxdepth: usize,
+        allowed_bucket_size: usize,
+        strategy: &SplitStrategy,
+        depth: usize,
+        build_children: F,
+    ) -> Self
+    where
+        F: FnOnce(Vec<HB>, Vec<HB>) -> (Self, Self),
+    {
         match data.len() {
             0 => AABBTree3D::Empty,
             1 => {
@@ -124,42 +265,95 @@ where
                         _ => Compare::Z,
                     };
                     let bb = Self::bb_of(&data).unwrap(); //unwrap fine due to early return in new and data not empty
-                    let center = bb.center_bb();
-
-                    let dleft = data
-                        .iter()
-                        .cloned()
-                        .filter(|x| Self::is_left_of(&comp, &x.bounding_box(), &center))
-                        .collect::<Vec<_>>();
-                    let dright = data
-                        .iter()
-                        .cloned()
-                        .filter(|x| Self::is_right_of(&comp, &x.bounding_box(), &center))
-                        .collect::<Vec<_>>();
-
-                    if (dleft.len() == dright.len()) && dleft.len() == data.len() {
-                        AABBTree3D::Leaf(AABBTree3DLeaf::new(data, bb))
-                    } else {
-                        let left = Box::new(Self::new_rec(
-                            dleft,
-                            maxdepth,
-                            allowed_bucket_size,
-                            depth + 1,
-                        ));
-                        let right = Box::new(Self::new_rec(
-                            dright,
-                            maxdepth,
-                            allowed_bucket_size,
-                            depth + 1,
-                        ));
 
-                        AABBTree3D::Branch(AABBTree3DBranch::new(left, right, bb))
+                    let split = match strategy {
+                        SplitStrategy::Center => Self::split_center(&data, &comp, &bb),
+                        SplitStrategy::Median => Self::split_median(&data, &comp),
+                    };
+
+                    match split {
+                        None => AABBTree3D::Leaf(AABBTree3DLeaf::new(data, bb)),
+                        Some((dleft, dright)) => {
+                            let (left, right) = build_children(dleft, dright);
+                            AABBTree3D::Branch(AABBTree3DBranch::new(
+                                Box::new(left),
+                                Box::new(right),
+                                bb,
+                            ))
+                        }
                     }
                 }
             }
         }
     }
 
+    fn split_center(data: &[HB], comp: &Compare, bb: &BoundingBox3D) -> Option<(Vec<HB>, Vec<HB>)> {
+        let center = bb.center_bb();
+
+        let dleft = data
+            .iter()
+            .filter(|x| Self::is_left_of(comp, &x.bounding_box(), &center))
+            .cloned()
+            .collect::<Vec<_>>();
+        let dright = data
+            .iter()
+            .filter(|x| Self::is_right_of(comp, &x.bounding_box(), &center))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if (dleft.len() == dright.len()) && dleft.len() == data.len() {
+            None
+        } else {
+            Some((dleft, dright))
+        }
+    }
+
+    fn split_median(data: &[HB], comp: &Compare) -> Option<(Vec<HB>, Vec<HB>)> {
+        // if the centers coincide along comp, try the other axes
+        let axes = match comp {
+            Compare::X => [Compare::X, Compare::Y, Compare::Z],
+            Compare::Y => [Compare::Y, Compare::Z, Compare::X],
+            Compare::Z => [Compare::Z, Compare::X, Compare::Y],
+        };
+        axes.iter()
+            .find_map(|comp| Self::split_median_along(data, comp))
+    }
+
+    fn split_median_along(data: &[HB], comp: &Compare) -> Option<(Vec<HB>, Vec<HB>)> {
+        let mut sorted = data
+            .iter()
+            .map(|x| (Self::center_value(comp, &x.bounding_box()), x))
+            .collect::<Vec<_>>();
+
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        // all centers coincide along this axis, there's no meaningful split
+        if sorted[0].0 == sorted[sorted.len() - 1].0 {
+            return None;
+        }
+
+        let median = sorted.len() / 2;
+        let dleft = sorted[..median]
+            .iter()
+            .map(|(_, x)| (*x).clone())
+            .collect::<Vec<_>>();
+        let dright = sorted[median..]
+            .iter()
+            .map(|(_, x)| (*x).clone())
+            .collect::<Vec<_>>();
+
+        Some((dleft, dright))
+    }
+
+    fn center_value(comp: &Compare, bb: &BoundingBox3D) -> f64 {
+        let center = bb.center_bb();
+        match comp {
+            Compare::X => center.x(),
+            Compare::Y => center.y(),
+            Compare::Z => center.z(),
+        }
+    }
+
     fn is_left_of(comp: &Compare, bb: &BoundingBox3D, center: &Point3D) -> bool {
         match comp {
             Compare::X => bb.min_p().x() < center.x(),
@@ -189,6 +383,89 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+/// Minimum number of elements of a node for its children to be built in parallel
+const PARALLEL_THRESHOLD: usize = 10_000;
+
+#[cfg(feature = "rayon")]
+impl<HB> AABBTree3D<HB>
+where
+    HB: HasBoundingBox3D + Clone + Send,
+{
+    /// Same as new, but builds the subtrees of large nodes in parallel. The resulting tree is identical
+    pub fn new_parallel(data: Vec<HB>, maxdepth: usize, allowed_bucket_size: usize) -> Self {
+        Self::new_parallel_with_strategy(data, maxdepth, allowed_bucket_size, SplitStrategy::Center)
+    }
+
+    /// Same as new_with_strategy, but builds the subtrees of large nodes in parallel. The resulting tree is identical
+    pub fn new_parallel_with_strategy(
+        data: Vec<HB>,
+        maxdepth: usize,
+        allowed_bucket_size: usize,
+        strategy: SplitStrategy,
+    ) -> Self {
+        Self::new_rec_parallel(data, maxdepth, allowed_bucket_size, &strategy, 0)
+    }
+
+    fn new_rec_parallel(
+        data: Vec<HB>,
+        maxdepth: usize,
+        allowed_bucket_size: usize,
+        strategy: &SplitStrategy,
+        depth: usize,
+    ) -> Self {
+        Self::new_node(
+            data,
+            maxdepth,
+            allowed_bucket_size,
+            strategy,
+            depth,
+            |dleft, dright| {
+                // below the threshold the overhead of spawning outweighs the gain
+                if dleft.len() + dright.len() < PARALLEL_THRESHOLD {
+                    return (
+                        Self::new_rec(dleft, maxdepth, allowed_bucket_size, strategy, depth + 1),
+                        Self::new_rec(dright, maxdepth, allowed_bucket_size, strategy, depth + 1),
+                    );
+                }
+                rayon::join(
+                    || {
+                        Self::new_rec_parallel(
+                            dleft,
+                            maxdepth,
+                            allowed_bucket_size,
+                            strategy,
+                            depth + 1,
+                        )
+                    },
+                    || {
+                        Self::new_rec_parallel(
+                            dright,
+                            maxdepth,
+                            allowed_bucket_size,
+                            strategy,
+                            depth + 1,
+                        )
+                    },
+                )
+            },
+        )
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Whether sqr_dist is smaller than the one of the best candidate so far
+fn is_closer<HB>(sqr_dist: f64, best: &Option<(f64, &HB)>) -> bool {
+    best.as_ref()
+        .is_none_or(|(best_sqr_dist, _)| sqr_dist < *best_sqr_dist)
+}
+
+/// Whether the ray enters the bounding box before the best hit so far
+fn is_hit_closer<HB>(ray: &Ray3D, bb: &BoundingBox3D, best: &Option<(f64, &HB)>) -> bool {
+    ray_entry(ray, bb).is_some_and(|t| best.as_ref().is_none_or(|(best_t, _)| t <= *best_t))
+}
+
 //------------------------------------------------------------------------------
 
 enum Compare {
@@ -231,6 +508,18 @@ where
     }
 
     pub fn for_each_intersection_candidate<'a>(&'a self, line: &Line3D, f: &mut dyn FnMut(&HB)) {
+        self.for_each_intersection_candidate_traced(line, f, None)
+    }
+
+    pub fn for_each_intersection_candidate_traced<'a>(
+        &'a self,
+        line: &Line3D,
+        f: &mut dyn FnMut(&HB),
+        traversed: Option<&mut Vec<BoundingBox3D>>,
+    ) {
+        if let Some(traversed) = traversed {
+            traversed.push(self.bb.clone());
+        }
         if intersection(line, &self.bb).is_none() {
             return;
         }
@@ -241,6 +530,21 @@ where
         }
     }
 
+    pub fn for_each_intersection_candidate_near_to_far<'a>(
+        &'a self,
+        ray: &Ray3D,
+        f: &mut dyn FnMut(&HB) -> bool,
+    ) -> bool {
+        let mut candidates: Vec<(f64, &HB)> = self
+            .data
+            .iter()
+            .filter_map(|x| ray_entry(ray, &x.bounding_box()).map(|t| (t, x)))
+            .collect();
+        candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        candidates.into_iter().all(|(_, x)| f(x))
+    }
+
     pub fn for_each_collision_candidate<'a>(&'a self, bb: &BoundingBox3D, f: &mut dyn FnMut(&HB)) {
         if !self.bb.collides_with(bb) {
             return;
@@ -262,6 +566,48 @@ where
         }
     }
 
+    pub fn first_hit<'a>(&'a self, ray: &Ray3D, best: &mut Option<(f64, &'a HB)>)
+    where
+        HB: HasTriangle3D,
+    {
+        if !is_hit_closer(ray, &self.bb, best) {
+            return;
+        }
+        for x in self.data.iter() {
+            if let Some(t) = intersection_line_triangle_parameter(&ray.line, &x.triangle()) {
+                if t > 0.0 && is_closer(t, best) {
+                    *best = Some((t, x));
+                }
+            }
+        }
+    }
+
+    pub fn nearest<'a>(&'a self, p: &Point3D, best: &mut Option<(f64, &'a HB)>) {
+        if !is_closer(*self.bb.sqr_distance(p), best) {
+            return;
+        }
+        for x in self.data.iter() {
+            let sqr_dist = *x.bounding_box().sqr_distance(p);
+            if is_closer(sqr_dist, best) {
+                *best = Some((sqr_dist, x));
+            }
+        }
+    }
+
+    pub fn bb_in_frustum<'a>(&'a self, frustum: &Frustum, result: &mut Vec<&'a HB>) {
+        match frustum.intersects_bb(&self.bb) {
+            Intersection::Outside => (),
+            Intersection::Inside => result.extend(self.data.iter()),
+            Intersection::Partial => {
+                for x in self.data.iter() {
+                    if frustum.intersects_bb(&x.bounding_box()) != Intersection::Outside {
+                        result.push(x)
+                    }
+                }
+            }
+        }
+    }
+
     pub fn bb_crossing_x_value<'a>(&'a self, x: f64, result: &mut Vec<&'a HB>) {
         if self.bb.crossing_x_value(x) {
             for d in self.data.iter() {
@@ -325,12 +671,59 @@ where
     }
 
     pub fn for_each_intersection_candidate<'a>(&'a self, line: &Line3D, f: &mut dyn FnMut(&HB)) {
+        self.for_each_intersection_candidate_traced(line, f, None)
+    }
+
+    pub fn for_each_intersection_candidate_traced<'a>(
+        &'a self,
+        line: &Line3D,
+        f: &mut dyn FnMut(&HB),
+        mut traversed: Option<&mut Vec<BoundingBox3D>>,
+    ) {
+        if let Some(traversed) = traversed.as_deref_mut() {
+            traversed.push(self.bb.clone());
+        }
         if intersection(line, &self.bb).is_none() {
             return;
         }
 
-        self.left.for_each_intersection_candidate(line, f);
-        self.right.for_each_intersection_candidate(line, f);
+        self.left
+            .for_each_intersection_candidate_traced(line, f, traversed.as_deref_mut());
+        self.right
+            .for_each_intersection_candidate_traced(line, f, traversed);
+    }
+
+    pub fn for_each_intersection_candidate_near_to_far<'a>(
+        &'a self,
+        ray: &Ray3D,
+        f: &mut dyn FnMut(&HB) -> bool,
+    ) -> bool {
+        let (left_bb, right_bb) = match (self.left.bb(), self.right.bb()) {
+            (Some(l), Some(r)) => (l, r),
+            // an empty child can't contain any candidates
+            (Some(_), None) => {
+                return self
+                    .left
+                    .for_each_intersection_candidate_near_to_far(ray, f)
+            }
+            (None, Some(_)) => {
+                return self
+                    .right
+                    .for_each_intersection_candidate_near_to_far(ray, f)
+            }
+            (None, None) => return true,
+        };
+
+        for (child, _) in which_child_first(ray, left_bb, right_bb).iter().flatten() {
+            let node = match child {
+                TreeChild::Left => &self.left,
+                TreeChild::Right => &self.right,
+            };
+            if !node.for_each_intersection_candidate_near_to_far(ray, f) {
+                return false;
+            }
+        }
+        true
     }
 
     pub fn for_each_collision_candidate<'a>(&'a self, bb: &BoundingBox3D, f: &mut dyn FnMut(&HB)) {
@@ -349,6 +742,61 @@ where
         }
     }
 
+    pub fn first_hit<'a>(&'a self, ray: &Ray3D, best: &mut Option<(f64, &'a HB)>)
+    where
+        HB: HasTriangle3D,
+    {
+        if !is_hit_closer(ray, &self.bb, best) {
+            return;
+        }
+
+        let (left_bb, right_bb) = match (self.left.bb(), self.right.bb()) {
+            (Some(l), Some(r)) => (l, r),
+            _ => {
+                self.left.first_hit_rec(ray, best);
+                self.right.first_hit_rec(ray, best);
+                return;
+            }
+        };
+
+        // the farther child is pruned within its own call if the nearer one contains a closer hit
+        for (child, _) in which_child_first(ray, left_bb, right_bb).iter().flatten() {
+            match child {
+                TreeChild::Left => self.left.first_hit_rec(ray, best),
+                TreeChild::Right => self.right.first_hit_rec(ray, best),
+            }
+        }
+    }
+
+    pub fn nearest<'a>(&'a self, p: &Point3D, best: &mut Option<(f64, &'a HB)>) {
+        if !is_closer(*self.bb.sqr_distance(p), best) {
+            return;
+        }
+
+        let sqr_dist = |node: &AABBTree3D<HB>| node.bb().map(|bb| *bb.sqr_distance(p));
+        let (first, second) = match (sqr_dist(&self.left), sqr_dist(&self.right)) {
+            (Some(l), Some(r)) if r < l => (&self.right, &self.left),
+            _ => (&self.left, &self.right),
+        };
+        // the second child is pruned within its own call if the first one found something closer
+        first.nearest_rec(p, best);
+        second.nearest_rec(p, best);
+    }
+
+    pub fn bb_in_frustum<'a>(&'a self, frustum: &Frustum, result: &mut Vec<&'a HB>) {
+        match frustum.intersects_bb(&self.bb) {
+            Intersection::Outside => (),
+            Intersection::Inside => {
+                self.left.push_all(result);
+                self.right.push_all(result);
+            }
+            Intersection::Partial => {
+                self.left.bb_in_frustum(frustum, result);
+                self.right.bb_in_frustum(frustum, result);
+            }
+        }
+    }
+
     pub fn bb_crossing_x_value<'a>(&'a self, x: f64, result: &mut Vec<&'a HB>) {
         if self.bb.crossing_x_value(x) {
             self.left.bb_crossing_x_value(x, result);
@@ -0,0 +1,143 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Alignment of positions with known correspondences
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Calculates the rigid transformation (rotation and translation) which best maps the source positions onto their corresponding target positions (Kabsch algorithm).
+/// Reflections are corrected, so the result is always a proper rotation.
+/// Fails if the counts of the positions don't match, if there are fewer than three pairs or if the positions are collinear
+pub fn kabsch<P, Q>(source: &[P], target: &[Q]) -> Result<Matrix4>
+where
+    P: Is3D,
+    Q: Is3D,
+{
+    umeyama(source, target, false).map(|(m, _)| m)
+}
+
+/// Calculates the similarity transformation (uniform scale, rotation and translation) which best maps the source positions onto their corresponding target positions (Umeyama algorithm).
+/// Returns the transformation and the estimated scale. Without with_scale the scale is fixed to 1.0, which is equal to kabsch.
+/// Fails if the counts of the positions don't match, if there are fewer than three pairs or if the positions are collinear
+pub fn umeyama<P, Q>(source: &[P], target: &[Q], with_scale: bool) -> Result<(Matrix4, f64)>
+where
+    P: Is3D,
+    Q: Is3D,
+{
+    if source.len() != target.len() {
+        return Err(ErrorKind::CorrespondenceCountMismatch);
+    }
+    if source.len() < 3 {
+        return Err(ErrorKind::TooFewPoints);
+    }
+
+    let (cs, source_cov) = covariance_3x3(source);
+    let (ct, _) = covariance_3x3(target);
+    let source_variance = source_cov[0][0] + source_cov[1][1] + source_cov[2][2];
+
+    let mut h = [[0.0; 3]; 3];
+    for (s, t) in source.iter().zip(target.iter()) {
+        let p = [s.x() - cs.x, s.y() - cs.y, s.z() - cs.z];
+        let q = [t.x() - ct.x, t.y() - ct.y, t.z() - ct.z];
+        for (row, pi) in h.iter_mut().zip(p.iter()) {
+            for (value, qj) in row.iter_mut().zip(q.iter()) {
+                *value += pi * qj;
+            }
+        }
+    }
+
+    let (r, trace) = rotation_of_covariance(&h)?;
+    // source_variance can't be 0, since the positions aren't collinear
+    let scale = if with_scale {
+        trace / source_variance
+    } else {
+        1.0
+    };
+
+    let mut result = Matrix4::identity();
+    for (i, ri) in r.iter().enumerate() {
+        for (value, rij) in result.data[i].iter_mut().zip(ri.iter()) {
+            *value = scale * rij;
+        }
+        let cti = ct.position_nd(i).unwrap(); // safe since i < 3
+        result.data[i][3] = cti - scale * (ri[0] * cs.x + ri[1] * cs.y + ri[2] * cs.z);
+    }
+
+    Ok((result, scale))
+}
+
+//------------------------------------------------------------------------------
+
+/// Calculates the rotation R maximizing trace(R * H) of the covariance H = sum(p * q^T), returning R and the maximized trace.
+/// Uses the singular value decomposition H = U * S * V^T, derived from the eigen decomposition of H^T * H
+fn rotation_of_covariance(h: &[[f64; 3]; 3]) -> Result<([[f64; 3]; 3], f64)> {
+    let mut hth = [[0.0; 3]; 3];
+    for (i, row) in hth.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| h[k][i] * h[k][j]).sum();
+        }
+    }
+
+    // sorted ascending, the largest singular value comes first in v
+    let [e3, e2, e1] = eigen_symmetric_3x3(&hth);
+    let s1 = e1.0.max(0.0).sqrt();
+    let s2 = e2.0.max(0.0).sqrt();
+    if s1 == 0.0 || s2 <= 1e-10 * s1 {
+        return Err(ErrorKind::PointsCollinear);
+    }
+
+    let mut v = [e1.1, e2.1, e3.1];
+    // V must be a proper rotation, flipping the last axis negates the last singular value
+    if cross(&v[0], &v[1]).dot(&v[2]) < 0.0 {
+        v[2] = -&v[2];
+    }
+
+    let h_times = |x: &Point3D| {
+        Point3D::new(
+            h[0][0] * x.x + h[0][1] * x.y + h[0][2] * x.z,
+            h[1][0] * x.x + h[1][1] * x.y + h[1][2] * x.z,
+            h[2][0] * x.x + h[2][1] * x.y + h[2][2] * x.z,
+        )
+    };
+
+    let u1 = h_times(&v[0]) / s1;
+    let u2 = h_times(&v[1]);
+    let u2 = &u2 - &(&u1 * u1.dot(&u2));
+    let u2 = &u2 / *u2.abs();
+    let u3 = cross(&u1, &u2);
+    // signed, since U and V are both proper rotations
+    let s3 = h_times(&v[2]).dot(&u3);
+    let u = [u1, u2, u3].map(|x| [x.x, x.y, x.z]);
+    let v = v.map(|x| [x.x, x.y, x.z]);
+
+    // R = V * U^T
+    let mut r = [[0.0; 3]; 3];
+    for (i, row) in r.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| v[k][i] * u[k][j]).sum();
+        }
+    }
+
+    Ok((r, s1 + s2 + s3))
+}
@@ -0,0 +1,392 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Surface reconstruction of point clouds using the ball pivoting algorithm
+//! https://en.wikipedia.org/wiki/Ball-pivoting_algorithm
+
+use crate::{estimate_normals::normals_and_neighbours, *};
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    f64::consts::PI,
+};
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+/// ReconstructParams, parameters used to reconstruct a mesh from a point cloud
+pub struct ReconstructParams {
+    /// Number of neighbours used to estimate the normal of each position
+    pub k_normals: usize,
+    /// Radii of the pivoting ball, used in ascending order.
+    /// Should be slightly larger than the average distance between neighbouring positions
+    pub radii: Vec<Positive>,
+}
+
+impl ReconstructParams {
+    /// Creates new parameters with a single ball radius and 10 neighbours for the normal estimation
+    pub fn new(radius: Positive) -> Self {
+        Self {
+            k_normals: 10,
+            radii: vec![radius],
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Reconstructs a mesh from positions with (outwards facing) normals using the ball pivoting algorithm
+/// A ball of each of the radii (ascending) is rolled over the positions, creating a face for every three positions it touches without containing any other position
+/// The resulting faces are oriented according to the passed normals
+pub fn ball_pivoting<P, M>(
+    pc: &PointCloud3D<P>,
+    normals: &[Norm3D],
+    radii: &[Positive],
+) -> Result<M>
+where
+    P: IsBuildable3D + Clone,
+    M: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3> + Default,
+{
+    if pc.len() < 3 {
+        return Err(ErrorKind::TooFewPoints);
+    }
+    if normals.len() != pc.len() {
+        return Err(ErrorKind::NormalArrayIncorrectLength);
+    }
+
+    let mut bp = BallPivoting::new(
+        pc.data.iter().map(|p| Point3D::new_from(p)).collect(),
+        normals
+            .iter()
+            .map(|n| Point3D::new(n.x(), n.y(), n.z()))
+            .collect(),
+    );
+
+    let mut sorted_radii = radii.to_vec();
+    sorted_radii.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    for r in sorted_radii {
+        bp.run(*r);
+    }
+
+    let mut result = M::default();
+    result.reserve_faces(bp.triangles.len());
+
+    let mut vids = HashMap::new();
+    for tri in bp.triangles.iter() {
+        let mut face_vids = [VId(0); 3];
+        for (j, index) in tri.iter().enumerate() {
+            face_vids[j] = *vids
+                .entry(*index)
+                .or_insert_with(|| result.add_vertex(pc[*index].clone()));
+        }
+        result.try_add_connection(face_vids[0], face_vids[1], face_vids[2])?;
+    }
+
+    Ok(result)
+}
+
+/// Estimates a normal for each position from the covariance of its k nearest neighbours (see estimate_normals)
+/// The normals are oriented consistently by propagating the orientation between neighbours, starting at the highest position of each connected region (facing +z)
+pub(crate) fn estimate_oriented_normals<P>(pc: &PointCloud3D<P>, k: usize) -> Result<Vec<Norm3D>>
+where
+    P: Is3D,
+{
+    let (mut normals, neighbours) = normals_and_neighbours(pc, k)?;
+
+    let mut visited = vec![false; pc.len()];
+    let mut order: Vec<usize> = (0..pc.len()).collect();
+    order.sort_by(|a, b| {
+        pc[*b]
+            .z()
+            .partial_cmp(&pc[*a].z())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut queue = VecDeque::new();
+    for start in order {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        if normals[start].z() < 0.0 {
+            normals[start] = -&normals[start];
+        }
+        queue.push_back(start);
+
+        while let Some(i) = queue.pop_front() {
+            for j in &neighbours[i] {
+                if visited[*j] {
+                    continue;
+                }
+                visited[*j] = true;
+                if normals[i].dot(&normals[*j]) < 0.0 {
+                    normals[*j] = -&normals[*j];
+                }
+                queue.push_back(*j);
+            }
+        }
+    }
+
+    Ok(normals)
+}
+
+//------------------------------------------------------------------------------
+
+struct BallPivoting {
+    ps: Vec<Point3D>,
+    ns: Vec<Point3D>,
+    cell_size: f64,
+    grid: HashMap<(i64, i64, i64), Vec<usize>>,
+    triangles: Vec<[usize; 3]>,
+    /// Directed edges of all triangles, mapping to the opposite vertex
+    edges: HashMap<(usize, usize), usize>,
+    vertex_edges: Vec<Vec<(usize, usize)>>,
+    used: Vec<bool>,
+    front: Vec<(usize, usize)>,
+    boundary: HashSet<(usize, usize)>,
+    i_seed: usize,
+}
+
+impl BallPivoting {
+    fn new(ps: Vec<Point3D>, ns: Vec<Point3D>) -> Self {
+        let n = ps.len();
+        Self {
+            ps,
+            ns,
+            cell_size: 1.0,
+            grid: HashMap::new(),
+            triangles: Vec::new(),
+            edges: HashMap::new(),
+            vertex_edges: vec![Vec::new(); n],
+            used: vec![false; n],
+            front: Vec::new(),
+            boundary: HashSet::new(),
+            i_seed: 0,
+        }
+    }
+
+    fn run(&mut self, r: f64) {
+        self.build_grid(2.0 * r);
+        self.i_seed = 0;
+
+        // edges which couldn't be pivoted with a smaller ball are retried
+        self.front.extend(self.boundary.drain());
+
+        loop {
+            while let Some(edge) = self.front.pop() {
+                if !self.is_front(edge) {
+                    continue;
+                }
+                if !self.pivot(edge, r) {
+                    self.boundary.insert(edge);
+                }
+            }
+
+            if !self.find_seed(r) {
+                break;
+            }
+        }
+    }
+
+    fn build_grid(&mut self, cell_size: f64) {
+        self.cell_size = cell_size;
+        self.grid.clear();
+        for (i, p) in self.ps.iter().enumerate() {
+            let key = Self::cell_of(p, cell_size);
+            self.grid.entry(key).or_default().push(i);
+        }
+    }
+
+    fn cell_of(p: &Point3D, cell_size: f64) -> (i64, i64, i64) {
+        (
+            (p.x / cell_size).floor() as i64,
+            (p.y / cell_size).floor() as i64,
+            (p.z / cell_size).floor() as i64,
+        )
+    }
+
+    fn neighbours(&self, p: &Point3D, radius: f64) -> Vec<usize> {
+        let (cx, cy, cz) = Self::cell_of(p, self.cell_size);
+        let reach = (radius / self.cell_size).ceil() as i64;
+        let sqr_radius = radius * radius;
+        let mut result = Vec::new();
+        for x in (cx - reach)..=(cx + reach) {
+            for y in (cy - reach)..=(cy + reach) {
+                for z in (cz - reach)..=(cz + reach) {
+                    if let Some(cell) = self.grid.get(&(x, y, z)) {
+                        for i in cell {
+                            if sqr_dist_3d(p, &self.ps[*i]) <= sqr_radius {
+                                result.push(*i)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Center of the ball with radius r touching the oriented triangle, lying on its front side
+    fn ball_center(&self, a: usize, b: usize, c: usize, r: f64) -> Option<Point3D> {
+        let (pa, pb, pc) = (&self.ps[a], &self.ps[b], &self.ps[c]);
+        let ab = pb - pa;
+        let ac = pc - pa;
+        let n: Point3D = cross(&ab, &ac);
+        let sqr_n = n.dot(&n);
+        if sqr_n <= f64::EPSILON * ab.dot(&ab) * ac.dot(&ac) {
+            return None;
+        }
+
+        // triangle has to face the same direction as its vertex normals
+        let vertex_normals = &(&self.ns[a] + &self.ns[b]) + &self.ns[c];
+        if n.dot(&vertex_normals) <= 0.0 {
+            return None;
+        }
+
+        let to_circumcenter = (cross::<_, Point3D>(&n, &ab) * ac.dot(&ac)
+            + cross::<_, Point3D>(&ac, &n) * ab.dot(&ab))
+            / (2.0 * sqr_n);
+        let sqr_circumradius = to_circumcenter.dot(&to_circumcenter);
+        if sqr_circumradius > r * r {
+            return None;
+        }
+
+        let height = (r * r - sqr_circumradius).sqrt();
+        Some(pa + &(to_circumcenter + n * (height / sqr_n.sqrt())))
+    }
+
+    fn is_empty_ball(&self, center: &Point3D, r: f64, triangle: [usize; 3]) -> bool {
+        let max_sqr = r * r * (1.0 - 1e-9);
+        self.neighbours(center, r)
+            .into_iter()
+            .all(|i| triangle.contains(&i) || sqr_dist_3d(center, &self.ps[i]) >= max_sqr)
+    }
+
+    fn is_front(&self, (a, b): (usize, usize)) -> bool {
+        self.edges.contains_key(&(a, b)) && !self.edges.contains_key(&(b, a))
+    }
+
+    /// Whether a vertex can still become part of a new triangle
+    fn is_available(&self, v: usize) -> bool {
+        !self.used[v] || self.vertex_edges[v].iter().any(|e| self.is_front(*e))
+    }
+
+    fn add_triangle(&mut self, a: usize, b: usize, c: usize) {
+        self.triangles.push([a, b, c]);
+        for &(from, to, opposite) in &[(a, b, c), (b, c, a), (c, a, b)] {
+            self.edges.insert((from, to), opposite);
+            self.vertex_edges[from].push((from, to));
+            self.vertex_edges[to].push((from, to));
+            self.used[from] = true;
+            if !self.edges.contains_key(&(to, from)) {
+                self.front.push((from, to));
+            }
+        }
+    }
+
+    fn pivot(&mut self, (a, b): (usize, usize), r: f64) -> bool {
+        let c = self.edges[&(a, b)];
+        let old_center = match self.ball_center(a, b, c, r) {
+            Some(x) => x,
+            None => return false,
+        };
+
+        let mid = (&self.ps[a] + &self.ps[b]) * 0.5;
+        let axis = match (&self.ps[b] - &self.ps[a]).normalized() {
+            Ok(x) => x,
+            Err(_) => return false,
+        };
+        let u = &old_center - &mid;
+
+        let mut candidates = Vec::new();
+        for k in self.neighbours(&mid, 2.0 * r) {
+            if k == a || k == b || k == c || !self.is_available(k) {
+                continue;
+            }
+            if self.edges.contains_key(&(a, k)) || self.edges.contains_key(&(k, b)) {
+                continue;
+            }
+            if let Some(center) = self.ball_center(b, a, k, r) {
+                let w = &center - &mid;
+                let mut angle = axis.dot(&cross::<_, Point3D>(&u, &w)).atan2(u.dot(&w));
+                if angle < 0.0 {
+                    angle = if angle > -1e-9 { 0.0 } else { angle + 2.0 * PI };
+                }
+                candidates.push((angle, k, center));
+            }
+        }
+
+        candidates.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+
+        for (_, k, center) in candidates {
+            if self.is_empty_ball(&center, r, [a, b, k]) {
+                self.add_triangle(b, a, k);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn find_seed(&mut self, r: f64) -> bool {
+        while self.i_seed < self.ps.len() {
+            let i = self.i_seed;
+            self.i_seed += 1;
+            if self.used[i] {
+                continue;
+            }
+
+            let mut near: Vec<usize> = self
+                .neighbours(&self.ps[i], 2.0 * r)
+                .into_iter()
+                .filter(|j| *j != i && !self.used[*j])
+                .collect();
+            near.sort_by(|x, y| {
+                sqr_dist_3d(&self.ps[i], &self.ps[*x])
+                    .partial_cmp(&sqr_dist_3d(&self.ps[i], &self.ps[*y]))
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            for (ij, j) in near[..].iter().enumerate() {
+                for k in near[ij + 1..].iter() {
+                    let n: Point3D =
+                        cross(&(&self.ps[*j] - &self.ps[i]), &(&self.ps[*k] - &self.ps[i]));
+                    let (b, c) = if n.dot(&self.ns[i]) >= 0.0 {
+                        (*j, *k)
+                    } else {
+                        (*k, *j)
+                    };
+
+                    if let Some(center) = self.ball_center(i, b, c, r) {
+                        if self.is_empty_ball(&center, r, [i, b, c]) {
+                            self.add_triangle(i, b, c);
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
@@ -217,6 +217,44 @@ impl BoundingBox3D {
             z: self.min.z() + (self.max.z() - self.min.z()) / 2.0,
         }
     }
+    /// Returns the eight octants of the bounding box, split at its center.
+    /// The order is ppp, ppn, pnp, pnn, npp, npn, nnp, nnn (see Direction).
+    /// Fails if the bounding box is too small to be split
+    pub fn octants(&self) -> Result<[BoundingBox3D; 8]> {
+        let (n, m, p) = (&self.min, self.center_bb(), &self.max);
+        let octant = |x: bool, y: bool, z: bool| {
+            let (minx, maxx) = if x { (m.x, p.x) } else { (n.x, m.x) };
+            let (miny, maxy) = if y { (m.y, p.y) } else { (n.y, m.y) };
+            let (minz, maxz) = if z { (m.z, p.z) } else { (n.z, m.z) };
+            BoundingBox3D::new(
+                &Point3D::new(minx, miny, minz),
+                &Point3D::new(maxx, maxy, maxz),
+            )
+        };
+
+        Ok([
+            octant(true, true, true)?,
+            octant(true, true, false)?,
+            octant(true, false, true)?,
+            octant(true, false, false)?,
+            octant(false, true, true)?,
+            octant(false, true, false)?,
+            octant(false, false, true)?,
+            octant(false, false, false)?,
+        ])
+    }
+    /// Returns the part of the line within the bounding box, None if the line misses it
+    pub fn clip_line(&self, line: &Line3D) -> Option<LineSegment3D> {
+        let (tmin, tmax) = slab_range(line, self);
+        if tmin.is_nan() || tmax.is_nan() || tmin > tmax {
+            return None;
+        }
+
+        Some(LineSegment3D::new(
+            &line.anchor + &line.dir * tmin,
+            &line.anchor + &line.dir * tmax,
+        ))
+    }
     /// Tests whether this bounding box is within the other
     pub fn is_inside(&self, other: &BoundingBox3D) -> bool {
         self.min.x() > other.min.x()
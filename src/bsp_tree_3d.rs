@@ -0,0 +1,190 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! BspTree3D, a binary space partitioning tree for positions in 3D space
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+/// BspTree3D, a binary space partitioning tree for positions in 3D space.
+/// Nodes are split recursively along the plane through their centroid, perpendicular to their principal axis (the direction of largest variance).
+/// Splitting stops once a node holds at most max_leaf_size positions or the positions can't be separated anymore (e.g. they are all equal)
+pub struct BspTree3D<P>
+where
+    P: Is3D,
+{
+    root: Option<BspNode<P>>,
+}
+
+#[derive(Debug, Clone)]
+enum BspNode<P>
+where
+    P: Is3D,
+{
+    Leaf(Vec<P>),
+    Branch(BspBranch<P>),
+}
+
+#[derive(Debug, Clone)]
+struct BspBranch<P>
+where
+    P: Is3D,
+{
+    plane: Plane3D<Point3D, Norm3D>,
+    positive: Box<BspNode<P>>,
+    negative: Box<BspNode<P>>,
+}
+
+impl<P> BspTree3D<P>
+where
+    P: Is3D,
+{
+    /// Creates a new BspTree3D whose leaves hold at most max_leaf_size positions (at least one)
+    pub fn new(pc: PointCloud3D<P>, max_leaf_size: usize) -> Self {
+        let root = if pc.data.is_empty() {
+            None
+        } else {
+            Some(BspNode::new(pc.data, max_leaf_size.max(1)))
+        };
+        BspTree3D { root }
+    }
+    /// Returns the number of stored positions
+    pub fn size(&self) -> usize {
+        self.leaves().map(|leaf| leaf.len()).sum()
+    }
+    /// Iterates the positions of all leaves
+    pub fn leaves(&self) -> impl Iterator<Item = &[P]> {
+        let mut result = Vec::new();
+        if let Some(root) = &self.root {
+            root.collect_leaves(&mut result);
+        }
+        result.into_iter()
+    }
+    /// Returns the stored position closest to search
+    pub fn nearest<PSearch>(&self, search: &PSearch) -> Option<&P>
+    where
+        PSearch: Is3D,
+    {
+        let mut best = None;
+        if let Some(root) = &self.root {
+            root.nearest(search, &mut best);
+        }
+        best.map(|(p, _)| p)
+    }
+}
+
+impl<P> BspNode<P>
+where
+    P: Is3D,
+{
+    fn new(ps: Vec<P>, max_leaf_size: usize) -> Self {
+        if ps.len() <= max_leaf_size {
+            return BspNode::Leaf(ps);
+        }
+
+        let plane = match principal_plane(&ps) {
+            Some(x) => x,
+            None => return BspNode::Leaf(ps),
+        };
+
+        let (positive, negative): (Vec<P>, Vec<P>) = ps
+            .into_iter()
+            .partition(|p| plane.signed_distance(p) >= 0.0);
+
+        // all positions on one side, further splitting wouldn't terminate
+        if positive.is_empty() || negative.is_empty() {
+            let mut ps = positive;
+            ps.extend(negative);
+            return BspNode::Leaf(ps);
+        }
+
+        BspNode::Branch(BspBranch {
+            plane,
+            positive: Box::new(BspNode::new(positive, max_leaf_size)),
+            negative: Box::new(BspNode::new(negative, max_leaf_size)),
+        })
+    }
+
+    fn collect_leaves<'a>(&'a self, result: &mut Vec<&'a [P]>) {
+        match self {
+            Self::Leaf(ps) => result.push(ps),
+            Self::Branch(branch) => {
+                branch.positive.collect_leaves(result);
+                branch.negative.collect_leaves(result);
+            }
+        }
+    }
+
+    fn nearest<'a, PSearch>(&'a self, search: &PSearch, best: &mut Option<(&'a P, f64)>)
+    where
+        PSearch: Is3D,
+    {
+        match self {
+            Self::Leaf(ps) => {
+                for p in ps {
+                    let sqr_dist = sqr_dist_3d(search, p);
+                    if best.is_none_or(|(_, min)| sqr_dist < min) {
+                        *best = Some((p, sqr_dist));
+                    }
+                }
+            }
+            Self::Branch(branch) => {
+                let d = branch.plane.signed_distance(search);
+                let (near, far) = if d >= 0.0 {
+                    (&branch.positive, &branch.negative)
+                } else {
+                    (&branch.negative, &branch.positive)
+                };
+
+                near.nearest(search, best);
+                // the far side can only contain a closer position if the plane is closer than the current best
+                if best.is_none_or(|(_, min)| d * d < min) {
+                    far.nearest(search, best);
+                }
+            }
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// The plane through the centroid of the positions, perpendicular to their principal axis. None if they don't have any spread
+fn principal_plane<P>(ps: &[P]) -> Option<Plane3D<Point3D, Norm3D>>
+where
+    P: Is3D,
+{
+    let (centroid, covariance) = covariance_3x3(ps);
+
+    // u and v span the plane, their normal (u x v) is the principal axis
+    let [(_, u), (_, v), (largest, _)] = eigen_symmetric_3x3(&covariance);
+    if largest <= 0.0 {
+        return None;
+    }
+
+    Some(Plane3D {
+        origin: centroid,
+        u: Norm3D::new(u).ok()?,
+        v: Norm3D::new(v).ok()?,
+    })
+}
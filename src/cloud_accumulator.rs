@@ -0,0 +1,102 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! CloudAccumulator, to incrementally calculate statistics of a point cloud without storing its positions
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+#[derive(Default, Debug, Clone)]
+/// CloudAccumulator, to incrementally calculate statistics of a point cloud without storing its positions.
+/// Useful to analyze clouds while streaming them from a file
+pub struct CloudAccumulator {
+    count: usize,
+    min: Point3D,
+    max: Point3D,
+    sum: Point3D,
+}
+
+impl CloudAccumulator {
+    /// Creates a new, empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a position to the statistics
+    pub fn push(&mut self, p: &dyn Is3D) {
+        if self.count == 0 {
+            self.min = Point3D::new(p.x(), p.y(), p.z());
+            self.max = self.min.clone();
+        } else {
+            self.min.x = self.min.x.min(p.x());
+            self.min.y = self.min.y.min(p.y());
+            self.min.z = self.min.z.min(p.z());
+            self.max.x = self.max.x.max(p.x());
+            self.max.y = self.max.y.max(p.y());
+            self.max.z = self.max.z.max(p.z());
+        }
+        self.sum.x += p.x();
+        self.sum.y += p.y();
+        self.sum.z += p.z();
+        self.count += 1;
+    }
+    /// Returns the number of positions pushed so far
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// Returns the statistics of all pushed positions, fails if no position was pushed
+    pub fn finish(self) -> Result<CloudStats> {
+        if self.count == 0 {
+            return Err(ErrorKind::TooFewPoints);
+        }
+
+        let n = self.count as f64;
+        Ok(CloudStats {
+            count: self.count,
+            centroid: Point3D::new(self.sum.x / n, self.sum.y / n, self.sum.z / n),
+            min: self.min,
+            max: self.max,
+        })
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+/// CloudStats, statistics of a point cloud as calculated by CloudAccumulator
+pub struct CloudStats {
+    /// The number of positions
+    pub count: usize,
+    /// The minimum coordinates of all positions
+    pub min: Point3D,
+    /// The maximum coordinates of all positions
+    pub max: Point3D,
+    /// The average of all positions
+    pub centroid: Point3D,
+}
+
+impl CloudStats {
+    /// Returns the bounding box of all positions, fails if the positions don't span a volume
+    pub fn bounding_box(&self) -> Result<BoundingBox3D> {
+        BoundingBox3D::new(&self.min, &self.max)
+    }
+}
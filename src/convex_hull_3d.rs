@@ -0,0 +1,223 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Convex hull algorithm returning a triangulated mesh of the hull
+//! Using the incremental convex hull algorithm https://en.wikipedia.org/wiki/Convex_hull_algorithms
+
+use crate::*;
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+//------------------------------------------------------------------------------
+
+/// Convex hull algorithm returning a triangulated mesh of the hull with outwards facing faces
+/// Using the incremental convex hull algorithm https://en.wikipedia.org/wiki/Convex_hull_algorithms
+/// Returns ErrorKind::TooFewPoints if the input has less than three points or all points are collinear.
+/// If all points are coplanar, the 2D convex hull within that plane is returned as flat mesh,
+/// where each triangle is added twice (once per side)
+pub fn convex_hull_3d<RA, P, M>(ra: &RA) -> Result<M>
+where
+    RA: IsRandomAccessible<P>,
+    P: IsBuildable3D + Clone,
+    M: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3> + Default,
+{
+    let n = ra.len();
+    if n < 3 {
+        return Err(ErrorKind::TooFewPoints);
+    }
+
+    let ps: Vec<Point3D> = (0..n).map(|i| Point3D::new_from(&ra[i])).collect();
+    let eps = tolerance(&ps);
+
+    // initial simplex: two extreme points, the point furthest from their line and the point furthest from their plane
+    let i0 = (0..n)
+        .min_by(|a, b| ps[*a].x.partial_cmp(&ps[*b].x).unwrap_or(Ordering::Equal))
+        .unwrap(); // safe since n >= 3
+    let i1 = furthest(n, |i| sqr_dist_3d(&ps[i0], &ps[i]).sqrt());
+    if sqr_dist_3d(&ps[i0], &ps[i1]).sqrt() <= eps {
+        return Err(ErrorKind::TooFewPoints);
+    }
+
+    let dir = &ps[i1] - &ps[i0];
+    let i2 = furthest(n, |i| *cross::<_, Point3D>(&dir, &(&ps[i] - &ps[i0])).abs());
+    let normal: Point3D = cross(&dir, &(&ps[i2] - &ps[i0]));
+    if *normal.abs() <= eps * *dir.abs() {
+        return Err(ErrorKind::TooFewPoints);
+    }
+
+    let i3 = furthest(n, |i| normal.dot(&(&ps[i] - &ps[i0])).abs());
+    if normal.dot(&(&ps[i3] - &ps[i0])).abs() <= eps * *normal.abs() {
+        return Ok(flat_hull(&ps, &ps[i0], &dir, &normal));
+    }
+
+    let mut faces = Vec::new();
+    for &[a, b, c, opposite] in &[
+        [i0, i1, i2, i3],
+        [i0, i1, i3, i2],
+        [i0, i2, i3, i1],
+        [i1, i2, i3, i0],
+    ] {
+        let face = HullFace::new(&ps, a, b, c);
+        if face.signed_distance(&ps[opposite]) > 0.0 {
+            faces.push(HullFace::new(&ps, a, c, b));
+        } else {
+            faces.push(face);
+        }
+    }
+
+    for i in 0..n {
+        if i == i0 || i == i1 || i == i2 || i == i3 {
+            continue;
+        }
+
+        let (visible, hidden): (Vec<HullFace>, Vec<HullFace>) = faces
+            .into_iter()
+            .partition(|f| f.signed_distance(&ps[i]) > eps);
+        faces = hidden;
+
+        if visible.is_empty() {
+            continue;
+        }
+
+        let visible_edges: HashSet<(usize, usize)> =
+            visible.iter().flat_map(|f| f.edges().to_vec()).collect();
+
+        // edges of visible faces without visible neighbour form the horizon
+        for &(a, b) in visible_edges.iter() {
+            if !visible_edges.contains(&(b, a)) {
+                faces.push(HullFace::new(&ps, a, b, i));
+            }
+        }
+    }
+
+    let mut result = M::default();
+    result.reserve_faces(faces.len());
+
+    let mut vids = HashMap::new();
+    for f in faces.iter() {
+        let mut face_vids = [VId(0); 3];
+        for (j, index) in f.vertices.iter().enumerate() {
+            face_vids[j] = *vids
+                .entry(*index)
+                .or_insert_with(|| result.add_vertex(ra[*index].clone()));
+        }
+        result.try_add_connection(face_vids[0], face_vids[1], face_vids[2])?;
+    }
+
+    Ok(result)
+}
+
+//------------------------------------------------------------------------------
+
+struct HullFace {
+    vertices: [usize; 3],
+    normal: Point3D,
+    origin: Point3D,
+}
+
+impl HullFace {
+    fn new(ps: &[Point3D], a: usize, b: usize, c: usize) -> Self {
+        let normal: Point3D = cross(&(&ps[b] - &ps[a]), &(&ps[c] - &ps[a]));
+        let normal = normal.normalized().unwrap_or(normal);
+        Self {
+            vertices: [a, b, c],
+            normal,
+            origin: ps[a].clone(),
+        }
+    }
+
+    fn signed_distance(&self, p: &Point3D) -> f64 {
+        self.normal.dot(&(p - &self.origin))
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+//------------------------------------------------------------------------------
+
+fn tolerance(ps: &[Point3D]) -> f64 {
+    let mut max = 0.0f64;
+    for p in ps {
+        max = max.max(p.x.abs()).max(p.y.abs()).max(p.z.abs());
+    }
+    1e-10 * max.max(1.0)
+}
+
+fn furthest<F>(n: usize, f: F) -> usize
+where
+    F: Fn(usize) -> f64,
+{
+    (0..n)
+        .max_by(|a, b| f(*a).partial_cmp(&f(*b)).unwrap_or(Ordering::Equal))
+        .unwrap_or(0)
+}
+
+fn flat_hull<P, M>(ps: &[Point3D], origin: &Point3D, dir: &Point3D, normal: &Point3D) -> M
+where
+    P: IsBuildable3D + Clone,
+    M: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3> + Default,
+{
+    // all unwraps safe, since dir and normal were checked to have a length
+    let u = dir.normalized().unwrap();
+    let v = cross::<_, Point3D>(normal, &u).normalized().unwrap();
+
+    let mut projected = PointCloud2D::<Point2D>::with_capacity(ps.len());
+    for p in ps {
+        let diff = p - origin;
+        projected.push(Point2D::new(u.dot(&diff), v.dot(&diff)));
+    }
+
+    let hull = convex_hull_2d(&projected);
+
+    let mut result = M::default();
+    result.reserve_vertices(hull.len());
+    result.reserve_faces(2 * hull.len());
+
+    let vids: Vec<VId> = hull
+        .iter()
+        .map(|p| {
+            result.add_vertex(P::new(
+                origin.x + p.x * u.x + p.y * v.x,
+                origin.y + p.x * u.y + p.y * v.y,
+                origin.z + p.x * u.z + p.y * v.z,
+            ))
+        })
+        .collect();
+
+    for i in 1..vids.len().saturating_sub(1) {
+        // ids are valid and unique, since taken from the hull
+        result
+            .try_add_connection(vids[0], vids[i], vids[i + 1])
+            .unwrap();
+        result
+            .try_add_connection(vids[0], vids[i + 1], vids[i])
+            .unwrap();
+    }
+
+    result
+}
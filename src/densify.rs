@@ -0,0 +1,75 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Densification of point clouds by interpolating between neighbouring positions
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Seed of the random number generator used by densify_knn, fixed to keep the results reproducible
+const DENSIFY_SEED: u64 = 42;
+
+/// Densifies a point cloud by interpolating between neighbouring positions.
+/// For each position, per_point new positions are inserted at random fractions along the segments to its k nearest neighbours.
+/// Neighbours at the exact same location are ignored.
+/// The random numbers are seeded, so equal input always yields equal output.
+/// The result contains all original positions, followed by the inserted ones
+pub fn densify_knn<P>(pc: &PointCloud3D<P>, k: usize, per_point: usize) -> PointCloud3D<P>
+where
+    P: IsBuildable3D + Clone + Default,
+{
+    let mut result = pc.clone();
+    if k == 0 || per_point == 0 || pc.len() < 2 {
+        return result;
+    }
+
+    let mut tree = KdTree::<P>::default();
+    tree.build(pc.clone()).unwrap(); // safe, since pc isn't empty
+
+    result.reserve_vertices(pc.len() * per_point);
+    let mut rng = SplitMix64::new(DENSIFY_SEED);
+    let mut neighbours = Vec::with_capacity(k + 1);
+
+    for p in pc.data.iter() {
+        neighbours.clear();
+        // +1 since p itself is part of the tree
+        tree.knearest(p, k + 1, &mut neighbours);
+        neighbours.retain(|q| sqr_dist_3d(p, q) > 0.0);
+        neighbours.truncate(k);
+        if neighbours.is_empty() {
+            continue;
+        }
+
+        for _ in 0..per_point {
+            let q = &neighbours[rng.next_index(neighbours.len())];
+            let t = rng.next_f64();
+            result.push(P::new(
+                p.x() + t * (q.x() - p.x()),
+                p.y() + t * (q.y() - p.y()),
+                p.z() + t * (q.z() - p.z()),
+            ));
+        }
+    }
+
+    result
+}
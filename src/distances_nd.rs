@@ -55,3 +55,29 @@ where
     }
     Ok(result)
 }
+
+/// Returns the candidate closest to query and its squared distance to it.
+/// Candidates whose distance can't be calculated (e.g. due to mismatching dimensions) are skipped.
+/// The search stops early once a candidate at the exact position of query is found
+pub fn nearest_nd<'a, P, I>(query: &P, candidates: I) -> Option<(&'a P, f64)>
+where
+    P: IsND + 'a,
+    I: IntoIterator<Item = &'a P>,
+{
+    let mut result: Option<(&'a P, f64)> = None;
+
+    for candidate in candidates {
+        let sqr_dist = match sqr_dist_nd(query, candidate) {
+            Ok(x) => x,
+            Err(_) => continue,
+        };
+        if result.is_none_or(|(_, min)| sqr_dist < min) {
+            result = Some((candidate, sqr_dist));
+            if sqr_dist == 0.0 {
+                break;
+            }
+        }
+    }
+
+    result
+}
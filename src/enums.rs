@@ -27,3 +27,89 @@ pub enum Precision {
     P32,
     P64,
 }
+
+/// Child of a binary tree node
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TreeChild {
+    Left,
+    Right,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+/// Strategy used to split the elements of a tree into its children
+pub enum SplitStrategy {
+    /// Split at the center of the bounding box. Fast, but might result in unbalanced trees for clustered data
+    Center,
+    /// Split at the median of the elements' bounding box centers. Guarantees balanced subtrees, unless all centers coincide
+    Median,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+/// Direction of a position relative to a reference, in terms of the octant it lies in.
+/// Naming: p == positive, n == negative ||| xyz => pnp => x positive, y negative, z positive
+pub enum Direction {
+    PPP,
+    PPN,
+    PNP,
+    PNN,
+    NPP,
+    NPN,
+    NNP,
+    NNN,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+/// Selection of the indices of a random accessible collection which shall be considered at all
+pub enum IndexSelection {
+    /// Consider all indices
+    #[default]
+    All,
+    /// Consider every step-th index, starting at offset. A step of 0 is treated as 1
+    Stride { step: usize, offset: usize },
+    /// Consider the indices within [start, end)
+    Range { start: usize, end: usize },
+}
+
+impl IndexSelection {
+    /// Whether the index is part of the selection
+    pub fn contains(&self, index: usize) -> bool {
+        match *self {
+            Self::All => true,
+            Self::Stride { step, offset } => {
+                index >= offset && (index - offset).is_multiple_of(step.max(1))
+            }
+            Self::Range { start, end } => index >= start && index < end,
+        }
+    }
+    /// Iterates the selected indices of a collection with n elements
+    pub fn indices(&self, n: usize) -> Box<dyn Iterator<Item = usize>> {
+        match *self {
+            Self::All => Box::new(0..n),
+            Self::Stride { step, offset } => Box::new((offset..n).step_by(step.max(1))),
+            Self::Range { start, end } => Box::new(start..end.min(n)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+/// Selection of the edges of a mesh
+pub enum EdgeSelection {
+    /// All edges
+    #[default]
+    All,
+    /// Edges used by a single face only
+    Boundary,
+    /// Edges between two faces whose dihedral angle (in radians) exceeds the threshold
+    Crease(f64),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+/// Relation of a volume to another one
+pub enum Intersection {
+    /// Completely inside
+    Inside,
+    /// Completely outside
+    Outside,
+    /// Partially inside and outside
+    Partial,
+}
@@ -0,0 +1,134 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Estimation of normals of point clouds via the principal components of local neighbourhoods
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Estimates a normal for each position from the covariance of its k nearest neighbours (the eigenvector of the smallest eigenvalue).
+/// The normals are not oriented consistently. Degenerate neighbourhoods (fewer than 3 distinct positions) result in Norm3D::norm_z()
+/// Fails for an empty point cloud
+pub fn estimate_normals<P>(pc: &PointCloud3D<P>, k: usize) -> Result<Vec<Norm3D>>
+where
+    P: Is3D,
+{
+    normals_and_neighbours(pc, k).map(|(normals, _)| normals)
+}
+
+//------------------------------------------------------------------------------
+
+/// Estimates the normal of each position as in estimate_normals, additionally returning the indices of the used neighbours
+pub(crate) fn normals_and_neighbours<P>(
+    pc: &PointCloud3D<P>,
+    k: usize,
+) -> Result<(Vec<Norm3D>, Vec<Vec<usize>>)>
+where
+    P: Is3D,
+{
+    // the indices are stored within the tree, so equal positions can still be told apart
+    let mut tree = KdTree::<IndexedPosition>::default();
+    tree.build(PointCloud3D::from(
+        pc.data
+            .iter()
+            .enumerate()
+            .map(|(index, p)| IndexedPosition {
+                pos: Point3D::new_from(p),
+                index,
+            })
+            .collect::<Vec<_>>(),
+    ))?;
+
+    let mut normals = Vec::with_capacity(pc.len());
+    let mut neighbours = Vec::with_capacity(pc.len());
+    let mut buffer = Vec::new();
+
+    for p in pc.data.iter() {
+        buffer.clear();
+        tree.knearest(p, k.max(3), &mut buffer);
+
+        normals.push(normal_of_neighbourhood(&buffer));
+        neighbours.push(buffer.iter().map(|x| x.index).collect::<Vec<_>>());
+    }
+
+    Ok((normals, neighbours))
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, PartialEq)]
+/// IndexedPosition, a position together with its index within the point cloud
+struct IndexedPosition {
+    pos: Point3D,
+    index: usize,
+}
+
+impl IsND for IndexedPosition {
+    fn n_dimensions() -> usize {
+        3
+    }
+
+    fn position_nd(&self, dimension: usize) -> Option<f64> {
+        self.pos.position_nd(dimension)
+    }
+}
+
+impl Is3D for IndexedPosition {
+    #[inline(always)]
+    fn x(&self) -> f64 {
+        self.pos.x
+    }
+    #[inline(always)]
+    fn y(&self) -> f64 {
+        self.pos.y
+    }
+    #[inline(always)]
+    fn z(&self) -> f64 {
+        self.pos.z
+    }
+}
+
+//------------------------------------------------------------------------------
+
+fn normal_of_neighbourhood(ps: &[IndexedPosition]) -> Norm3D {
+    let mut distinct: Vec<&Point3D> = Vec::with_capacity(3);
+    for p in ps {
+        if distinct.len() == 3 {
+            break;
+        }
+        if !distinct.contains(&&p.pos) {
+            distinct.push(&p.pos);
+        }
+    }
+    if distinct.len() < 3 {
+        return Norm3D::norm_z();
+    }
+
+    let (_, cov) = covariance_3x3(ps);
+
+    let [(_, smallest), _, (largest_value, _)] = eigen_symmetric_3x3(&cov);
+    if largest_value <= 0.0 {
+        return Norm3D::norm_z();
+    }
+    Norm3D::new(smallest).unwrap_or(Norm3D::norm_z())
+}
@@ -0,0 +1,66 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Algorithm to extract a subset of the faces of a mesh into a new mesh
+
+use crate::*;
+
+use std::collections::HashMap;
+
+//------------------------------------------------------------------------------
+
+/// Algorithm to extract a subset of the faces of a mesh into a new mesh.
+/// Only the vertices used by the extracted faces are copied, their ids are remapped compactly in order of first use.
+/// Returns ErrorKind::IncorrectFaceID if any of the face ids is invalid
+pub fn extract_faces<M, P, EM>(mesh: &M, face_ids: &[FId]) -> Result<EM>
+where
+    M: IsMesh<P, Face3>,
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3> + Default,
+{
+    let mut result = EM::default();
+    result.reserve_faces(face_ids.len());
+
+    let mut vids = HashMap::new();
+
+    for fid in face_ids {
+        let face = mesh
+            .face_vertex_ids(*fid)
+            .ok_or(ErrorKind::IncorrectFaceID)?;
+
+        let mut new_vids = [VId(0); 3];
+        for (new_vid, old) in new_vids.iter_mut().zip([face.a, face.b, face.c].iter()) {
+            *new_vid = match vids.get(&old.0) {
+                Some(vid) => *vid,
+                None => {
+                    let vid =
+                        result.add_vertex(mesh.vertex(*old).ok_or(ErrorKind::IncorrectVertexID)?);
+                    vids.insert(old.0, vid);
+                    vid
+                }
+            };
+        }
+
+        result.try_add_connection(new_vids[0], new_vids[1], new_vids[2])?;
+    }
+
+    Ok(result)
+}
@@ -37,6 +37,22 @@ impl FilterBox3D {
     pub fn new(box_3d: Box3D) -> Self {
         FilterBox3D { box_3d }
     }
+    /// Creates a new FilterBox3D from its center and sizes
+    pub fn build(center: Point3D, size_x: Positive, size_y: Positive, size_z: Positive) -> Self {
+        Self::new(Box3D {
+            center,
+            size_x,
+            size_y,
+            size_z,
+        })
+    }
+    /// Creates a new FilterBox3D covering the bounding box of the given object
+    pub fn from_bb<H>(x: &H) -> Self
+    where
+        H: HasBoundingBox3D,
+    {
+        x.bounding_box().into()
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -0,0 +1,133 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! FilterCorridor3D, a filter which allows positions within a certain distance of a polyline
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+/// FilterCorridor3D, a filter which allows positions within a certain distance of a polyline
+pub struct FilterCorridor3D {
+    polyline: Vec<Point3D>,
+    radius: Positive,
+    tree: AABBTree3D<CorridorSegment>,
+}
+
+impl FilterCorridor3D {
+    /// Creates a new FilterCorridor3D with the given polyline and radius.
+    /// A polyline with a single position results in a spherical filter, an empty one denies all positions
+    pub fn new(polyline: Vec<Point3D>, radius: Positive) -> Self {
+        let segments: Vec<CorridorSegment> = match polyline.len() {
+            0 => Vec::new(),
+            1 => vec![CorridorSegment::new(
+                LineSegment3D::new(polyline[0].clone(), polyline[0].clone()),
+                radius,
+            )],
+            _ => polyline
+                .windows(2)
+                .map(|w| {
+                    CorridorSegment::new(LineSegment3D::new(w[0].clone(), w[1].clone()), radius)
+                })
+                .collect(),
+        };
+
+        FilterCorridor3D {
+            polyline,
+            radius,
+            tree: AABBTree3D::new(segments, 16, 4),
+        }
+    }
+    /// Returns the polyline of the corridor
+    pub fn polyline(&self) -> &[Point3D] {
+        &self.polyline
+    }
+    /// Returns the radius of the corridor
+    pub fn radius(&self) -> Positive {
+        self.radius
+    }
+}
+
+impl<T> IsFilter<T> for FilterCorridor3D
+where
+    T: Is3D,
+{
+    fn is_allowed(&self, p: &T) -> bool {
+        let r = *self.radius;
+        let bb = match BoundingBox3D::new(
+            &Point3D::new(p.x() - r, p.y() - r, p.z() - r),
+            &Point3D::new(p.x() + r, p.y() + r, p.z() + r),
+        ) {
+            Ok(bb) => bb,
+            Err(_) => return false,
+        };
+
+        let mut allowed = false;
+        self.tree.for_each_collision_candidate(&bb, &mut |s| {
+            if !allowed && dist_3d(p, &s.segment.closest_point(p)) <= r {
+                allowed = true;
+            }
+        });
+        allowed
+    }
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone)]
+/// Segment of the corridor, with its bounding box grown by the radius
+struct CorridorSegment {
+    segment: LineSegment3D,
+    bb: BoundingBox3D,
+}
+
+impl CorridorSegment {
+    fn new(segment: LineSegment3D, radius: Positive) -> Self {
+        let r = *radius;
+        let min = Point3D::new(
+            segment.start.x.min(segment.end.x) - r,
+            segment.start.y.min(segment.end.y) - r,
+            segment.start.z.min(segment.end.z) - r,
+        );
+        let max = Point3D::new(
+            segment.start.x.max(segment.end.x) + r,
+            segment.start.y.max(segment.end.y) + r,
+            segment.start.z.max(segment.end.z) + r,
+        );
+        // safe since radius is positive
+        let bb = BoundingBox3D::new(&min, &max).unwrap();
+        CorridorSegment { segment, bb }
+    }
+}
+
+impl HasBoundingBox3D for CorridorSegment {
+    fn bounding_box(&self) -> BoundingBox3D {
+        self.bb.clone()
+    }
+}
+
+impl HasBoundingBox3DMaybe for CorridorSegment {
+    fn bounding_box_maybe(&self) -> Option<BoundingBox3D> {
+        Some(self.bb.clone())
+    }
+}
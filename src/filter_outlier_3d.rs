@@ -26,8 +26,6 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //! For this use the same input to build this filter as to filter against
 //! Points will find themselves, so increase the required count by 1
 
-//@todo write 2D version once KdTree also supports 2D
-
 use crate::*;
 
 use std::cell::RefCell;
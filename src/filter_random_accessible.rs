@@ -28,12 +28,14 @@ use crate::*;
 
 //------------------------------------------------------------------------------
 
-/// FilterRandomAccessible, a filter which can transform any IsFilter into an IsFilterRandomAccessible
+/// FilterRandomAccessible, a filter which can transform any IsFilter into an IsFilterRandomAccessible.
+/// Optionally only a deterministic selection of the indices is considered, e.g. to decimate huge collections
 pub struct FilterRandomAccessible<F, T>
 where
     F: IsFilter<T>,
 {
     filter: F,
+    selection: IndexSelection,
     _marker: PhantomData<T>,
 }
 
@@ -44,9 +46,19 @@ where
     pub fn new(filter: F) -> Self {
         FilterRandomAccessible {
             filter,
+            selection: IndexSelection::All,
             _marker: PhantomData,
         }
     }
+    /// Only considers the selected indices, all others are filtered out
+    pub fn with_selection(mut self, selection: IndexSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+    /// Returns the considered selection of the indices
+    pub fn selection(&self) -> IndexSelection {
+        self.selection
+    }
 }
 
 impl<F, T, RA> IsFilterRandomAccessible<RA, T> for FilterRandomAccessible<F, T>
@@ -62,8 +74,7 @@ where
         match view {
             View::Full => {
                 let mut indices = HashSet::new();
-                let n = ra.len();
-                for i in 0..n {
+                for i in self.selection.indices(ra.len()) {
                     if self.filter.is_allowed(&ra[i]) {
                         indices.insert(i);
                     }
@@ -78,7 +89,7 @@ where
                         indices_to_remove.push(*index);
                         continue;
                     }
-                    if !self.filter.is_allowed(&ra[*index]) {
+                    if !self.selection.contains(*index) || !self.filter.is_allowed(&ra[*index]) {
                         indices_to_remove.push(*index);
                     }
                 }
@@ -37,6 +37,10 @@ impl FilterSphere {
     pub fn new(sphere: Sphere) -> Self {
         FilterSphere { sphere }
     }
+    /// Creates a new FilterSphere from its center and radius
+    pub fn build(center: Point3D, radius: Positive) -> Self {
+        Self::new(Sphere { center, radius })
+    }
 }
 
 impl IsND for FilterSphere {
@@ -62,7 +66,7 @@ impl Is3D for FilterSphere {
 
     #[inline(always)]
     fn z(&self) -> f64 {
-        self.sphere.y()
+        self.sphere.z()
     }
 }
 
@@ -136,7 +140,7 @@ where
     T: Is3D,
 {
     fn is_allowed(&self, p: &T) -> bool {
-        dist_3d(p, &self.sphere.center) <= *self.sphere.radius
+        sqr_dist_3d(p, &self.sphere.center) <= *self.sphere.radius * *self.sphere.radius
     }
 }
 
@@ -0,0 +1,52 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Least squares fitting of planes to point clouds
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Fits a plane to the positions by least squares (principal component analysis of their covariance).
+/// The origin of the plane is the centroid, u and v are the directions of largest and second largest spread.
+/// Returns ErrorKind::TooFewPoints for fewer than 3 positions and ErrorKind::PointsCollinear if the positions don't span a plane
+pub fn fit_plane<P, PL, N>(pc: &PointCloud3D<P>) -> Result<PL>
+where
+    P: Is3D,
+    PL: IsPlane3D<Point3D, N>,
+    N: IsNormalized3D,
+{
+    if pc.len() < 3 {
+        return Err(ErrorKind::TooFewPoints);
+    }
+
+    let (center, cov) = covariance_3x3(&pc.data);
+
+    let [_, (second_value, second), (largest_value, largest)] = eigen_symmetric_3x3(&cov);
+
+    // relative to the largest spread, since the covariance scales with the size of the cloud
+    if largest_value <= 0.0 || second_value <= 1e-12 * largest_value {
+        return Err(ErrorKind::PointsCollinear);
+    }
+
+    Ok(PL::new(center, N::new(largest)?, N::new(second)?))
+}
@@ -0,0 +1,161 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Frustum, a convex volume bounded by 6 planes, e.g. the visible volume of a camera
+
+use crate::*;
+
+use std::collections::HashSet;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Frustum, a convex volume bounded by 6 planes, e.g. the visible volume of a camera
+pub struct Frustum {
+    planes: [FrustumPlane; 6],
+}
+
+impl Frustum {
+    /// Creates a new Frustum from 6 planes, whose normals (u x v) must point towards the inside
+    pub fn new<P, N>(planes: &[Plane3D<P, N>; 6]) -> Self
+    where
+        P: Is3D,
+        N: IsNormalized3D,
+    {
+        Frustum {
+            planes: planes.each_ref().map(|plane| {
+                let normal = plane.normal();
+                FrustumPlane {
+                    normal: [normal.x(), normal.y(), normal.z()],
+                    offset: -(normal.x() * plane.origin.x()
+                        + normal.y() * plane.origin.y()
+                        + normal.z() * plane.origin.z()),
+                }
+            }),
+        }
+    }
+    /// Creates a new Frustum from a (view-)projection matrix, covering the positions which are mapped into the clip volume [-w, w]
+    pub fn from_view_projection(m: &Matrix4) -> Result<Self> {
+        let row = |i: usize| m.data[i];
+        let combine = |a: [f64; 4], b: [f64; 4], sign: f64| {
+            [
+                a[0] + sign * b[0],
+                a[1] + sign * b[1],
+                a[2] + sign * b[2],
+                a[3] + sign * b[3],
+            ]
+        };
+
+        let mut planes = [FrustumPlane::default(); 6];
+        for (i, plane) in planes.iter_mut().enumerate() {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            let [a, b, c, d] = combine(row(3), row(i / 2), sign);
+            let length = (a * a + b * b + c * c).sqrt();
+            if length == 0.0 {
+                return Err(ErrorKind::NormalizeVecWithoutLength);
+            }
+            *plane = FrustumPlane {
+                normal: [a / length, b / length, c / length],
+                offset: d / length,
+            };
+        }
+
+        Ok(Frustum { planes })
+    }
+    /// Returns whether the position lies within the frustum (or on its border)
+    pub fn contains_point<P>(&self, p: &P) -> bool
+    where
+        P: Is3D,
+    {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance([p.x(), p.y(), p.z()]) >= 0.0)
+    }
+    /// Returns how the bounding box lies relative to the frustum.
+    /// Conservative, boxes close to the frustum's corners might be reported as Partial although lying Outside
+    pub fn intersects_bb(&self, bb: &BoundingBox3D) -> Intersection {
+        let (min, max) = (bb.min_p(), bb.max_p());
+        let (min, max) = ([min.x(), min.y(), min.z()], [max.x(), max.y(), max.z()]);
+
+        let mut result = Intersection::Inside;
+        for plane in self.planes.iter() {
+            // the corners furthest along / against the normal
+            let mut positive = min;
+            let mut negative = max;
+            for i in 0..3 {
+                if plane.normal[i] >= 0.0 {
+                    positive[i] = max[i];
+                    negative[i] = min[i];
+                }
+            }
+
+            if plane.signed_distance(positive) < 0.0 {
+                return Intersection::Outside;
+            }
+            if plane.signed_distance(negative) < 0.0 {
+                result = Intersection::Partial;
+            }
+        }
+
+        result
+    }
+}
+
+impl<P> IsFilter<P> for Frustum
+where
+    P: Is3D,
+{
+    fn is_allowed(&self, p: &P) -> bool {
+        self.contains_point(p)
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Returns a View of all positions within the frustum
+pub fn cull_cloud<RA, P>(pc: &RA, frustum: &Frustum) -> View
+where
+    RA: IsRandomAccessible<P>,
+    P: Is3D,
+{
+    let mut indices = HashSet::new();
+    for i in 0..pc.len() {
+        if frustum.contains_point(&pc[i]) {
+            indices.insert(i);
+        }
+    }
+    View::Restricted(indices)
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, PartialOrd, Default, Clone, Copy)]
+struct FrustumPlane {
+    normal: [f64; 3],
+    offset: f64,
+}
+
+impl FrustumPlane {
+    fn signed_distance(&self, p: [f64; 3]) -> f64 {
+        self.normal[0] * p[0] + self.normal[1] * p[1] + self.normal[2] * p[2] + self.offset
+    }
+}
@@ -91,6 +91,29 @@ where
     }
 }
 
+/// Calculates the direction of one position to a reference in terms of the octant it lies in.
+/// Coordinates equal to the reference's are treated as positive
+pub fn calc_direction<P1, P2>(reference: &P1, p: &P2) -> Direction
+where
+    P1: Is3D,
+    P2: Is3D,
+{
+    let px = p.x() >= reference.x();
+    let py = p.y() >= reference.y();
+    let pz = p.z() >= reference.z();
+
+    match (px, py, pz) {
+        (true, true, true) => Direction::PPP,
+        (true, true, false) => Direction::PPN,
+        (true, false, true) => Direction::PNP,
+        (true, false, false) => Direction::PNN,
+        (false, true, true) => Direction::NPP,
+        (false, true, false) => Direction::NPN,
+        (false, false, true) => Direction::NNP,
+        (false, false, false) => Direction::NNN,
+    }
+}
+
 /// Calculates the distance within a given dimension between two IsBuildable3D
 pub fn dimension_dist<P1, P2>(lhs: &P1, rhs: &P2, dim: i8) -> Result<f64>
 where
@@ -234,6 +257,89 @@ where
     }
 }
 
+/// Returns the arithmetic mean of all positions within the cloud (None if empty)
+pub fn centroid<P>(pc: &PointCloud3D<P>) -> Option<Point3D>
+where
+    P: Is3D,
+{
+    pc.center_of_gravity()
+}
+
+/// Returns a sphere enclosing all positions of the cloud, using Ritter's algorithm seeded from the bounding box
+/// The result isn't necessarily minimal.
+/// Returns None if the cloud is empty or all positions are equal, since the radius has to be positive
+pub fn bounding_sphere<P>(pc: &PointCloud3D<P>) -> Option<Sphere>
+where
+    P: Is3D,
+{
+    let first = pc.data.first()?;
+    let pos = |p: &P| Point3D::new(p.x(), p.y(), p.z());
+
+    // start at the position defining the bounding box in its largest dimension
+    let mut mins = [first; 3];
+    let mut maxs = [first; 3];
+    for p in pc.data.iter() {
+        for dim in 0..3 {
+            let val = p.position_nd(dim).unwrap(); // safe since dim < 3
+            if val < mins[dim].position_nd(dim).unwrap() {
+                mins[dim] = p;
+            }
+            if val > maxs[dim].position_nd(dim).unwrap() {
+                maxs[dim] = p;
+            }
+        }
+    }
+    let start = (0..3)
+        .map(|dim| (mins[dim], maxs[dim]))
+        .max_by(|x, y| {
+            sqr_dist_3d(x.0, x.1)
+                .partial_cmp(&sqr_dist_3d(y.0, y.1))
+                .unwrap_or(Ordering::Equal)
+        })
+        .unwrap() // safe since iterating 0..3
+        .0;
+
+    // the initial diameter is spanned by the position furthest from start and the one furthest from that
+    let furthest = |from: &P| {
+        pc.data
+            .iter()
+            .max_by(|x, y| {
+                sqr_dist_3d(from, *x)
+                    .partial_cmp(&sqr_dist_3d(from, *y))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap() // safe since pc isn't empty
+    };
+    let a = furthest(start);
+    let b = furthest(a);
+
+    let mut center = center_3d(&pos(a), &pos(b));
+    let mut radius = 0.5 * dist_3d(a, b);
+
+    // grow the sphere to include all positions
+    for p in pc.data.iter() {
+        let d = dist_3d(&center, p);
+        if d > radius {
+            let new_radius = 0.5 * (radius + d);
+            let shift = (new_radius - radius) / d;
+            center = &center + &(&conn(&center, &pos(p)) * shift);
+            radius = new_radius;
+        }
+    }
+
+    // tighten to the actual maximum distance, which also makes up for rounding errors
+    let radius = pc
+        .data
+        .iter()
+        .map(|p| dist_3d(&center, p))
+        .fold(0.0, f64::max);
+
+    Some(Sphere {
+        center,
+        radius: Positive::new(radius)?,
+    })
+}
+
 /// Collects all intersections between a ray and mesh
 pub fn collect_intersections_ray_mesh<P, M>(ray: &Ray3D, mesh: &M, intersections: &mut Vec<P>)
 where
@@ -298,6 +404,64 @@ where
     Some(P::new_from(&p))
 }
 
+/// Finds the parameter t of the intersection between a line and triangle (Möller–Trumbore), such that the intersection is anchor + t * dir.
+/// t might be negative, None if the line misses the triangle or is parallel to it
+pub fn intersection_line_triangle_parameter(line: &Line3D, triangle: &[Point3D; 3]) -> Option<f64> {
+    let [a, b, c] = triangle;
+    moller_trumbore(line, a, b, c, false).map(|(t, _, _)| t)
+}
+
+/// Finds the intersection between a ray and triangle (Möller–Trumbore).
+/// Returns the parameter t (the intersection is anchor + t * dir) and the barycentric coordinates (u, v), such that the intersection is also (1 - u - v) * a + u * b + v * c.
+/// If cull_backfaces is set, triangles whose counter-clockwise winding (a, b, c) faces away from the ray are ignored.
+/// None if the ray misses the triangle, is parallel to it or the intersection is behind the anchor (t < 0)
+pub fn intersect_ray_triangle<P>(
+    ray: &Line3D,
+    a: &P,
+    b: &P,
+    c: &P,
+    cull_backfaces: bool,
+) -> Option<(f64, f64, f64)>
+where
+    P: Is3D,
+{
+    let pos = |p: &P| Point3D::new(p.x(), p.y(), p.z());
+    moller_trumbore(ray, &pos(a), &pos(b), &pos(c), cull_backfaces).filter(|(t, _, _)| *t >= 0.0)
+}
+
+fn moller_trumbore(
+    line: &Line3D,
+    a: &Point3D,
+    b: &Point3D,
+    c: &Point3D,
+    cull_backfaces: bool,
+) -> Option<(f64, f64, f64)> {
+    let e1 = conn(a, b);
+    let e2 = conn(a, c);
+    let pv = cross(&line.dir, &e2);
+    let det = e1.dot(&pv);
+
+    // the determinant is negative for triangles facing away from the line
+    if det.abs() < 1e-12 || (cull_backfaces && det < 0.0) {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+
+    let tv = conn(a, &line.anchor);
+    let u = tv.dot(&pv) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let qv = cross(&tv, &e1);
+    let v = line.dir.dot(&qv) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some((e2.dot(&qv) * inv_det, u, v))
+}
+
 /// Applies the function to each intersection candidate
 pub fn for_each_intersecting<'c, I, HB>(
     ray: &Ray3D,
@@ -391,6 +555,15 @@ where
     result
 }
 
+/// Calculates the winding of a face given by three vertices ((v2 - v1) x (v3 - v1)).
+/// It points to the side the counter-clockwise winding faces and its length is twice the area of the face
+pub fn face_winding<P>(v1: &P, v2: &P, v3: &P) -> Point3D
+where
+    P: IsBuildable3D,
+{
+    cross(&conn(v1, v2), &Point3D::new_from(&conn(v1, v3)))
+}
+
 /// Calculates the normal of a face given by three vertices
 pub fn normal_of_face<P>(v1: &P, v2: &P, v3: &P) -> Norm3D
 where
@@ -441,6 +614,46 @@ pub fn max64(a: f64, b: f64) -> f64 {
 //@todo better location and as trait?
 /// The intersection between a line and BoundingBox if there is any
 pub fn intersection(l: &Line3D, b: &BoundingBox3D) -> Option<Point3D> {
+    let (tmin, tmax) = slab_range(l, b);
+
+    if tmax >= tmin && tmax >= 0.0 {
+        Some(&l.anchor + &l.dir * tmin)
+    } else {
+        None
+    }
+}
+
+/// The distance along the ray at which it enters the BoundingBox, if it hits it at all.
+/// Returns 0.0 if the ray starts within the box
+pub fn ray_entry(ray: &Ray3D, b: &BoundingBox3D) -> Option<f64> {
+    let (tmin, tmax) = slab_range(&ray.line, b);
+
+    if tmax >= tmin && tmax >= 0.0 {
+        Some(tmin.max(0.0))
+    } else {
+        None
+    }
+}
+
+/// Orders the children of a binary tree node by the distance at which the ray enters their bounding boxes (near to far).
+/// Children missed by the ray are None and sorted last
+pub fn which_child_first(
+    ray: &Ray3D,
+    left_bb: &BoundingBox3D,
+    right_bb: &BoundingBox3D,
+) -> [Option<(TreeChild, f64)>; 2] {
+    let left = ray_entry(ray, left_bb).map(|t| (TreeChild::Left, t));
+    let right = ray_entry(ray, right_bb).map(|t| (TreeChild::Right, t));
+
+    match (left, right) {
+        (Some(l), Some(r)) if r.1 < l.1 => [Some(r), Some(l)],
+        (None, Some(r)) => [Some(r), None],
+        _ => [left, right],
+    }
+}
+
+/// The range of the line parameter within the slabs of the BoundingBox (empty if min > max)
+pub(crate) fn slab_range(l: &Line3D, b: &BoundingBox3D) -> (f64, f64) {
     let inv_dir = [1.0 / l.dir.x(), 1.0 / l.dir.y(), 1.0 / l.dir.z()];
     let min = b.min_p();
     let max = b.max_p();
@@ -463,9 +676,85 @@ pub fn intersection(l: &Line3D, b: &BoundingBox3D) -> Option<Point3D> {
     tmin = max64(tmin, min64(tz1, tz2));
     tmax = min64(tmax, max64(tz1, tz2));
 
-    if tmax >= tmin && tmax >= 0.0 {
-        Some(&l.anchor + &l.dir * tmin)
-    } else {
-        None
+    (tmin, tmax)
+}
+
+/// Calculates the centroid of the positions and their covariance matrix (the sum of the outer products of the offsets to the centroid, not divided by the count)
+/// Returns zeros for no positions
+pub fn covariance_3x3<P>(ps: &[P]) -> (Point3D, [[f64; 3]; 3])
+where
+    P: Is3D,
+{
+    let mut centroid = Point3D::new(0.0, 0.0, 0.0);
+    let mut cov = [[0.0; 3]; 3];
+    if ps.is_empty() {
+        return (centroid, cov);
+    }
+
+    let n = ps.len() as f64;
+    for p in ps {
+        centroid.x += p.x() / n;
+        centroid.y += p.y() / n;
+        centroid.z += p.z() / n;
+    }
+
+    for p in ps {
+        let d = [p.x() - centroid.x, p.y() - centroid.y, p.z() - centroid.z];
+        for (i, row) in cov.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x += d[i] * d[j];
+            }
+        }
     }
+
+    (centroid, cov)
+}
+
+/// Calculates the eigenvalues and normalized eigenvectors of a symmetric 3x3 matrix using the Jacobi eigenvalue algorithm
+/// The result is sorted by ascending eigenvalue
+pub fn eigen_symmetric_3x3(m: &[[f64; 3]; 3]) -> [(f64, Point3D); 3] {
+    let mut a = *m;
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    for _ in 0..50 {
+        let off = a[0][1].abs() + a[0][2].abs() + a[1][2].abs();
+        let diag = a[0][0].abs() + a[1][1].abs() + a[2][2].abs();
+        if off <= f64::EPSILON * diag || off == 0.0 {
+            break;
+        }
+
+        // rotate the largest off diagonal element to zero
+        let (p, q) = if a[0][1].abs() >= a[0][2].abs() && a[0][1].abs() >= a[1][2].abs() {
+            (0, 1)
+        } else if a[0][2].abs() >= a[1][2].abs() {
+            (0, 2)
+        } else {
+            (1, 2)
+        };
+
+        let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+        let sign = if theta >= 0.0 { 1.0 } else { -1.0 };
+        let t = sign / (theta.abs() + (theta * theta + 1.0).sqrt());
+        let c = 1.0 / (t * t + 1.0).sqrt();
+        let s = t * c;
+
+        for row in a.iter_mut().chain(v.iter_mut()) {
+            let (xp, xq) = (row[p], row[q]);
+            row[p] = c * xp - s * xq;
+            row[q] = s * xp + c * xq;
+        }
+        let (rp, rq) = (a[p], a[q]);
+        for (k, (xp, xq)) in rp.iter().zip(rq.iter()).enumerate() {
+            a[p][k] = c * xp - s * xq;
+            a[q][k] = s * xp + c * xq;
+        }
+    }
+
+    let mut result = [
+        (a[0][0], Point3D::new(v[0][0], v[1][0], v[2][0])),
+        (a[1][1], Point3D::new(v[0][1], v[1][1], v[2][1])),
+        (a[2][2], Point3D::new(v[0][2], v[1][2], v[2][2])),
+    ];
+    result.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+    result
 }
@@ -0,0 +1,196 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Gilbert–Johnson–Keerthi (GJK) distance and overlap checks between convex point sets
+//! https://en.wikipedia.org/wiki/Gilbert%E2%80%93Johnson%E2%80%93Keerthi_distance_algorithm
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Maximum number of GJK iterations before the current estimate is returned
+const MAX_ITERATIONS: usize = 64;
+
+/// Relative tolerance used to detect convergence and touching / overlapping sets
+const TOLERANCE: f64 = 1e-10;
+
+//------------------------------------------------------------------------------
+
+/// Calculates the distance between the convex hulls of two point sets using GJK.
+/// Returns 0.0 if the hulls overlap or touch and f64::INFINITY if either set is empty.
+/// Iterates at most 64 times, convergence is reached once an iteration improves the squared distance by less than 1e-10 (relative)
+pub fn gjk_distance(a: &[Point3D], b: &[Point3D]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return f64::INFINITY;
+    }
+
+    let mut v = &a[0] - &b[0];
+    let mut simplex: Vec<Point3D> = Vec::with_capacity(4);
+
+    for _ in 0..MAX_ITERATIONS {
+        let sqr_v = v.dot(&v);
+        if sqr_v <= TOLERANCE * TOLERANCE {
+            return 0.0;
+        }
+
+        let w = support(a, b, &(-&v));
+
+        // no further progress towards the origin possible
+        if sqr_v - v.dot(&w) <= TOLERANCE * sqr_v {
+            return sqr_v.sqrt();
+        }
+
+        simplex.push(w);
+        let (closest, reduced) = closest_on_simplex(&simplex);
+
+        // origin enclosed by tetrahedron
+        if reduced.len() == 4 {
+            return 0.0;
+        }
+
+        simplex = reduced;
+        v = closest;
+    }
+
+    *v.abs()
+}
+
+/// Checks whether the convex hulls of two point sets overlap or touch using GJK.
+/// See gjk_distance for the used iteration cap and tolerance
+pub fn gjk_intersect(a: &[Point3D], b: &[Point3D]) -> bool {
+    gjk_distance(a, b) == 0.0
+}
+
+//------------------------------------------------------------------------------
+
+fn furthest_along<'a>(xs: &'a [Point3D], dir: &Point3D) -> &'a Point3D {
+    let mut result = &xs[0];
+    let mut max = result.dot(dir);
+    for x in xs.iter().skip(1) {
+        let d = x.dot(dir);
+        if d > max {
+            max = d;
+            result = x;
+        }
+    }
+    result
+}
+
+/// Support point of the Minkowski difference a - b
+fn support(a: &[Point3D], b: &[Point3D], dir: &Point3D) -> Point3D {
+    furthest_along(a, dir) - furthest_along(b, &(-dir))
+}
+
+/// Returns the point of the simplex closest to the origin and the smallest sub simplex containing it
+fn closest_on_simplex(simplex: &[Point3D]) -> (Point3D, Vec<Point3D>) {
+    let n = simplex.len();
+    let mut best: Option<(f64, Point3D, Vec<Point3D>)> = None;
+
+    // iterate all non-empty subsets, the closest valid projection is the closest point
+    for mask in 1..(1usize << n) {
+        let subset: Vec<Point3D> = (0..n)
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| simplex[i].clone())
+            .collect();
+
+        if let Some(p) = project_origin(&subset) {
+            let sqr = p.dot(&p);
+            let better = match &best {
+                None => true,
+                Some((min, _, _)) => sqr < *min,
+            };
+            if better {
+                best = Some((sqr, p, subset));
+            }
+        }
+    }
+
+    // safe, since single points are always valid projections
+    let (_, p, subset) = best.unwrap();
+    (p, subset)
+}
+
+/// Projects the origin onto the affine hull of the points.
+/// Returns None if the projection lies outside the hull of the points or the points are degenerate
+fn project_origin(ps: &[Point3D]) -> Option<Point3D> {
+    let k = ps.len() - 1;
+    if k == 0 {
+        return Some(ps[0].clone());
+    }
+
+    let edges: Vec<Point3D> = ps.iter().skip(1).map(|p| p - &ps[0]).collect();
+
+    // normal equations of the least squares problem min |p0 + sum(l_i * e_i)|
+    let mut m = [[0.0; 4]; 3];
+    for i in 0..k {
+        for j in 0..k {
+            m[i][j] = edges[i].dot(&edges[j]);
+        }
+        m[i][k] = -edges[i].dot(&ps[0]);
+    }
+
+    let lambdas = solve(&mut m, k)?;
+
+    let sum: f64 = lambdas[..k].iter().sum();
+    if sum >= 1.0 || lambdas[..k].iter().any(|l| *l <= 0.0) {
+        return None;
+    }
+
+    let mut result = ps[0].clone();
+    for i in 0..k {
+        result = result + &edges[i] * lambdas[i];
+    }
+    Some(result)
+}
+
+/// Solves the k x k system in m (last column being the right hand side) via Gaussian elimination
+fn solve(m: &mut [[f64; 4]; 3], k: usize) -> Option<[f64; 3]> {
+    for col in 0..k {
+        let pivot = (col..k).max_by(|a, b| {
+            m[*a][col]
+                .abs()
+                .partial_cmp(&m[*b][col].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+
+        if m[pivot][col].abs() <= TOLERANCE * TOLERANCE {
+            return None;
+        }
+        m.swap(col, pivot);
+
+        let pivot_row = m[col];
+        for (row, values) in m.iter_mut().enumerate().take(k) {
+            if row != col {
+                let factor = values[col] / pivot_row[col];
+                for c in col..=k {
+                    values[c] -= factor * pivot_row[c];
+                }
+            }
+        }
+    }
+
+    let mut result = [0.0; 3];
+    for i in 0..k {
+        result[i] = m[i][k] / m[i][i];
+    }
+    Some(result)
+}
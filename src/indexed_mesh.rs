@@ -0,0 +1,100 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Conversions between meshes and their indexed form (positions and index triples), as used by renderers and array based interfaces
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Positions and the vertex index triple of each face
+pub type IndexedMesh = (Vec<[f64; 3]>, Vec<[u32; 3]>);
+
+/// Converts a mesh to its positions and the vertex index triple of each face.
+/// Fails if the vertex count exceeds the range of u32
+pub fn to_indexed<M, P>(mesh: &M) -> Result<IndexedMesh>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    if mesh.num_vertices() > u32::MAX as usize {
+        return Err(ErrorKind::NumberConversionError);
+    }
+
+    let positions = (0..mesh.num_vertices())
+        .map(|i| {
+            mesh.vertex(VId(i))
+                .map(|p| p.xyz())
+                .ok_or(ErrorKind::IncorrectVertexID)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let indices = (0..mesh.num_faces())
+        .map(|i| {
+            let f = mesh
+                .face_vertex_ids(FId(i))
+                .ok_or(ErrorKind::IncorrectFaceID)?;
+            Ok([f.a.0 as u32, f.b.0 as u32, f.c.0 as u32])
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((positions, indices))
+}
+
+/// Appends positions and faces given as vertex index triples to a mesh.
+/// The indices refer to positions, not to vertices already within the mesh.
+/// All indices are validated before the mesh is changed, failing with IndexOutOfBounds or FaceIDsNotUnique
+pub fn from_indexed<EM, P>(
+    positions: &[[f64; 3]],
+    indices: &[[u32; 3]],
+    mesh: &mut EM,
+) -> Result<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    for [a, b, c] in indices {
+        if [a, b, c].iter().any(|i| **i as usize >= positions.len()) {
+            return Err(ErrorKind::IndexOutOfBounds);
+        }
+        if a == b || a == c || b == c {
+            return Err(ErrorKind::FaceIDsNotUnique);
+        }
+    }
+
+    let offset = mesh.num_vertices();
+    mesh.reserve_vertices(positions.len());
+    mesh.reserve_faces(indices.len());
+
+    for [x, y, z] in positions {
+        mesh.add_vertex(P::new(*x, *y, *z));
+    }
+    for [a, b, c] in indices {
+        mesh.try_add_connection(
+            VId(offset + *a as usize),
+            VId(offset + *b as usize),
+            VId(offset + *c as usize),
+        )?;
+    }
+
+    Ok(())
+}
@@ -283,6 +283,8 @@ impl fmt::Display for GcodeError {
     }
 }
 
+impl std::error::Error for GcodeError {}
+
 impl From<ioError> for GcodeError {
     fn from(_error: ioError) -> Self {
         GcodeError::AccessFile
@@ -97,7 +97,7 @@ where
             return None;
         }
         if self.header.is_none() {
-            if let Ok(header) = load_header(&mut self.read).and_then(|x| Header::try_from(x)) {
+            if let Ok(header) = load_las_header(&mut self.read).and_then(Header::try_from) {
                 if let Ok(_) = self
                     .read
                     .seek(SeekFrom::Start(header.offset_point_data as u64))
@@ -159,7 +159,8 @@ where
 
 //------------------------------------------------------------------------------
 
-fn load_header<R>(mut read: R) -> LasResult<HeaderRaw>
+/// Loads the raw header of a .las file
+pub fn load_las_header<R>(mut read: R) -> LasResult<HeaderRaw>
 where
     R: Read,
 {
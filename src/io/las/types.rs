@@ -123,6 +123,21 @@ pub struct HeaderRaw {
     pub n_points_return: [u64; 15],          //120 375
 }
 
+impl HeaderRaw {
+    /// The file signature, which should be "LASF"
+    pub fn file_signature_string(&self) -> String {
+        lossy_string(&self.file_signature)
+    }
+    /// The system identifier, without trailing zeros
+    pub fn system_identifier_string(&self) -> String {
+        lossy_string(&self.system_identifier)
+    }
+    /// The name of the generating software, without trailing zeros
+    pub fn generating_software_string(&self) -> String {
+        lossy_string(&self.generating_software)
+    }
+}
+
 //------------------------------------------------------------------------------
 
 #[derive(Debug, Default, Clone)]
@@ -172,6 +187,8 @@ impl fmt::Display for LasError {
     }
 }
 
+impl std::error::Error for LasError {}
+
 /// Result type for .las file operation
 pub type LasResult<T> = std::result::Result<T, LasError>;
 
@@ -192,3 +209,11 @@ impl From<FromBytesError> for LasError {
         LasError::BinaryData
     }
 }
+
+//------------------------------------------------------------------------------
+
+/// Converts the zero padded char array of the header lossily to a String
+fn lossy_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|x| *x == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
@@ -52,6 +52,9 @@ pub use self::ptx::*;
 mod pts;
 pub use self::pts::*;
 
+mod pcd;
+pub use self::pcd::*;
+
 mod gcode;
 pub use self::gcode::*;
 
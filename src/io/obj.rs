@@ -25,8 +25,9 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 use crate::*;
 
 use std::{
+    collections::VecDeque,
     fmt,
-    io::{BufRead, Error as ioError},
+    io::{BufRead, Error as ioError, Write},
     iter::FusedIterator,
     marker::PhantomData,
 };
@@ -102,6 +103,7 @@ where
 //------------------------------------------------------------------------------
 
 /// Iterator to incrementally load a mesh from a .obj file
+/// Faces with more than three vertices are fan-triangulated
 pub struct ObjMeshIterator<P, R>
 where
     P: IsBuildable3D,
@@ -111,6 +113,9 @@ where
     is_done: bool,
     i_line: usize,
     line_buffer: Vec<u8>,
+    n_vertices: usize,
+    indices: Vec<usize>,
+    pending: VecDeque<[usize; 3]>,
     phantom_p: PhantomData<P>,
 }
 
@@ -125,6 +130,9 @@ where
             is_done: false,
             i_line: 0,
             line_buffer: Vec::new(),
+            n_vertices: 0,
+            indices: Vec::new(),
+            pending: VecDeque::new(),
             phantom_p: PhantomData,
         }
     }
@@ -138,6 +146,9 @@ where
     type Item = ObjIOResult<FaceData<P>>;
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(face) = self.pending.pop_front() {
+            return Some(Ok(FaceData::Face(face)));
+        }
         if self.is_done {
             return None;
         }
@@ -145,6 +156,7 @@ where
             self.i_line += 1;
 
             if line.starts_with(b"v ") {
+                self.n_vertices += 1;
                 return Some(
                     fetch_vertex(line)
                         .map(|x| FaceData::Data(x))
@@ -155,15 +167,13 @@ where
                         }),
                 );
             } else if line.starts_with(b"f ") {
-                return Some(
-                    fetch_face(line)
-                        .map(|x| FaceData::Face(x))
-                        .line(self.i_line, line)
-                        .map_err(|e| {
-                            self.is_done = true;
-                            e
-                        }),
-                );
+                if let Err(e) = fetch_face(line, self.n_vertices, &mut self.indices) {
+                    self.is_done = true;
+                    return Some(Err(e).line(self.i_line, line));
+                }
+                fan_triangulate(&self.indices, &mut self.pending);
+                // fetch_face ensures at least 3 indices and therefore one triangle
+                return self.pending.pop_front().map(|x| Ok(FaceData::Face(x)));
             }
         }
 
@@ -183,6 +193,7 @@ where
 //------------------------------------------------------------------------------
 
 /// Loads an IsMesh3D from the .obj file format
+/// Faces with more than three vertices are fan-triangulated
 pub fn load_obj_mesh<EM, P, R>(read: R, mesh: &mut EM) -> ObjIOResult<()>
 where
     EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
@@ -207,6 +218,141 @@ where
     Ok(())
 }
 
+/// Saves an IsMesh3D in the .obj file format
+pub fn save_obj_mesh<M, P, W>(write: &mut W, mesh: &M) -> ObjResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    W: Write,
+{
+    write_obj_vertices(write, mesh)?;
+
+    for i in 0..mesh.num_faces() {
+        let f = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating num_faces
+        write.write_all(format!("f {} {} {}\n", f.a.0 + 1, f.b.0 + 1, f.c.0 + 1).as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Saves an IsMesh3D in the .obj file format with one normal per vertex
+pub fn save_obj_mesh_with_vertex_normals<M, P, N, W>(
+    write: &mut W,
+    mesh: &M,
+    normals: &[N],
+) -> ObjResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    N: IsNormalized3D,
+    W: Write,
+{
+    if mesh.num_vertices() != normals.len() {
+        return Err(ObjError::NormalArrayIncorrectLength);
+    }
+
+    write_obj_vertices(write, mesh)?;
+    write_obj_normals(write, normals)?;
+
+    for i in 0..mesh.num_faces() {
+        let f = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating num_faces
+        let (a, b, c) = (f.a.0 + 1, f.b.0 + 1, f.c.0 + 1);
+        write.write_all(format!("f {}//{} {}//{} {}//{}\n", a, a, b, b, c, c).as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Saves an IsMesh3D in the .obj file format with one normal per face
+pub fn save_obj_mesh_with_face_normals<M, P, N, W>(
+    write: &mut W,
+    mesh: &M,
+    normals: &[N],
+) -> ObjResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    N: IsNormalized3D,
+    W: Write,
+{
+    if mesh.num_faces() != normals.len() {
+        return Err(ObjError::NormalArrayIncorrectLength);
+    }
+
+    write_obj_vertices(write, mesh)?;
+    write_obj_normals(write, normals)?;
+
+    for i in 0..mesh.num_faces() {
+        let f = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating num_faces
+        let n = i + 1;
+        write.write_all(
+            format!(
+                "f {}//{} {}//{} {}//{}\n",
+                f.a.0 + 1,
+                n,
+                f.b.0 + 1,
+                n,
+                f.c.0 + 1,
+                n
+            )
+            .as_bytes(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Saves a ray and bounding boxes (e.g. those traversed during a query) as line elements of the .obj file format for debugging purposes.
+/// The ray is written as a single segment which reaches past all of the boxes, each box is written as wireframe of its 12 edges
+pub fn save_obj_ray_debug<W>(write: &mut W, ray: &Ray3D, boxes: &[BoundingBox3D]) -> ObjResult<()>
+where
+    W: Write,
+{
+    let anchor = &ray.line.anchor;
+    let dir = Point3D::new(ray.line.dir.x(), ray.line.dir.y(), ray.line.dir.z());
+
+    let mut length = 1.0f64;
+    for bb in boxes {
+        for corner in bb.corners().iter() {
+            length = length.max(dir.dot(&(corner - anchor)));
+        }
+    }
+    let end = anchor + &(&dir * length);
+
+    write_obj_vertex(write, anchor)?;
+    write_obj_vertex(write, &end)?;
+    write.write_all(b"l 1 2\n")?;
+
+    // corners are indexed as 4 * x + 2 * y + z, with 0 for min and 1 for max
+    const EDGES: [(usize, usize); 12] = [
+        (0, 1),
+        (2, 3),
+        (4, 5),
+        (6, 7),
+        (0, 2),
+        (1, 3),
+        (4, 6),
+        (5, 7),
+        (0, 4),
+        (1, 5),
+        (2, 6),
+        (3, 7),
+    ];
+
+    for (i, bb) in boxes.iter().enumerate() {
+        for corner in bb.corners().iter() {
+            write_obj_vertex(write, corner)?;
+        }
+        // obj indices are 1-based and the ray uses the first 2
+        let offset = 3 + 8 * i;
+        for (a, b) in EDGES.iter() {
+            write.write_all(format!("l {} {}\n", offset + a, offset + b).as_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Loads IsPushable<Is3D> from the .obj file format
 pub fn load_obj_points<IP, P, R>(read: R, ip: &mut IP) -> ObjIOResult<()>
 where
@@ -229,13 +375,15 @@ where
 pub enum ObjError {
     AccessFile,
     InvalidMeshIndices,
+    NormalArrayIncorrectLength,
     Face,
     Vertex,
 }
 
 /// Result type for .obj file operations
 pub type ObjIOResult<T> = IOResult<T, ObjError>;
-type ObjResult<T> = std::result::Result<T, ObjError>;
+/// Result type for .obj file operations
+pub type ObjResult<T> = std::result::Result<T, ObjError>;
 
 impl fmt::Debug for ObjError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -244,6 +392,9 @@ impl fmt::Debug for ObjError {
             Self::Face => write!(f, "Unable to parse face"),
             Self::Vertex => write!(f, "Unable to parse vertex"),
             Self::InvalidMeshIndices => write!(f, "File contains invalid mesh indices"),
+            Self::NormalArrayIncorrectLength => {
+                write!(f, "The provided normal array has an incorrect length")
+            }
         }
     }
 }
@@ -254,6 +405,8 @@ impl fmt::Display for ObjError {
     }
 }
 
+impl std::error::Error for ObjError {}
+
 impl From<ioError> for ObjError {
     fn from(_error: ioError) -> Self {
         ObjError::AccessFile
@@ -262,6 +415,39 @@ impl From<ioError> for ObjError {
 
 //------------------------------------------------------------------------------
 
+fn write_obj_vertex<P, W>(write: &mut W, p: &P) -> ObjResult<()>
+where
+    P: Is3D,
+    W: Write,
+{
+    write.write_all(format!("v {} {} {}\n", p.x(), p.y(), p.z()).as_bytes())?;
+    Ok(())
+}
+
+fn write_obj_vertices<M, P, W>(write: &mut W, mesh: &M) -> ObjResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    W: Write,
+{
+    for i in 0..mesh.num_vertices() {
+        let v = mesh.vertex(VId(i)).unwrap(); // safe since iterating num_vertices
+        write_obj_vertex(write, &v)?;
+    }
+    Ok(())
+}
+
+fn write_obj_normals<N, W>(write: &mut W, normals: &[N]) -> ObjResult<()>
+where
+    N: IsNormalized3D,
+    W: Write,
+{
+    for n in normals {
+        write.write_all(format!("vn {} {} {}\n", n.x(), n.y(), n.z()).as_bytes())?;
+    }
+    Ok(())
+}
+
 #[inline(always)]
 fn fetch_vertex<P>(line: &[u8]) -> ObjResult<P>
 where
@@ -290,23 +476,32 @@ where
     Ok(P::new(x, y, z))
 }
 
+/// Parses the indices of a face line, supporting the forms 'a', 'a/t', 'a/t/n' and 'a//n'
+/// Negative indices are relative to the n_vertices read so far
 #[inline(always)]
-fn fetch_face(line: &[u8]) -> ObjResult<[usize; 3]> {
+fn fetch_face(line: &[u8], n_vertices: usize, indices: &mut Vec<usize>) -> ObjResult<()> {
+    indices.clear();
     let mut words = to_words_skip_empty(line);
 
     // skip "f"
     words.next().ok_or(ObjError::Face)?;
 
-    let mut tmp = words.next().ok_or(ObjError::Face)?;
-    let a: usize = from_ascii(until_bytes(tmp, b'/')).ok_or(ObjError::Face)?;
-
-    tmp = words.next().ok_or(ObjError::Face)?;
-    let b: usize = from_ascii(until_bytes(tmp, b'/')).ok_or(ObjError::Face)?;
+    for word in words {
+        let index: i64 = from_ascii(until_bytes(word, b'/')).ok_or(ObjError::Face)?;
+        //obj indexing starts at 1
+        let index = match index {
+            i if i > 0 => i as usize - 1,
+            i if i < 0 && i.unsigned_abs() as usize <= n_vertices => {
+                n_vertices - i.unsigned_abs() as usize
+            }
+            _ => return Err(ObjError::InvalidMeshIndices),
+        };
+        indices.push(index);
+    }
 
-    tmp = words.next().ok_or(ObjError::Face)?;
-    let c: usize = from_ascii(until_bytes(tmp, b'/')).ok_or(ObjError::Face)?;
+    if indices.len() < 3 {
+        return Err(ObjError::Face);
+    }
 
-    //@todo could fail if 0 in file
-    //obj indexing starts at 1
-    Ok([a - 1, b - 1, c - 1])
+    Ok(())
 }
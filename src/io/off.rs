@@ -25,8 +25,9 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 use crate::*;
 
 use std::{
+    collections::VecDeque,
     fmt,
-    io::{BufRead, Error as ioError},
+    io::{BufRead, Error as ioError, Write},
     iter::FusedIterator,
     marker::PhantomData,
 };
@@ -147,6 +148,7 @@ where
 //------------------------------------------------------------------------------
 
 /// Iterator to incrementally load a mesh from a .off file
+/// Faces with more than three vertices are fan-triangulated
 pub struct OffMeshIterator<P, R>
 where
     P: IsBuildable3D,
@@ -159,6 +161,8 @@ where
     off_seen: bool,
     counts: Option<[usize; 2]>,
     n_vertices_added: usize,
+    indices: Vec<usize>,
+    pending: VecDeque<[usize; 3]>,
     phantom_p: PhantomData<P>,
 }
 
@@ -176,36 +180,35 @@ where
             off_seen: false,
             counts: None,
             n_vertices_added: 0,
+            indices: Vec::new(),
+            pending: VecDeque::new(),
             phantom_p: PhantomData,
         }
     }
 
     #[inline(always)]
-    fn fetch_face(line: &[u8]) -> OffResult<[usize; 3]> {
+    fn fetch_face(line: &[u8], indices: &mut Vec<usize>) -> OffResult<()> {
         let mut words = to_words_skip_empty(line);
 
-        let count_face = words.next().ok_or(OffError::FaceVertexCount)?;
-
-        if count_face == b"3" {
-            let a = words
-                .next()
-                .and_then(|word| from_ascii(word))
-                .ok_or(OffError::Face)?;
+        let count_face: usize = words
+            .next()
+            .and_then(from_ascii)
+            .ok_or(OffError::FaceVertexCount)?;
 
-            let b = words
-                .next()
-                .and_then(|word| from_ascii(word))
-                .ok_or(OffError::Face)?;
+        if count_face < 3 {
+            return Err(OffError::FaceVertexCount);
+        }
 
-            let c = words
+        indices.clear();
+        for _ in 0..count_face {
+            let index = words
                 .next()
                 .and_then(|word| from_ascii(word))
                 .ok_or(OffError::Face)?;
-
-            Ok([a, b, c])
-        } else {
-            Err(OffError::FaceVertexCount)
+            indices.push(index);
         }
+
+        Ok(())
     }
 
     #[inline(always)]
@@ -232,6 +235,9 @@ where
     type Item = OffIOResult<FaceDataReserve<P>>;
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(face) = self.pending.pop_front() {
+            return Some(Ok(FaceDataReserve::Face(face)));
+        }
         if self.is_done {
             return None;
         }
@@ -261,27 +267,30 @@ where
             }
 
             // safe since checked above
-            return Some(
-                (if self.n_vertices_added < self.counts.unwrap()[0] {
-                    self.n_vertices_added += 1;
+            if self.n_vertices_added < self.counts.unwrap()[0] {
+                self.n_vertices_added += 1;
 
+                return Some(
                     fetch_vertex(line)
                         .map(|x| FaceDataReserve::Data(x))
                         .line(self.i_line, line)
-                } else {
-                    Self::fetch_face(line)
-                        .map(|x| FaceDataReserve::Face(x))
-                        .line(self.i_line, line)
                         .map_err(|e| {
                             self.is_done = true;
                             e
-                        })
-                })
-                .map_err(|e| {
-                    self.is_done = true;
-                    e
-                }),
-            );
+                        }),
+                );
+            }
+
+            if let Err(e) = Self::fetch_face(line, &mut self.indices) {
+                self.is_done = true;
+                return Some(Err(e).line(self.i_line, line));
+            }
+            fan_triangulate(&self.indices, &mut self.pending);
+            // fetch_face ensures at least 3 indices and therefore one triangle
+            return self
+                .pending
+                .pop_front()
+                .map(|x| Ok(FaceDataReserve::Face(x)));
         }
 
         self.is_done = true;
@@ -300,6 +309,7 @@ where
 //------------------------------------------------------------------------------
 
 /// Loads an IsMesh3D from the off file format
+/// Faces with more than three vertices are fan-triangulated
 pub fn load_off_mesh<EM, P, R>(read: R, mesh: &mut EM) -> OffIOResult<()>
 where
     EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
@@ -328,6 +338,31 @@ where
     Ok(())
 }
 
+/// Saves an IsMesh3D in the .off file format
+pub fn save_off_mesh<M, P, W>(write: &mut W, mesh: &M) -> OffResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    W: Write,
+{
+    let n_vertices = mesh.num_vertices();
+    let n_faces = mesh.num_faces();
+
+    write.write_all(format!("OFF\n{} {} 0\n", n_vertices, n_faces).as_bytes())?;
+
+    for i in 0..n_vertices {
+        let v = mesh.vertex(VId(i)).unwrap(); // safe since iterating n_vertices
+        write.write_all(format!("{} {} {}\n", v.x(), v.y(), v.z()).as_bytes())?;
+    }
+
+    for i in 0..n_faces {
+        let f = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating n_faces
+        write.write_all(format!("3 {} {} {}\n", f.a.0, f.b.0, f.c.0).as_bytes())?;
+    }
+
+    Ok(())
+}
+
 /// Loads IsPushable<Is3D> from the .off file format
 pub fn load_off_points<IP, P, R>(read: R, ip: &mut IP) -> OffIOResult<()>
 where
@@ -362,7 +397,8 @@ pub enum OffError {
 
 /// Result type for .off file operations
 pub type OffIOResult<T> = IOResult<T, OffError>;
-type OffResult<T> = std::result::Result<T, OffError>;
+/// Result type for .off file operations
+pub type OffResult<T> = std::result::Result<T, OffError>;
 
 impl fmt::Debug for OffError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -384,6 +420,8 @@ impl fmt::Display for OffError {
     }
 }
 
+impl std::error::Error for OffError {}
+
 impl From<ioError> for OffError {
     fn from(_error: ioError) -> Self {
         OffError::AccessFile
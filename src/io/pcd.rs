@@ -0,0 +1,338 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+//! Module for IO operations of the .pcd file format
+
+use crate::*;
+
+use std::{
+    fmt,
+    io::{BufRead, Error as ioError},
+};
+
+use super::{byte_reader::*, types::*, utils::*};
+
+//------------------------------------------------------------------------------
+
+/// Loads IsPushable<Is3D> from the .pcd file format.
+/// Supports ascii and binary (little endian) data with x, y and z stored as floats, other fields are skipped
+pub fn load_pcd<IP, P, R>(mut read: R, ip: &mut IP) -> PcdIOResult<()>
+where
+    IP: IsPushable<P>,
+    P: IsBuildable3D,
+    R: BufRead,
+{
+    let mut line_buffer = Vec::new();
+    let mut i_line = 0;
+
+    let header = load_header(&mut read, &mut line_buffer, &mut i_line)?;
+
+    ip.reserve(header.n_points);
+
+    if header.is_binary {
+        let mut record = vec![0u8; header.record_size];
+        for _ in 0..header.n_points {
+            read.read_exact(&mut record)
+                .map_err(|_| PcdError::LoadFileEndReached)
+                .simple()?;
+
+            let x = read_coordinate(&record, &header.x).simple()?;
+            let y = read_coordinate(&record, &header.y).simple()?;
+            let z = read_coordinate(&record, &header.z).simple()?;
+
+            ip.push(P::new(x, y, z))
+        }
+    } else {
+        let mut i_point = 0;
+        while i_point < header.n_points {
+            let line = fetch_line(&mut read, &mut line_buffer).index(i_line)?;
+            i_line += 1;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let x = fetch_coordinate(line, &header.x).line(i_line, line)?;
+            let y = fetch_coordinate(line, &header.y).line(i_line, line)?;
+            let z = fetch_coordinate(line, &header.z).line(i_line, line)?;
+
+            ip.push(P::new(x, y, z));
+            i_point += 1;
+        }
+    }
+
+    Ok(())
+}
+
+//------------------------------------------------------------------------------
+
+struct PcdHeader {
+    n_points: usize,
+    is_binary: bool,
+    record_size: usize,
+    x: PcdCoordinate,
+    y: PcdCoordinate,
+    z: PcdCoordinate,
+}
+
+/// Location of a coordinate within a record
+struct PcdCoordinate {
+    byte_offset: usize,
+    word_offset: usize,
+    size: usize,
+}
+
+//------------------------------------------------------------------------------
+
+fn load_header<R>(
+    read: &mut R,
+    line_buffer: &mut Vec<u8>,
+    i_line: &mut usize,
+) -> PcdIOResult<PcdHeader>
+where
+    R: BufRead,
+{
+    let mut names: Vec<Vec<u8>> = Vec::new();
+    let mut sizes: Vec<usize> = Vec::new();
+    let mut types: Vec<u8> = Vec::new();
+    let mut counts: Option<Vec<usize>> = None;
+    let mut width: Option<usize> = None;
+    let mut height: Option<usize> = None;
+    let mut points: Option<usize> = None;
+
+    loop {
+        let line = trim_start(fetch_line(read, line_buffer).index(*i_line)?);
+        *i_line += 1;
+
+        if line.is_empty() || line.starts_with(b"#") {
+            continue;
+        }
+
+        let mut words = to_words_skip_empty(line);
+        let keyword = match words.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+
+        match keyword {
+            b"VERSION" | b"VIEWPOINT" => (),
+            b"FIELDS" => names = words.map(|word| word.to_vec()).collect(),
+            b"SIZE" => {
+                sizes = words
+                    .map(from_ascii)
+                    .collect::<Option<_>>()
+                    .ok_or(PcdError::Size)
+                    .line(*i_line, line)?
+            }
+            b"TYPE" => {
+                types = words
+                    .map(|word| match word {
+                        b"F" | b"I" | b"U" => Some(word[0]),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or(PcdError::Type)
+                    .line(*i_line, line)?
+            }
+            b"COUNT" => {
+                counts = Some(
+                    words
+                        .map(from_ascii)
+                        .collect::<Option<_>>()
+                        .ok_or(PcdError::Count)
+                        .line(*i_line, line)?,
+                )
+            }
+            b"WIDTH" => width = Some(fetch_header_value(words).line(*i_line, line)?),
+            b"HEIGHT" => height = Some(fetch_header_value(words).line(*i_line, line)?),
+            b"POINTS" => points = Some(fetch_header_value(words).line(*i_line, line)?),
+            b"DATA" => {
+                let is_binary = match words.next() {
+                    Some(b"ascii") => false,
+                    Some(b"binary") => true,
+                    _ => return Err(PcdError::DataFormat).line(*i_line, line),
+                };
+
+                let n_fields = names.len();
+                let counts = counts.unwrap_or_else(|| vec![1; n_fields]);
+                if sizes.len() != n_fields || types.len() != n_fields || counts.len() != n_fields {
+                    return Err(PcdError::FieldCount).line(*i_line, line);
+                }
+
+                let mut x = None;
+                let mut y = None;
+                let mut z = None;
+                let mut byte_offset = 0;
+                let mut word_offset = 0;
+
+                for i in 0..n_fields {
+                    let target = match names[i].as_slice() {
+                        b"x" => Some(&mut x),
+                        b"y" => Some(&mut y),
+                        b"z" => Some(&mut z),
+                        _ => None,
+                    };
+
+                    if let Some(target) = target {
+                        if types[i] != b'F' || !(sizes[i] == 4 || sizes[i] == 8) || counts[i] != 1 {
+                            return Err(PcdError::CoordinateType).line(*i_line, line);
+                        }
+                        *target = Some(PcdCoordinate {
+                            byte_offset,
+                            word_offset,
+                            size: sizes[i],
+                        });
+                    }
+
+                    byte_offset += sizes[i] * counts[i];
+                    word_offset += counts[i];
+                }
+
+                let (x, y, z) = match (x, y, z) {
+                    (Some(x), Some(y), Some(z)) => (x, y, z),
+                    _ => return Err(PcdError::CoordinatesMissing).line(*i_line, line),
+                };
+
+                let n_points = points
+                    .or_else(|| width.map(|w| w * height.unwrap_or(1)))
+                    .ok_or(PcdError::PointCount)
+                    .line(*i_line, line)?;
+
+                return Ok(PcdHeader {
+                    n_points,
+                    is_binary,
+                    record_size: byte_offset,
+                    x,
+                    y,
+                    z,
+                });
+            }
+            _ => return Err(PcdError::HeaderLine).line(*i_line, line),
+        }
+    }
+}
+
+#[inline(always)]
+fn fetch_header_value<'a, I>(mut words: I) -> PcdResult<usize>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    words
+        .next()
+        .and_then(from_ascii)
+        .ok_or(PcdError::HeaderLine)
+}
+
+#[inline(always)]
+fn fetch_coordinate(line: &[u8], coordinate: &PcdCoordinate) -> PcdResult<f64> {
+    to_words_skip_empty(line)
+        .nth(coordinate.word_offset)
+        .and_then(from_ascii)
+        .ok_or(PcdError::Vertex)
+}
+
+#[inline(always)]
+fn read_coordinate(record: &[u8], coordinate: &PcdCoordinate) -> PcdResult<f64> {
+    let mut bytes = &record[coordinate.byte_offset..];
+    match coordinate.size {
+        4 => LittleReader::read_f32(&mut bytes).map(|x| x as f64),
+        _ => LittleReader::read_f64(&mut bytes),
+    }
+    .map_err(|_| PcdError::Vertex)
+}
+
+//------------------------------------------------------------------------------
+
+/// Error type for .pcd file operations
+pub enum PcdError {
+    LoadFileEndReached,
+    AccessFile,
+    HeaderLine,
+    Size,
+    Type,
+    Count,
+    FieldCount,
+    CoordinateType,
+    CoordinatesMissing,
+    PointCount,
+    DataFormat,
+    Vertex,
+}
+
+/// Result type for .pcd file operations
+pub type PcdIOResult<T> = IOResult<T, PcdError>;
+type PcdResult<T> = std::result::Result<T, PcdError>;
+
+impl fmt::Debug for PcdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::LoadFileEndReached => write!(f, "Unexpected reach of .pcd file end"),
+            Self::AccessFile => write!(f, "Unable to access file"),
+            Self::HeaderLine => write!(f, "Unable to parse header line"),
+            Self::Size => write!(f, "Unable to parse field sizes"),
+            Self::Type => write!(f, "Unable to parse field types"),
+            Self::Count => write!(f, "Unable to parse field counts"),
+            Self::FieldCount => write!(
+                f,
+                "Number of sizes, types or counts doesn't match the fields"
+            ),
+            Self::CoordinateType => write!(f, "Coordinates must be single floats of size 4 or 8"),
+            Self::CoordinatesMissing => write!(f, "Fields x, y or z missing"),
+            Self::PointCount => write!(f, "Unable to determine the number of points"),
+            Self::DataFormat => write!(f, "Data format must be ascii or binary"),
+            Self::Vertex => write!(f, "Unable to parse vertex"),
+        }
+    }
+}
+
+impl fmt::Display for PcdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for PcdError {}
+
+impl From<ioError> for PcdError {
+    fn from(_error: ioError) -> Self {
+        PcdError::AccessFile
+    }
+}
+
+impl From<FetchLineError> for PcdError {
+    fn from(_error: FetchLineError) -> Self {
+        PcdError::LoadFileEndReached
+    }
+}
+
+impl From<WithLineInfo<FetchLineError>> for WithLineInfo<PcdError> {
+    fn from(other: WithLineInfo<FetchLineError>) -> Self {
+        match other {
+            WithLineInfo::<FetchLineError>::None(x) => WithLineInfo::None(PcdError::from(x)),
+            WithLineInfo::<FetchLineError>::Index(i, x) => {
+                WithLineInfo::Index(i, PcdError::from(x))
+            }
+            WithLineInfo::<FetchLineError>::Line(i, l, x) => {
+                WithLineInfo::Line(i, l, PcdError::from(x))
+            }
+        }
+    }
+}
@@ -58,6 +58,8 @@ where
     let mut vertex_between_first_snd = BytesWords::default();
     let mut vertex_between_snd_third = BytesWords::default();
     let mut after = BytesWords::default();
+    let mut vertex_properties = Vec::new();
+    let mut vertex_offset = BytesWords::default();
 
     let mut opt_face_count_type = None;
     let mut opt_face_index_type = None;
@@ -85,9 +87,9 @@ where
 
         if opt_format.is_none() {
             opt_format = Some(match line {
-                b"format ascii 1.0" => Format::Ascii,
-                b"format binary_little_endian 1.0" => Format::LittleEndian,
-                b"format binary_big_endian 1.0" => Format::BigEndian,
+                b"format ascii 1.0" => PlyFormat::Ascii,
+                b"format binary_little_endian 1.0" => PlyFormat::LittleEndian,
+                b"format binary_big_endian 1.0" => PlyFormat::BigEndian,
                 _ => return Err(PlyError::LoadFormatNotFound).line(*i_line, line),
             });
             continue;
@@ -144,20 +146,34 @@ where
                         .next()
                         .ok_or(PlyError::InvalidProperty)
                         .line(*i_line, line)?;
-                    if id == b"x" {
-                        opt_fst_type = Some(VertexType::try_from(t).line(*i_line, line)?);
-                        n_types_found += 1;
-                        vertex_order[i_vertex_order] = Xyz::X;
-                        i_vertex_order += 1;
-                    } else if id == b"y" {
-                        opt_snd_type = Some(VertexType::try_from(t).line(*i_line, line)?);
-                        n_types_found += 1;
-                        vertex_order[i_vertex_order] = Xyz::Y;
-                        i_vertex_order += 1;
-                    } else if id == b"z" {
-                        opt_third_type = Some(VertexType::try_from(t).line(*i_line, line)?);
+                    vertex_properties.push(VertexProperty {
+                        name: String::from_utf8_lossy(id).into_owned(),
+                        t,
+                        offset: vertex_offset.clone(),
+                    });
+                    vertex_offset.bytes += t.size_bytes();
+                    vertex_offset.words += 1;
+
+                    let xyz = match id {
+                        b"x" => Some(Xyz::X),
+                        b"y" => Some(Xyz::Y),
+                        b"z" => Some(Xyz::Z),
+                        _ => None,
+                    };
+                    if let Some(xyz) = xyz {
+                        // types are stored in the order of occurrence, point_with_order takes care of the mapping to x, y and z
+                        let vt = Some(VertexType::try_from(t).line(*i_line, line)?);
+                        match n_types_found {
+                            0 => opt_fst_type = vt,
+                            1 => opt_snd_type = vt,
+                            2 => opt_third_type = vt,
+                            _ => {
+                                return Err(PlyError::InvalidVertexDimensionDefinition)
+                                    .line(*i_line, line)
+                            }
+                        }
                         n_types_found += 1;
-                        vertex_order[i_vertex_order] = Xyz::Z;
+                        vertex_order[i_vertex_order] = xyz;
                         i_vertex_order += 1;
                     } else if n_types_found == 0 {
                         vertex_before.bytes += t.size_bytes();
@@ -221,7 +237,13 @@ where
         }
 
         if line == b"end_header" && ply_found {
-            if let (Some(format), Some(n_vertices), Some(x_type), Some(y_type), Some(z_type)) = (
+            if let (
+                Some(format),
+                Some(n_vertices),
+                Some(fst_type),
+                Some(snd_type),
+                Some(third_type),
+            ) = (
                 opt_format,
                 opt_n_vertices,
                 opt_fst_type,
@@ -232,13 +254,14 @@ where
                     count: n_vertices,
                     format: VertexFormat {
                         order: VertexOrder::try_from(vertex_order).line(*i_line, line)?,
-                        first: x_type,
-                        snd: y_type,
-                        third: z_type,
+                        first: fst_type,
+                        snd: snd_type,
+                        third: third_type,
                         before: vertex_before,
                         between_first_snd: vertex_between_first_snd,
                         between_snd_third: vertex_between_snd_third,
                         after,
+                        properties: vertex_properties,
                     },
                 };
 
@@ -55,13 +55,13 @@ where
             let to_reserve_data_faces = Some((header.vertex.count, header.face.count));
 
             let inner = match header.format {
-                Format::Ascii => BinaryOrAsciiPlyMeshInteralIterator::Ascii(
+                PlyFormat::Ascii => BinaryOrAsciiPlyMeshInteralIterator::Ascii(
                     PlyAsciiMeshIterator::new(read, header, i_line),
                 ),
-                Format::LittleEndian => BinaryOrAsciiPlyMeshInteralIterator::BinaryLittle(
+                PlyFormat::LittleEndian => BinaryOrAsciiPlyMeshInteralIterator::BinaryLittle(
                     PlyBinaryMeshIterator::new(read, header),
                 ),
-                Format::BigEndian => BinaryOrAsciiPlyMeshInteralIterator::BinaryBig(
+                PlyFormat::BigEndian => BinaryOrAsciiPlyMeshInteralIterator::BinaryBig(
                     PlyBinaryMeshIterator::new(read, header),
                 ),
             };
@@ -134,18 +134,20 @@ where
         let mut line_buffer = Vec::new();
         let mut i_line = 0;
 
-        let header: PartialHeader = load_header(&mut read, &mut line_buffer, &mut i_line)?.into();
+        let header = load_header(&mut read, &mut line_buffer, &mut i_line)?;
+        let is_last_element = matches!(header, Header::Partial(_));
+        let header: PartialHeader = header.into();
         let to_reserve = Some(header.vertex.count);
 
         let inner = match header.format {
-            Format::Ascii => BinaryOrAsciiPlyPointsInteralIterator::Ascii(
+            PlyFormat::Ascii => BinaryOrAsciiPlyPointsInteralIterator::Ascii(
                 PlyAsciiPointsIterator::new(read, header, i_line),
             ),
-            Format::LittleEndian => BinaryOrAsciiPlyPointsInteralIterator::BinaryLittle(
-                PlyBinaryPointsIterator::new(read, header),
+            PlyFormat::LittleEndian => BinaryOrAsciiPlyPointsInteralIterator::BinaryLittle(
+                PlyBinaryPointsIterator::new(read, header, is_last_element),
             ),
-            Format::BigEndian => BinaryOrAsciiPlyPointsInteralIterator::BinaryBig(
-                PlyBinaryPointsIterator::new(read, header),
+            PlyFormat::BigEndian => BinaryOrAsciiPlyPointsInteralIterator::BinaryBig(
+                PlyBinaryPointsIterator::new(read, header, is_last_element),
             ),
         };
 
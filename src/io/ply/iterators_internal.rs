@@ -25,6 +25,7 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 use crate::*;
 
 use std::{
+    collections::VecDeque,
     io::{BufRead, Read},
     iter::FusedIterator,
     marker::PhantomData,
@@ -124,7 +125,7 @@ where
         let partial_header: PartialHeader = header.clone().into();
         Self {
             header,
-            p_iter: Some(PlyBinaryPointsIterator::new(read, partial_header)),
+            p_iter: Some(PlyBinaryPointsIterator::new(read, partial_header, false)),
             f_iter: None,
         }
     }
@@ -203,6 +204,7 @@ where
     read: R,
     is_done: bool,
     header: PartialHeader,
+    is_last_element: bool,
     current: usize,
     phantom_p: PhantomData<P>,
     phantom_br: PhantomData<BR>,
@@ -214,11 +216,13 @@ where
     R: Read,
     BR: IsByteReader,
 {
-    pub fn new(read: R, header: PartialHeader) -> Self {
+    /// is_last_element should be true if no other elements (e.g. faces) follow the vertices
+    pub fn new(read: R, header: PartialHeader, is_last_element: bool) -> Self {
         Self {
             read,
             is_done: false,
             header,
+            is_last_element,
             current: 0,
             phantom_p: PhantomData,
             phantom_br: PhantomData,
@@ -280,7 +284,11 @@ where
             }))
         } else {
             self.is_done = true;
-            None
+            if self.is_last_element {
+                ensure_end_of_data(&mut self.read).err().map(Err)
+            } else {
+                None
+            }
         }
     }
 }
@@ -421,6 +429,7 @@ where
     current: usize,
     i_line: usize,
     line_buffer: Vec<u8>,
+    pending: VecDeque<[usize; 3]>,
 }
 
 impl<R> PlyAsciiFacesIterator<R>
@@ -435,6 +444,7 @@ where
             current: 0,
             i_line,
             line_buffer: Vec::new(),
+            pending: VecDeque::new(),
         }
     }
 }
@@ -446,6 +456,9 @@ where
     type Item = PlyIOResult<[usize; 3]>;
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(triangle) = self.pending.pop_front() {
+            return Some(Ok(triangle));
+        }
         if self.is_done {
             return None;
         }
@@ -454,13 +467,19 @@ where
             while let Ok(line) = fetch_line(&mut self.read, &mut self.line_buffer) {
                 self.i_line += 1;
                 return Some(
-                    collect_index_line(&line)
+                    match collect_index_line(line)
                         .ok_or(PlyError::FaceStructure)
                         .line(self.i_line, line)
-                        .map_err(|e| {
+                    {
+                        Ok(indices) => {
+                            fan_triangulate(&indices, &mut self.pending);
+                            Ok(self.pending.pop_front().unwrap()) // safe since at least 3 indices
+                        }
+                        Err(e) => {
                             self.is_done = true;
-                            e
-                        }),
+                            Err(e)
+                        }
+                    },
                 );
             }
         }
@@ -484,6 +503,8 @@ where
     is_done: bool,
     header: FullHeader,
     current: usize,
+    indices: Vec<usize>,
+    pending: VecDeque<[usize; 3]>,
     phantom: PhantomData<BR>,
 }
 
@@ -498,6 +519,8 @@ where
             is_done: false,
             header,
             current: 0,
+            indices: Vec::new(),
+            pending: VecDeque::new(),
             phantom: PhantomData,
         }
     }
@@ -508,17 +531,22 @@ where
 
         let element_count = read_face_type::<BR, _>(&mut self.read, self.header.face.format.count)?;
 
-        if element_count != 3 {
+        if element_count < 3 {
             return Err(PlyError::FaceStructure);
         }
 
-        let a = read_face_type::<BR, _>(&mut self.read, self.header.face.format.index)?;
-        let b = read_face_type::<BR, _>(&mut self.read, self.header.face.format.index)?;
-        let c = read_face_type::<BR, _>(&mut self.read, self.header.face.format.index)?;
+        self.indices.clear();
+        for _ in 0..element_count {
+            self.indices.push(read_face_type::<BR, _>(
+                &mut self.read,
+                self.header.face.format.index,
+            )?);
+        }
 
         skip_bytes(&mut self.read, self.header.face.format.after.bytes)?;
 
-        Ok([a, b, c])
+        fan_triangulate(&self.indices, &mut self.pending);
+        Ok(self.pending.pop_front().unwrap()) // safe since at least 3 indices
     }
 }
 
@@ -530,6 +558,9 @@ where
     type Item = PlyResult<[usize; 3]>;
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(triangle) = self.pending.pop_front() {
+            return Some(Ok(triangle));
+        }
         if self.is_done {
             return None;
         }
@@ -541,7 +572,8 @@ where
             }))
         } else {
             self.is_done = true;
-            None
+            // faces are always the last element
+            ensure_end_of_data(&mut self.read).err().map(Err)
         }
     }
 }
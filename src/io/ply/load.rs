@@ -24,11 +24,14 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use crate::*;
 
-use std::io::{BufRead, Read};
+use std::{
+    cell::Cell,
+    io::{BufRead, Read},
+};
 
-use super::super::{byte_reader::*, types::*};
+use super::super::{byte_reader::*, types::*, utils::*};
 
-use super::{header::*, iterators::*, iterators_internal::*, types::*};
+use super::{header::*, iterators::*, iterators_internal::*, types::*, utils::*};
 
 //------------------------------------------------------------------------------
 
@@ -53,11 +56,11 @@ where
             mesh.reserve_faces(header.face.count);
 
             match header.format {
-                Format::Ascii => load_mesh_ascii(&mut read, mesh, header, &mut i_line),
-                Format::LittleEndian => {
+                PlyFormat::Ascii => load_mesh_ascii(&mut read, mesh, header, &mut i_line),
+                PlyFormat::LittleEndian => {
                     load_mesh_binary::<LittleReader, _, _, _>(&mut read, mesh, header).simple()
                 }
-                Format::BigEndian => {
+                PlyFormat::BigEndian => {
                     load_mesh_binary::<BigReader, _, _, _>(&mut read, mesh, header).simple()
                 }
             }?;
@@ -68,11 +71,11 @@ where
             ip.reserve(header.vertex.count);
 
             match header.format {
-                Format::Ascii => load_points_ascii(&mut read, ip, header, i_line),
-                Format::LittleEndian => {
+                PlyFormat::Ascii => load_points_ascii(&mut read, ip, header, i_line),
+                PlyFormat::LittleEndian => {
                     load_points_binary::<LittleReader, _, _, _>(&mut read, ip, header).simple()
                 }
-                Format::BigEndian => {
+                PlyFormat::BigEndian => {
                     load_points_binary::<BigReader, _, _, _>(&mut read, ip, header).simple()
                 }
             }?;
@@ -113,6 +116,220 @@ where
 
 //------------------------------------------------------------------------------
 
+/// Loads an IsMesh3D from the .ply file format, additionally pushing the value of the named scalar vertex property (e.g. "intensity") of each vertex to out.
+/// Files without faces result in a mesh without faces
+pub fn load_ply_with_scalar<EM, P, IPS, R>(
+    read: R,
+    mesh: &mut EM,
+    property_name: &str,
+    out: &mut IPS,
+) -> PlyIOResult<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D,
+    IPS: IsPushable<f64>,
+    R: BufRead,
+{
+    load_ply_with_properties(
+        read,
+        mesh,
+        |format| {
+            format
+                .property(property_name)
+                .map(|x| vec![x.clone()])
+                .ok_or_else(|| PlyError::PropertyNotFound(property_name.to_string()))
+        },
+        |values| match values {
+            DataReserve::Reserve(n) => out.reserve(n),
+            DataReserve::Data(x) => out.push(x[0]),
+        },
+    )
+}
+
+/// Loads an IsMesh3D from the .ply file format, additionally pushing the color of each vertex to colors.
+/// Colors are only pushed if the red, green and blue vertex properties exist (alpha is ignored).
+/// Integer channels are used as they are, floating point channels are expected within [0.0, 1.0].
+/// Files without faces result in a mesh without faces
+pub fn load_ply_with_colors<EM, P, IPC, R>(
+    read: R,
+    mesh: &mut EM,
+    colors: &mut IPC,
+) -> PlyIOResult<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D,
+    IPC: IsPushable<Rgb>,
+    R: BufRead,
+{
+    // floating point channels are scaled, which is only known once the header was read
+    let scales = Cell::new([1.0; 3]);
+    load_ply_with_properties(
+        read,
+        mesh,
+        |format| {
+            let channels = ["red", "green", "blue"]
+                .iter()
+                .filter_map(|name| format.property(name).cloned())
+                .collect::<Vec<_>>();
+            if channels.len() != 3 {
+                return Ok(Vec::new());
+            }
+            let mut channel_scales = [1.0; 3];
+            for (scale, channel) in channel_scales.iter_mut().zip(channels.iter()) {
+                if matches!(channel.t, Type::Float | Type::Double) {
+                    *scale = 255.0;
+                }
+            }
+            scales.set(channel_scales);
+            Ok(channels)
+        },
+        |values| match values {
+            DataReserve::Reserve(n) => colors.reserve(n),
+            DataReserve::Data(x) => {
+                if let [r, g, b] = x {
+                    let scales = scales.get();
+                    let channel =
+                        |value: f64, scale: f64| (value * scale).round().clamp(0.0, 255.0) as u8;
+                    colors.push(Rgb {
+                        r: channel(*r, scales[0]),
+                        g: channel(*g, scales[1]),
+                        b: channel(*b, scales[2]),
+                    });
+                }
+            }
+        },
+    )
+}
+
+//------------------------------------------------------------------------------
+
+/// Loads an IsMesh3D from the .ply file format, additionally passing the values of the vertex properties chosen by select to out
+fn load_ply_with_properties<EM, P, R, S, O>(
+    mut read: R,
+    mesh: &mut EM,
+    select: S,
+    mut out: O,
+) -> PlyIOResult<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D,
+    R: BufRead,
+    S: FnOnce(&VertexFormat) -> PlyResult<Vec<VertexProperty>>,
+    O: FnMut(DataReserve<&[f64]>),
+{
+    let mut line_buffer = Vec::new();
+    let mut i_line = 0;
+
+    let header = load_header(&mut read, &mut line_buffer, &mut i_line)?;
+    let (format, vertex) = match &header {
+        Header::Full(x) => (x.format, x.vertex.clone()),
+        Header::Partial(x) => (x.format, x.vertex.clone()),
+    };
+
+    let properties = select(&vertex.format).simple()?;
+    // safe, since the header is only valid if x, y and z exist
+    let xyz = [
+        vertex.format.property("x").unwrap().clone(),
+        vertex.format.property("y").unwrap().clone(),
+        vertex.format.property("z").unwrap().clone(),
+    ];
+
+    mesh.reserve_vertices(vertex.count);
+    out(DataReserve::Reserve(vertex.count));
+
+    match format {
+        PlyFormat::Ascii => {
+            let mut values = vec![0.0; properties.len()];
+            for _ in 0..vertex.count {
+                let line = fetch_line(&mut read, &mut line_buffer)
+                    .or(Err(PlyError::LoadVertexCountIncorrect))
+                    .simple()?;
+                i_line += 1;
+
+                let words: Vec<&[u8]> = to_words_skip_empty(line).collect();
+                let value = |p: &VertexProperty| {
+                    words
+                        .get(p.offset.words)
+                        .and_then(|w| from_ascii::<f64>(w))
+                        .ok_or(PlyError::InvalidVertex)
+                };
+
+                let [x, y, z] = &xyz;
+                mesh.add_vertex(P::new(
+                    value(x).line(i_line, line)?,
+                    value(y).line(i_line, line)?,
+                    value(z).line(i_line, line)?,
+                ));
+                for (v, property) in values.iter_mut().zip(properties.iter()) {
+                    *v = value(property).line(i_line, line)?;
+                }
+                out(DataReserve::Data(&values));
+            }
+        }
+        PlyFormat::LittleEndian => {
+            load_vertices_with_properties_binary::<LittleReader, _, _, _, _>(
+                &mut read,
+                mesh,
+                &vertex,
+                &xyz,
+                &properties,
+                &mut out,
+            )
+            .simple()?;
+        }
+        PlyFormat::BigEndian => {
+            load_vertices_with_properties_binary::<BigReader, _, _, _, _>(
+                &mut read,
+                mesh,
+                &vertex,
+                &xyz,
+                &properties,
+                &mut out,
+            )
+            .simple()?;
+        }
+    }
+
+    let header = match header {
+        Header::Full(x) => x,
+        Header::Partial(_) => {
+            if !matches!(format, PlyFormat::Ascii) {
+                ensure_end_of_data(&mut read).simple()?;
+            }
+            return Ok(());
+        }
+    };
+    mesh.reserve_faces(header.face.count);
+
+    let mut add_face = |[a, b, c]: [usize; 3]| {
+        mesh.try_add_connection(VId(a), VId(b), VId(c))
+            .map(|_| ())
+            .or(Err(PlyError::InvalidMeshIndices))
+    };
+
+    match format {
+        PlyFormat::Ascii => {
+            for face in PlyAsciiFacesIterator::new(&mut read, header, i_line) {
+                add_face(face?).simple()?;
+            }
+        }
+        PlyFormat::LittleEndian => {
+            for face in PlyBinaryFacesIterator::<LittleReader, _>::new(&mut read, header) {
+                add_face(face.simple()?).simple()?;
+            }
+        }
+        PlyFormat::BigEndian => {
+            for face in PlyBinaryFacesIterator::<BigReader, _>::new(&mut read, header) {
+                add_face(face.simple()?).simple()?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+//------------------------------------------------------------------------------
+
 /// Loads the points from the .ply file into IsPushable<Is3D>
 pub fn load_ply_points<IP, P, R>(read: R, ip: &mut IP) -> PlyIOResult<()>
 where
@@ -132,6 +349,39 @@ where
     Ok(())
 }
 
+/// Loads the points from the .ply file into IsPushable<Is3D>, handling positions with non-finite coordinates as defined
+pub fn load_ply_points_handling_non_finite<IP, P, R>(
+    read: R,
+    ip: &mut IP,
+    handling: NonFiniteHandling,
+) -> PlyIOResult<()>
+where
+    IP: IsPushable<P>,
+    P: IsBuildable3D,
+    R: BufRead,
+{
+    let iterator = PlyPointsIterator::<P, R>::new(read)?;
+    let mut i_vertex = 0;
+
+    for rp in iterator {
+        match rp? {
+            DataReserve::Reserve(x) => ip.reserve(x),
+            DataReserve::Data(x) => {
+                let is_finite = x.x().is_finite() && x.y().is_finite() && x.z().is_finite();
+                match handling {
+                    _ if is_finite => ip.push(x),
+                    NonFiniteHandling::Accept => ip.push(x),
+                    NonFiniteHandling::Skip => (),
+                    NonFiniteHandling::Error => return Err(PlyError::NonFinite(i_vertex)).simple(),
+                }
+                i_vertex += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 //------------------------------------------------------------------------------
 //------------------------------------------------------------------------------
 //------------------------------------------------------------------------------
@@ -149,7 +399,8 @@ where
     R: Read,
     BR: IsByteReader,
 {
-    let iterator = PlyBinaryPointsIterator::<BR, _, _>::new(read, header);
+    // only used for headers without faces
+    let iterator = PlyBinaryPointsIterator::<BR, _, _>::new(read, header, true);
 
     for p in iterator {
         ip.push(p?)
@@ -236,3 +487,43 @@ where
 
     Ok(())
 }
+
+//------------------------------------------------------------------------------
+
+fn load_vertices_with_properties_binary<BR, EM, P, R, O>(
+    read: &mut R,
+    mesh: &mut EM,
+    vertex: &VertexData,
+    xyz: &[VertexProperty; 3],
+    properties: &[VertexProperty],
+    out: &mut O,
+) -> PlyResult<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D,
+    R: Read,
+    BR: IsByteReader,
+    O: FnMut(DataReserve<&[f64]>),
+{
+    let mut record = vec![0u8; vertex.format.stride_bytes()];
+    let mut values = vec![0.0; properties.len()];
+    let value =
+        |record: &[u8], p: &VertexProperty| read_type::<BR, _>(&mut &record[p.offset.bytes..], p.t);
+
+    for _ in 0..vertex.count {
+        read.read_exact(&mut record)?;
+
+        let [x, y, z] = xyz;
+        mesh.add_vertex(P::new(
+            value(&record, x)?,
+            value(&record, y)?,
+            value(&record, z)?,
+        ));
+        for (v, property) in values.iter_mut().zip(properties.iter()) {
+            *v = value(&record, property)?;
+        }
+        out(DataReserve::Data(&values));
+    }
+
+    Ok(())
+}
@@ -29,8 +29,10 @@ mod load;
 mod save;
 mod types;
 mod utils;
+mod writer;
 
 pub use iterators::*;
 pub use load::*;
 pub use save::*;
-pub use types::{MeshOrPoints, PlyError};
+pub use types::{MeshOrPoints, PlyError, PlyFormat, Type as PlyType};
+pub use writer::*;
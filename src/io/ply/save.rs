@@ -24,54 +24,38 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use crate::*;
 
-use super::types::*;
+use super::{types::*, writer::*, PlyType};
 
 use std::io::Write;
 
 //------------------------------------------------------------------------------
 
-/// Saves an IsMesh3D in the ASCII .ply file format
-pub fn save_ply_ascii<M, P, W>(write: &mut W, mesh: &M) -> PlyResult<()>
+/// Saves an IsMesh3D in the .ply file format of the given format, storing the positions with double precision
+pub fn save_ply<M, P, W>(write: &mut W, mesh: &M, format: PlyFormat) -> PlyResult<()>
 where
     M: IsMesh<P, Face3>,
     P: IsBuildable3D,
     W: Write,
 {
-    let header = "ply\n".to_string()
-        + "format ascii 1.0\n"
-        + "comment Created by rust-3d\n"
-        + "element vertex "
-        + &mesh.num_vertices().to_string()
-        + "\n"
-        + "property float x\n"
-        + "property float y\n"
-        + "property float z\n"
-        + "element face "
-        + &mesh.num_faces().to_string()
-        + "\n"
-        + "property list uchar uint vertex_indices\n"
-        + "end_header\n";
-    write.write_all(header.as_bytes())?;
+    save_ply_preset(write, mesh, format, PlyType::Double, None::<&[Norm3D]>)
+}
 
-    for i in 0..mesh.num_vertices() {
-        let vertex = mesh.vertex(VId(i)).unwrap(); // safe since iterating num_vertices
-        write.write_all((vertex.to_str() + "\n").as_bytes())?;
-    }
+//------------------------------------------------------------------------------
 
-    for i in 0..mesh.num_faces() {
-        let face = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating num_faces
-        write.write_all(
-            ("3 ".to_string()
-                + &face.a.to_string()
-                + " "
-                + &face.b.to_string()
-                + " "
-                + &face.c.to_string()
-                + "\n")
-                .as_bytes(),
-        )?;
-    }
-    Ok(())
+/// Saves an IsMesh3D in the ASCII .ply file format
+pub fn save_ply_ascii<M, P, W>(write: &mut W, mesh: &M) -> PlyResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    W: Write,
+{
+    save_ply_preset(
+        write,
+        mesh,
+        PlyFormat::Ascii,
+        PlyType::Float,
+        None::<&[Norm3D]>,
+    )
 }
 
 //------------------------------------------------------------------------------
@@ -151,72 +135,11 @@ where
     P: IsBuildable3D,
     W: Write,
 {
-    let header = match precision {
-        Precision::P32 => {
-            "ply\n".to_string()
-                + "format binary_big_endian 1.0\n"
-                + "comment Created by rust-3d\n"
-                + "element vertex "
-                + &mesh.num_vertices().to_string()
-                + "\n"
-                + "property float x\n"
-                + "property float y\n"
-                + "property float z\n"
-                + "element face "
-                + &mesh.num_faces().to_string()
-                + "\n"
-                + "property list uint8 uint32 vertex_indices\n"
-                + "end_header\n"
-        }
-        Precision::P64 => {
-            "ply\n".to_string()
-                + "format binary_big_endian 1.0\n"
-                + "comment Created by rust-3d\n"
-                + "element vertex "
-                + &mesh.num_vertices().to_string()
-                + "\n"
-                + "property double x\n"
-                + "property double y\n"
-                + "property double z\n"
-                + "element face "
-                + &mesh.num_faces().to_string()
-                + "\n"
-                + "property list uint8 uint32 vertex_indices\n"
-                + "end_header\n"
-        }
+    let t = match precision {
+        Precision::P32 => PlyType::Float,
+        Precision::P64 => PlyType::Double,
     };
-
-    write.write_all(header.as_bytes())?;
-
-    match precision {
-        Precision::P32 => {
-            for i in 0..mesh.num_vertices() {
-                let vertex = mesh.vertex(VId(i)).unwrap(); // safe since iterating num_vertices
-                write.write(&(vertex.x() as f32).to_be_bytes())?;
-                write.write(&(vertex.y() as f32).to_be_bytes())?;
-                write.write(&(vertex.z() as f32).to_be_bytes())?;
-            }
-        }
-
-        Precision::P64 => {
-            for i in 0..mesh.num_vertices() {
-                let vertex = mesh.vertex(VId(i)).unwrap(); // safe since iterating num_vertices
-                write.write(&(vertex.x()).to_be_bytes())?;
-                write.write(&(vertex.y()).to_be_bytes())?;
-                write.write(&(vertex.z()).to_be_bytes())?;
-            }
-        }
-    }
-
-    for i in 0..mesh.num_faces() {
-        let face = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating num_faces
-        write.write(&3u8.to_be_bytes())?;
-        write.write(&(face.a.0 as u32).to_be_bytes())?;
-        write.write(&(face.b.0 as u32).to_be_bytes())?;
-        write.write(&(face.c.0 as u32).to_be_bytes())?;
-    }
-
-    Ok(())
+    save_ply_preset(write, mesh, PlyFormat::BigEndian, t, None::<&[Norm3D]>)
 }
 
 //------------------------------------------------------------------------------
@@ -321,3 +244,155 @@ where
 
     Ok(())
 }
+
+//------------------------------------------------------------------------------
+
+/// Saves an IsMesh3D in the ASCII .ply file format with optional additional vertex normals
+pub fn save_ply_ascii_with_normals<M, P, N, W>(
+    write: &mut W,
+    mesh: &M,
+    normals: Option<&[N]>,
+) -> PlyResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    N: IsNormalized3D,
+    W: Write,
+{
+    save_ply_preset(write, mesh, PlyFormat::Ascii, PlyType::Float, normals)
+}
+
+//------------------------------------------------------------------------------
+
+/// Saves an IsMesh3D in the binary .ply file format with optional additional vertex normals
+pub fn save_ply_binary_with_normals<M, P, N, W>(
+    write: &mut W,
+    mesh: &M,
+    precision: &Precision,
+    normals: Option<&[N]>,
+) -> PlyResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    N: IsNormalized3D,
+    W: Write,
+{
+    let t = match precision {
+        Precision::P32 => PlyType::Float,
+        Precision::P64 => PlyType::Double,
+    };
+    save_ply_preset(write, mesh, PlyFormat::BigEndian, t, normals)
+}
+
+//------------------------------------------------------------------------------
+
+/// Serializes an IsMesh3D into the bytes of a .ply file of the given format, just like save_ply
+pub fn mesh_to_ply_bytes<M, P>(mesh: &M, format: PlyFormat) -> PlyResult<Vec<u8>>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    let mut result = Vec::new();
+    save_ply(&mut result, mesh, format)?;
+    Ok(result)
+}
+
+//------------------------------------------------------------------------------
+
+/// Saves the positions of an IsRandomAccessible<Is3D> with additional colors as point set (without any faces) in the .ply file format, storing the positions with double precision
+pub fn save_ply_points_colored<RA, P, W>(
+    write: &mut W,
+    cloud: &RA,
+    colors: &[Rgb],
+    format: PlyFormat,
+) -> PlyResult<()>
+where
+    RA: IsRandomAccessible<P>,
+    P: Is3D,
+    W: Write,
+{
+    let n_vertices = cloud.len();
+
+    if n_vertices != colors.len() {
+        return Err(PlyError::ColorArrayIncorrectLength);
+    }
+
+    let mut writer = PlyWriter::new(format)
+        .positions(PlyType::Double)
+        .vertex_property("red", PlyType::UChar)
+        .vertex_property("green", PlyType::UChar)
+        .vertex_property("blue", PlyType::UChar)
+        .with_faces()
+        .begin(write, n_vertices, 0)?;
+
+    for (i, color) in colors.iter().enumerate() {
+        let p = &cloud[i];
+        writer.push_vertex(&[
+            p.x(),
+            p.y(),
+            p.z(),
+            color.r as f64,
+            color.g as f64,
+            color.b as f64,
+        ])?;
+    }
+
+    writer.finish()
+}
+
+//------------------------------------------------------------------------------
+
+/// Saves the positions, optional normals and faces of an IsMesh3D via PlyWriter, the normals are stored with the type of the positions
+fn save_ply_preset<M, P, N, W>(
+    write: &mut W,
+    mesh: &M,
+    format: PlyFormat,
+    t: PlyType,
+    normals: Option<&[N]>,
+) -> PlyResult<()>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    N: IsNormalized3D,
+    W: Write,
+{
+    let n_vertices = mesh.num_vertices();
+    let n_faces = mesh.num_faces();
+
+    let mut writer = PlyWriter::new(format).positions(t);
+    if let Some(normals) = normals {
+        if n_vertices != normals.len() {
+            return Err(PlyError::NormalArrayIncorrectLength);
+        }
+        writer = writer
+            .vertex_property("nx", t)
+            .vertex_property("ny", t)
+            .vertex_property("nz", t);
+    }
+    let mut writer = writer.with_faces().begin(write, n_vertices, n_faces)?;
+
+    for i in 0..n_vertices {
+        let vertex = mesh.vertex(VId(i)).unwrap(); // safe since iterating n_vertices
+        match normals {
+            Some(normals) => {
+                let normal = &normals[i]; // safe since normals checked to have n_vertices elements
+                writer.push_vertex(&[
+                    vertex.x(),
+                    vertex.y(),
+                    vertex.z(),
+                    normal.x(),
+                    normal.y(),
+                    normal.z(),
+                ])?
+            }
+            None => writer.push_vertex(&[vertex.x(), vertex.y(), vertex.z()])?,
+        }
+    }
+
+    for i in 0..n_faces {
+        let face = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating n_faces
+        writer.push_face(&[face.a.0, face.b.0, face.c.0])?;
+    }
+
+    writer.finish()
+}
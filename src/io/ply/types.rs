@@ -30,7 +30,8 @@ use crate::io::IOResult;
 
 //------------------------------------------------------------------------------
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// Scalar type of a .ply property
 pub enum Type {
     Char,
     UChar,
@@ -58,6 +59,22 @@ impl Type {
     }
 }
 
+impl Type {
+    /// The keyword of the type within .ply headers
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Char => "char",
+            Self::UChar => "uchar",
+            Self::Short => "short",
+            Self::UShort => "ushort",
+            Self::Int => "int",
+            Self::UInt => "uint",
+            Self::Float => "float",
+            Self::Double => "double",
+        }
+    }
+}
+
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -186,6 +203,16 @@ impl TryFrom<Type> for FaceType {
 
 //------------------------------------------------------------------------------
 
+#[derive(Debug, Clone)]
+pub struct VertexProperty {
+    pub name: String,
+    pub t: Type,
+    /// Offset from the start of the vertex record
+    pub offset: BytesWords,
+}
+
+//------------------------------------------------------------------------------
+
 #[derive(Debug, Clone)]
 pub struct VertexFormat {
     pub order: VertexOrder,
@@ -196,6 +223,18 @@ pub struct VertexFormat {
     pub between_first_snd: BytesWords,
     pub between_snd_third: BytesWords,
     pub after: BytesWords,
+    pub properties: Vec<VertexProperty>,
+}
+
+impl VertexFormat {
+    /// Returns the property with the given name
+    pub fn property(&self, name: &str) -> Option<&VertexProperty> {
+        self.properties.iter().find(|x| x.name == name)
+    }
+    /// Returns the number of bytes of a single binary vertex record
+    pub fn stride_bytes(&self) -> usize {
+        self.properties.iter().map(|x| x.t.size_bytes()).sum()
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -210,8 +249,9 @@ pub struct FaceFormat {
 
 //------------------------------------------------------------------------------
 
-#[derive(Copy, Clone, Debug)]
-pub enum Format {
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// Format of the data of a .ply file
+pub enum PlyFormat {
     Ascii,
     LittleEndian,
     BigEndian,
@@ -250,14 +290,14 @@ pub enum Header {
 
 #[derive(Debug, Clone)]
 pub struct FullHeader {
-    pub format: Format,
+    pub format: PlyFormat,
     pub vertex: VertexData,
     pub face: FaceData,
 }
 
 #[derive(Debug, Clone)]
 pub struct PartialHeader {
-    pub format: Format,
+    pub format: PlyFormat,
     pub vertex: VertexData,
 }
 
@@ -288,8 +328,10 @@ pub enum PlyError {
     LoadVertexIndexDefinitionNotFound,
     LoadHeaderInvalid,
     LoadVertexCountIncorrect,
+    LoadVerticesIncorrect,
     AccessFile,
     ColorArrayIncorrectLength,
+    NormalArrayIncorrectLength,
     VertexElement,
     FaceElement,
     InvalidType(String),
@@ -301,6 +343,12 @@ pub enum PlyError {
     PropertyLineLocation,
     FaceStructure,
     InvalidVertexDimensionDefinition,
+    NonFinite(usize),
+    PropertyNotFound(String),
+    InvalidPropertyName(String),
+    RowIncorrectLength,
+    ElementCountIncorrect,
+    FaceTooLarge(usize),
 }
 
 /// Result type for .ply file operations
@@ -319,9 +367,16 @@ impl fmt::Debug for PlyError {
             }
             Self::LoadHeaderInvalid => write!(f, "Header of .ply seems to be invalid"),
             Self::LoadVertexCountIncorrect => write!(f, "Vertex count of .ply not found"),
+            Self::LoadVerticesIncorrect => write!(
+                f,
+                "Binary data of .ply doesn't match the header's element definitions"
+            ),
             Self::ColorArrayIncorrectLength => {
                 write!(f, "The provided color array has an incorrect length")
             }
+            Self::NormalArrayIncorrectLength => {
+                write!(f, "The provided normal array has an incorrect length")
+            }
             Self::VertexElement => write!(f, "Invalid vertex element"),
             Self::FaceElement => write!(f, "Invalid face element"),
             Self::InvalidType(x) => write!(f, "Invalid type in header '{}'", x),
@@ -334,10 +389,27 @@ impl fmt::Debug for PlyError {
             Self::InvalidVertexDimensionDefinition => {
                 write!(f, "Invalid order / definition of vertex dimension order")
             }
+            Self::NonFinite(i_vertex) => {
+                write!(f, "Non-finite coordinate in vertex {}", i_vertex)
+            }
+            Self::PropertyNotFound(x) => write!(f, "Vertex property '{}' not found", x),
+            Self::InvalidPropertyName(x) => write!(f, "Invalid property name '{}'", x),
+            Self::RowIncorrectLength => {
+                write!(
+                    f,
+                    "The number of values doesn't match the declared properties"
+                )
+            }
+            Self::ElementCountIncorrect => {
+                write!(f, "The number of written elements doesn't match the header")
+            }
+            Self::FaceTooLarge(n) => {
+                write!(f, "Face with {} vertices exceeds the maximum of 255", n)
+            }
             Self::PropertyLineLocation => write!(f, "Found property line at unexpected location",),
             Self::FaceStructure => write!(
                 f,
-                "Invalid face structure, only supporting 3 vertices per face"
+                "Invalid face structure, requiring at least 3 vertices per face"
             ),
         }
     }
@@ -349,6 +421,8 @@ impl fmt::Display for PlyError {
     }
 }
 
+impl std::error::Error for PlyError {}
+
 impl From<ioError> for PlyError {
     fn from(_error: ioError) -> Self {
         PlyError::AccessFile
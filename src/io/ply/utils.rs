@@ -33,18 +33,35 @@ use std::io::Read;
 
 //------------------------------------------------------------------------------
 
+/// Ensures there's no data left after the last element.
+/// Remaining data indicates that the header declares fewer properties than were written, causing all elements to be misaligned
+pub fn ensure_end_of_data<R>(read: &mut R) -> PlyResult<()>
+where
+    R: Read,
+{
+    let mut buffer = [0u8; 1];
+    match read.read(&mut buffer)? {
+        0 => Ok(()),
+        _ => Err(PlyError::LoadVerticesIncorrect),
+    }
+}
+
+//------------------------------------------------------------------------------
+
 #[inline(always)]
-pub fn collect_index_line(line: &[u8]) -> Option<[usize; 3]> {
+pub fn collect_index_line(line: &[u8]) -> Option<Vec<usize>> {
     let mut words = to_words_skip_empty(line);
-    if words.next()? != b"3" {
+    let count: usize = from_ascii(words.next()?)?;
+    if count < 3 {
         return None;
     }
 
-    let a = from_ascii(words.next()?)?;
-    let b = from_ascii(words.next()?)?;
-    let c = from_ascii(words.next()?)?;
+    let mut result = Vec::with_capacity(count);
+    for _ in 0..count {
+        result.push(from_ascii(words.next()?)?);
+    }
 
-    Some([a, b, c])
+    Some(result)
 }
 
 //------------------------------------------------------------------------------
@@ -81,6 +98,26 @@ where
 
 //------------------------------------------------------------------------------
 
+#[inline(always)]
+pub fn read_type<BR, R>(read: &mut R, t: Type) -> PlyResult<f64>
+where
+    BR: IsByteReader,
+    R: Read,
+{
+    Ok(match t {
+        Type::Char => BR::read_i8(read)? as f64,
+        Type::UChar => BR::read_u8(read)? as f64,
+        Type::Short => BR::read_i16(read)? as f64,
+        Type::UShort => BR::read_u16(read)? as f64,
+        Type::Int => BR::read_i32(read)? as f64,
+        Type::UInt => BR::read_u32(read)? as f64,
+        Type::Float => BR::read_f32(read)? as f64,
+        Type::Double => BR::read_f64(read)?,
+    })
+}
+
+//------------------------------------------------------------------------------
+
 #[inline(always)]
 pub fn point_with_order<P>(fst: f64, snd: f64, third: f64, order: VertexOrder) -> P
 where
@@ -90,8 +127,8 @@ where
         VertexOrder::Xyz => P::new(fst, snd, third),
         VertexOrder::Xzy => P::new(fst, third, snd),
         VertexOrder::Yxz => P::new(snd, fst, third),
-        VertexOrder::Yzx => P::new(snd, third, fst),
-        VertexOrder::Zxy => P::new(third, fst, snd),
+        VertexOrder::Yzx => P::new(third, fst, snd),
+        VertexOrder::Zxy => P::new(snd, third, fst),
         VertexOrder::Zyx => P::new(third, snd, fst),
     }
 }
@@ -0,0 +1,250 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Module for PlyWriter, a writer of .ply files with custom vertex properties
+
+use super::types::{Type as PlyType, *};
+
+use std::io::Write;
+
+//------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+/// PlyWriter, a builder to declare the vertex properties (and optionally faces) of a .ply file.
+/// Calling begin() writes the header and returns a PlyElementWriter, which the vertices and faces are pushed to
+pub struct PlyWriter {
+    format: PlyFormat,
+    properties: Vec<(String, PlyType)>,
+    with_faces: bool,
+}
+
+impl PlyWriter {
+    /// Creates a new writer without any properties
+    pub fn new(format: PlyFormat) -> Self {
+        Self {
+            format,
+            properties: Vec::new(),
+            with_faces: false,
+        }
+    }
+    /// Declares a further vertex property
+    pub fn vertex_property(mut self, name: &str, t: PlyType) -> Self {
+        self.properties.push((name.to_string(), t));
+        self
+    }
+    /// Declares the x, y and z vertex properties of type t
+    pub fn positions(self, t: PlyType) -> Self {
+        self.vertex_property("x", t)
+            .vertex_property("y", t)
+            .vertex_property("z", t)
+    }
+    /// Declares a face element, storing the vertex indices of each face
+    pub fn with_faces(mut self) -> Self {
+        self.with_faces = true;
+        self
+    }
+    /// Writes the header and returns the writer for the n_vertices vertices and n_faces faces (ignored if faces weren't declared)
+    pub fn begin<W>(
+        self,
+        write: &mut W,
+        n_vertices: usize,
+        n_faces: usize,
+    ) -> PlyResult<PlyElementWriter<'_, W>>
+    where
+        W: Write,
+    {
+        for (name, _) in self.properties.iter() {
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(PlyError::InvalidPropertyName(name.clone()));
+            }
+        }
+
+        let keyword = match self.format {
+            PlyFormat::Ascii => "ascii",
+            PlyFormat::BigEndian => "binary_big_endian",
+            PlyFormat::LittleEndian => "binary_little_endian",
+        };
+
+        let mut header = format!(
+            "ply\nformat {} 1.0\ncomment Created by rust-3d\nelement vertex {}\n",
+            keyword, n_vertices
+        );
+        for (name, t) in self.properties.iter() {
+            header += &format!("property {} {}\n", t.keyword(), name);
+        }
+        if self.with_faces {
+            header += &format!(
+                "element face {}\nproperty list uchar int vertex_indices\n",
+                n_faces
+            );
+        }
+        header += "end_header\n";
+        write.write_all(header.as_bytes())?;
+
+        Ok(PlyElementWriter {
+            write,
+            format: self.format,
+            types: self.properties.into_iter().map(|(_, t)| t).collect(),
+            n_vertices_left: n_vertices,
+            n_faces_left: if self.with_faces { n_faces } else { 0 },
+            line: String::new(),
+        })
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// PlyElementWriter, writes the vertices and faces of a .ply file as declared by PlyWriter.
+/// All vertices have to be pushed before the faces
+pub struct PlyElementWriter<'a, W>
+where
+    W: Write,
+{
+    write: &'a mut W,
+    format: PlyFormat,
+    types: Vec<PlyType>,
+    n_vertices_left: usize,
+    n_faces_left: usize,
+    line: String,
+}
+
+impl<'a, W> PlyElementWriter<'a, W>
+where
+    W: Write,
+{
+    /// Writes a vertex, values must hold one value per declared property (in order of declaration)
+    pub fn push_vertex(&mut self, values: &[f64]) -> PlyResult<()> {
+        if values.len() != self.types.len() {
+            return Err(PlyError::RowIncorrectLength);
+        }
+        if self.n_vertices_left == 0 {
+            return Err(PlyError::ElementCountIncorrect);
+        }
+        self.n_vertices_left -= 1;
+
+        match self.format {
+            PlyFormat::Ascii => {
+                self.line.clear();
+                for (i, (value, t)) in values.iter().zip(self.types.iter()).enumerate() {
+                    if i != 0 {
+                        self.line.push(' ');
+                    }
+                    self.line += &ascii_value(*value, *t);
+                }
+                self.line.push('\n');
+                self.write.write_all(self.line.as_bytes())?;
+            }
+            PlyFormat::BigEndian | PlyFormat::LittleEndian => {
+                let big_endian = self.format == PlyFormat::BigEndian;
+                for (value, t) in values.iter().zip(self.types.iter()) {
+                    write_binary_value(self.write, *value, *t, big_endian)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+    /// Writes a face, which must be declared via PlyWriter::with_faces.
+    /// All vertices have to be written beforehand
+    pub fn push_face(&mut self, vertex_ids: &[usize]) -> PlyResult<()> {
+        if self.n_vertices_left != 0 || self.n_faces_left == 0 {
+            return Err(PlyError::ElementCountIncorrect);
+        }
+        // the index count is written as uchar
+        if vertex_ids.len() > 255 {
+            return Err(PlyError::FaceTooLarge(vertex_ids.len()));
+        }
+        self.n_faces_left -= 1;
+
+        match self.format {
+            PlyFormat::Ascii => {
+                self.line.clear();
+                self.line += &vertex_ids.len().to_string();
+                for id in vertex_ids {
+                    self.line.push(' ');
+                    self.line += &id.to_string();
+                }
+                self.line.push('\n');
+                self.write.write_all(self.line.as_bytes())?;
+            }
+            PlyFormat::BigEndian | PlyFormat::LittleEndian => {
+                let big_endian = self.format == PlyFormat::BigEndian;
+                write_binary_value(
+                    self.write,
+                    vertex_ids.len() as f64,
+                    PlyType::UChar,
+                    big_endian,
+                )?;
+                for id in vertex_ids {
+                    write_binary_value(self.write, *id as f64, PlyType::Int, big_endian)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+    /// Finishes writing, fails if fewer elements than declared were written
+    pub fn finish(self) -> PlyResult<()> {
+        if self.n_vertices_left != 0 || self.n_faces_left != 0 {
+            return Err(PlyError::ElementCountIncorrect);
+        }
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+
+fn ascii_value(value: f64, t: PlyType) -> String {
+    match t {
+        PlyType::Float | PlyType::Double => value.to_string(),
+        PlyType::Char | PlyType::Short | PlyType::Int => (value as i64).to_string(),
+        PlyType::UChar | PlyType::UShort | PlyType::UInt => (value as u64).to_string(),
+    }
+}
+
+fn write_binary_value<W>(write: &mut W, value: f64, t: PlyType, big_endian: bool) -> PlyResult<()>
+where
+    W: Write,
+{
+    macro_rules! bytes {
+        ($x:expr) => {
+            if big_endian {
+                write.write_all(&$x.to_be_bytes())
+            } else {
+                write.write_all(&$x.to_le_bytes())
+            }
+        };
+    }
+
+    match t {
+        PlyType::Char => bytes!(value as i8),
+        PlyType::UChar => bytes!(value as u8),
+        PlyType::Short => bytes!(value as i16),
+        PlyType::UShort => bytes!(value as u16),
+        PlyType::Int => bytes!(value as i32),
+        PlyType::UInt => bytes!(value as u32),
+        PlyType::Float => bytes!(value as f32),
+        PlyType::Double => bytes!(value),
+    }?;
+
+    Ok(())
+}
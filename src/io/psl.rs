@@ -280,6 +280,8 @@ impl fmt::Display for PslError {
     }
 }
 
+impl std::error::Error for PslError {}
+
 impl From<ioError> for PslError {
     fn from(_error: ioError) -> Self {
         PslError::AccessFile
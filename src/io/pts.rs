@@ -222,6 +222,8 @@ impl fmt::Display for PtsError {
     }
 }
 
+impl std::error::Error for PtsError {}
+
 impl From<ioError> for PtsError {
     fn from(_error: ioError) -> Self {
         PtsError::AccessFile
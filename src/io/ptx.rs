@@ -291,6 +291,8 @@ impl fmt::Display for PtxError {
     }
 }
 
+impl std::error::Error for PtxError {}
+
 impl From<ioError> for PtxError {
     fn from(_error: ioError) -> Self {
         PtxError::AccessFile
@@ -52,6 +52,28 @@ pub struct StlFace<P> {
 
 //------------------------------------------------------------------------------
 
+/// Options used when saving .stl files
+pub struct StlSaveOptions {
+    /// Name of the solid for the ASCII format, must not contain line breaks.
+    /// Header for the binary format, must not be longer than 80 bytes (it is not truncated, but results in an error)
+    pub header: String,
+}
+
+impl StlSaveOptions {
+    /// Creates new options with the given header / solid name
+    pub fn new(header: String) -> Self {
+        Self { header }
+    }
+}
+
+impl Default for StlSaveOptions {
+    fn default() -> Self {
+        Self::new("STL generated by rust-3d".to_string())
+    }
+}
+
+//------------------------------------------------------------------------------
+
 /// Saves an IsMesh3D in the ASCII .stl file format
 pub fn save_stl_ascii<M, P, W>(write: &mut W, mesh: &M) -> StlResult<()>
 where
@@ -59,11 +81,30 @@ where
     P: IsBuildable3D,
     W: Write,
 {
-    write.write_all(b"solid STL generated by rust-3d\n")?;
+    save_stl_ascii_with_options(write, mesh, &StlSaveOptions::default())
+}
+
+/// Saves an IsMesh3D in the ASCII .stl file format, using the header of the options as name of the solid.
+/// Returns StlError::InvalidHeader if the name contains a line break
+pub fn save_stl_ascii_with_options<M, P, W>(
+    write: &mut W,
+    mesh: &M,
+    options: &StlSaveOptions,
+) -> StlResult<()>
+where
+    M: IsMesh3D<P>,
+    P: IsBuildable3D,
+    W: Write,
+{
+    if options.header.contains(['\n', '\r']) {
+        return Err(StlError::InvalidHeader);
+    }
+
+    write.write_all(format!("solid {}\n", options.header).as_bytes())?;
 
     for i in 0..mesh.num_faces() {
         let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
-        let n = mesh.face_normal(FId(i)).unwrap(); // safe since iterating num_faces
+        let n = face_normal_or_zero(mesh, FId(i));
         let buffer = "facet normal ".to_string()
             + &str_exp(&n)
             + "\n"
@@ -81,10 +122,85 @@ where
             + "endfacet\n";
         write.write_all(buffer.as_bytes())?;
     }
-    write.write_all(b"endsolid STL generated by rust-3d\n")?;
+    write.write_all(format!("endsolid {}\n", options.header).as_bytes())?;
+    Ok(())
+}
+
+/// Serializes an IsMesh3D into the bytes of an ASCII .stl file
+pub fn mesh_to_stl_ascii_bytes<M, P>(mesh: &M) -> StlResult<Vec<u8>>
+where
+    M: IsMesh3D<P>,
+    P: IsBuildable3D,
+{
+    let mut result = Vec::new();
+    save_stl_ascii(&mut result, mesh)?;
+    Ok(result)
+}
+
+/// Saves an IsMesh3D in the binary .stl file format
+pub fn save_stl_binary<M, P, W>(write: &mut W, mesh: &M) -> StlResult<()>
+where
+    M: IsMesh3D<P>,
+    P: IsBuildable3D,
+    W: Write,
+{
+    save_stl_binary_with_options(write, mesh, &StlSaveOptions::default())
+}
+
+/// Saves an IsMesh3D in the binary .stl file format, using the header of the options (padded with zeros to 80 bytes).
+/// Returns StlError::InvalidHeader if the header is longer than 80 bytes and StlError::InvalidFaceCount if the mesh has too many faces
+pub fn save_stl_binary_with_options<M, P, W>(
+    write: &mut W,
+    mesh: &M,
+    options: &StlSaveOptions,
+) -> StlResult<()>
+where
+    M: IsMesh3D<P>,
+    P: IsBuildable3D,
+    W: Write,
+{
+    let n_faces = mesh.num_faces();
+    if n_faces > MAX_TRIANGLES_BINARY as usize {
+        return Err(StlError::InvalidFaceCount);
+    }
+
+    let header = options.header.as_bytes();
+    if header.len() > 80 {
+        return Err(StlError::InvalidHeader);
+    }
+    let mut buffer = [0u8; 80];
+    buffer[..header.len()].copy_from_slice(header);
+    write.write_all(&buffer)?;
+    write.write_all(&(n_faces as u32).to_le_bytes())?;
+
+    for i in 0..n_faces {
+        let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+        let n = face_normal_or_zero(mesh, FId(i));
+        write_stl_triangle(
+            write,
+            &StlFace {
+                a: v1,
+                b: v2,
+                c: v3,
+                n,
+            },
+        )?;
+    }
+
     Ok(())
 }
 
+/// Serializes an IsMesh3D into the bytes of a binary .stl file
+pub fn mesh_to_stl_binary_bytes<M, P>(mesh: &M) -> StlResult<Vec<u8>>
+where
+    M: IsMesh3D<P>,
+    P: IsBuildable3D,
+{
+    let mut result = Vec::new();
+    save_stl_binary(&mut result, mesh)?;
+    Ok(result)
+}
+
 //------------------------------------------------------------------------------
 
 /// Iterator to incrementally load a .stl file
@@ -94,6 +210,7 @@ where
     R: BufRead,
 {
     inner: BinaryOrAsciiIterator<P, R>,
+    normal_handling: NormalHandling,
 }
 
 impl<P, R> StlIterator<P, R>
@@ -102,15 +219,21 @@ where
     R: BufRead,
 {
     pub fn new(mut read: R, format: StlFormat) -> StlIOResult<Self> {
-        if is_ascii(&mut read, format).simple()? {
-            Ok(Self {
-                inner: BinaryOrAsciiIterator::Ascii(StlAsciiIterator::new(read)),
-            })
+        let inner = if is_ascii(&mut read, format).simple()? {
+            BinaryOrAsciiIterator::Ascii(StlAsciiIterator::new(read))
         } else {
-            Ok(Self {
-                inner: BinaryOrAsciiIterator::Binary(StlBinaryIterator::new(read)),
-            })
-        }
+            BinaryOrAsciiIterator::Binary(StlBinaryIterator::new(read))
+        };
+        Ok(Self {
+            inner,
+            normal_handling: NormalHandling::AsStored,
+        })
+    }
+
+    /// Sets how the stored face normals are handled (used as they are by default)
+    pub fn normal_handling(mut self, handling: NormalHandling) -> Self {
+        self.normal_handling = handling;
+        self
     }
 }
 
@@ -122,9 +245,26 @@ where
     type Item = StlIOResult<DataReserve<StlFace<P>>>;
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        match &mut self.inner {
+        let next = match &mut self.inner {
             BinaryOrAsciiIterator::Ascii(x) => x.next(),
             BinaryOrAsciiIterator::Binary(x) => x.next(),
+        };
+
+        match next {
+            Some(Ok(DataReserve::Data(mut face))) => {
+                let recompute = match self.normal_handling {
+                    NormalHandling::AsStored => false,
+                    NormalHandling::Recompute => true,
+                    NormalHandling::RecomputeIfZero => {
+                        face.n.x() == 0.0 && face.n.y() == 0.0 && face.n.z() == 0.0
+                    }
+                };
+                if recompute {
+                    face.n = winding_normal(&face.a, &face.b, &face.c);
+                }
+                Some(Ok(DataReserve::Data(face)))
+            }
+            x => x,
         }
     }
 }
@@ -345,17 +485,83 @@ where
     R: BufRead,
     IPN: IsPushable<P>,
 {
+    load_stl_mesh_duped_with_normal_handling(
+        read,
+        format,
+        NormalHandling::AsStored,
+        mesh,
+        face_normals,
+    )
+}
+
+/// Loads a Mesh from .stl file with duplicate vertices, handling the stored face normals as defined
+pub fn load_stl_mesh_duped_with_normal_handling<EM, P, R, IPN>(
+    read: R,
+    format: StlFormat,
+    normal_handling: NormalHandling,
+    mesh: &mut EM,
+    face_normals: &mut IPN,
+) -> StlIOResult<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D + Clone,
+    R: BufRead,
+    IPN: IsPushable<P>,
+{
+    let iterator = StlIterator::new(read, format)?.normal_handling(normal_handling);
+
+    for fr in iterator {
+        match fr? {
+            DataReserve::Reserve(n) => {
+                mesh.reserve_vertices(3 * n);
+                mesh.reserve_faces(n);
+            }
+            DataReserve::Data(face) => {
+                mesh.add_face(face.a, face.b, face.c);
+                face_normals.push(face.n);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads a Mesh from .stl file with duplicate vertices, calling progress every n_faces faces with the number of loaded and total faces.
+/// The total is only known for binary files and 0 otherwise. Loading is aborted with StlError::Cancelled if progress returns false
+pub fn load_stl_mesh_duped_with_progress<EM, P, R, IPN>(
+    read: R,
+    format: StlFormat,
+    mesh: &mut EM,
+    face_normals: &mut IPN,
+    n_faces: usize,
+    progress: &mut dyn FnMut(usize, usize) -> bool,
+) -> StlIOResult<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D + Clone,
+    R: BufRead,
+    IPN: IsPushable<P>,
+{
+    let n_faces = n_faces.max(1);
     let iterator = StlIterator::new(read, format)?;
 
+    let mut total = 0;
+    let mut done = 0;
+
     for fr in iterator {
         match fr? {
             DataReserve::Reserve(n) => {
                 mesh.reserve_vertices(3 * n);
                 mesh.reserve_faces(n);
+                total = n;
             }
             DataReserve::Data(face) => {
                 mesh.add_face(face.a, face.b, face.c);
                 face_normals.push(face.n);
+                done += 1;
+                if done % n_faces == 0 && !progress(done, total) {
+                    return Err(StlError::Cancelled).simple();
+                }
             }
         }
     }
@@ -425,6 +631,78 @@ where
 
 //------------------------------------------------------------------------------
 
+/// Loads a Mesh from .stl file with unique vertices, dropping invalid triangles.
+/// Vertices are considered equal if their coordinates round to the same cell of a grid with the given size,
+/// this way vertices that differ only by floating point noise are merged.
+/// Note that vertices close to, but on different sides of a cell border, are not merged
+pub fn load_stl_mesh_unique_quantized<EM, P, R, IPN>(
+    read: R,
+    format: StlFormat,
+    grid_size: Positive,
+    mesh: &mut EM,
+    face_normals: &mut IPN,
+) -> StlIOResult<()>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3>,
+    P: IsBuildable3D + Clone,
+    R: BufRead,
+    IPN: IsPushable<P>,
+{
+    let mut map = FnvHashMap::default();
+    let iterator = StlIterator::<P, R>::new(read, format)?;
+
+    let cell_of = |p: &P| -> (i64, i64, i64) {
+        (
+            (p.x() / *grid_size).round() as i64,
+            (p.y() / *grid_size).round() as i64,
+            (p.z() / *grid_size).round() as i64,
+        )
+    };
+
+    for fr in iterator {
+        match fr? {
+            DataReserve::Reserve(n) => {
+                //Can't reserve vertices since not sure how many are unique
+                mesh.reserve_faces(n);
+                face_normals.reserve(n);
+            }
+            DataReserve::Data(face) => {
+                let [a, b, c, n] = [face.a, face.b, face.c, face.n];
+                let id_a = *map.entry(cell_of(&a)).or_insert_with(|| {
+                    let value = mesh.num_vertices();
+                    mesh.add_vertex(a);
+                    value
+                });
+
+                let id_b = *map.entry(cell_of(&b)).or_insert_with(|| {
+                    let value = mesh.num_vertices();
+                    mesh.add_vertex(b);
+                    value
+                });
+
+                let id_c = *map.entry(cell_of(&c)).or_insert_with(|| {
+                    let value = mesh.num_vertices();
+                    mesh.add_vertex(c);
+                    value
+                });
+
+                // Ignore this issues since this only fails if a triangle uses a vertex multiple times
+                // Simply do not add this triangle and normal
+                if mesh
+                    .try_add_connection(VId(id_a), VId(id_b), VId(id_c))
+                    .is_ok()
+                {
+                    face_normals.push(n);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+//------------------------------------------------------------------------------
+
 /// Loads points from .stl file as triplets into IsPushable<IsBuildable3D>
 pub fn load_stl_triplets<IP, P, R, IPN>(
     read: R,
@@ -512,6 +790,24 @@ where
     })
 }
 
+#[inline(always)]
+fn write_stl_triangle<P, W>(write: &mut W, face: &StlFace<P>) -> StlResult<()>
+where
+    P: Is3D,
+    W: Write,
+{
+    // size for StlTriangle + u16 attribute
+    let mut buffer = [0u8; 50];
+    for (i, p) in [&face.n, &face.a, &face.b, &face.c].iter().enumerate() {
+        for (j, value) in [p.x(), p.y(), p.z()].iter().enumerate() {
+            let start = 12 * i + 4 * j;
+            buffer[start..start + 4].copy_from_slice(&(*value as f32).to_le_bytes());
+        }
+    }
+    write.write_all(&buffer)?;
+    Ok(())
+}
+
 //------------------------------------------------------------------------------
 
 fn read_stl_facet<P, R>(
@@ -541,7 +837,7 @@ where
     line = trim_start(fetch_line(read, line_buffer).index(*i_line)?);
     *i_line += 1;
 
-    if !line.starts_with(b"outer loop") {
+    if !starts_with_words(line, &[b"outer", b"loop"]) {
         return Err(StlError::Loop).line(*i_line, line);
     }
 
@@ -585,6 +881,43 @@ where
 
 //------------------------------------------------------------------------------
 
+/// Normal of the face, zero for degenerate faces
+fn face_normal_or_zero<M, P>(mesh: &M, fid: FId) -> P
+where
+    M: IsMesh3D<P>,
+    P: IsBuildable3D,
+{
+    match mesh.face_normal(fid) {
+        Ok(n) => P::new(n.x(), n.y(), n.z()),
+        Err(_) => P::new(0.0, 0.0, 0.0),
+    }
+}
+
+/// Normalized normal of the triangle a, b, c (counter clockwise winding), zero for degenerate triangles
+fn winding_normal<P>(a: &P, b: &P, c: &P) -> P
+where
+    P: IsBuildable3D,
+{
+    let winding = face_winding(a, b, c);
+    let length = *winding.abs();
+
+    if length == 0.0 || !length.is_finite() {
+        P::new(0.0, 0.0, 0.0)
+    } else {
+        P::new(winding.x / length, winding.y / length, winding.z / length)
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Checks whether line starts with the given words, allowing any amount of spaces / tabs between them
+fn starts_with_words(line: &[u8], expected: &[&[u8]]) -> bool {
+    let mut words = to_words_skip_empty(line);
+    expected.iter().all(|e| words.next() == Some(*e))
+}
+
+//------------------------------------------------------------------------------
+
 fn read_stl_vertex<P>(line: &[u8]) -> Option<P>
 where
     P: IsBuildable3D,
@@ -647,6 +980,18 @@ impl Default for StlFormat {
     }
 }
 
+/// Defines how the face normals stored within .stl files are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalHandling {
+    /// Use the stored normals as they are
+    #[default]
+    AsStored,
+    /// Always recompute the normals from the vertex winding
+    Recompute,
+    /// Recompute the normals from the vertex winding if the stored ones are zero
+    RecomputeIfZero,
+}
+
 //------------------------------------------------------------------------------
 
 /// Error type for .stl file operations
@@ -660,6 +1005,8 @@ pub enum StlError {
     Vertex,
     Loop,
     EndLoop,
+    InvalidHeader,
+    Cancelled,
 }
 
 /// Result type for .stl file operations
@@ -680,6 +1027,8 @@ impl fmt::Debug for StlError {
             Self::Vertex => write!(f, "Unable to parse vertex"),
             Self::Loop => write!(f, "Unable to parse loop"),
             Self::EndLoop => write!(f, "Unable to parse endloop"),
+            Self::InvalidHeader => write!(f, "The header is invalid for the used format"),
+            Self::Cancelled => write!(f, "Loading was cancelled"),
         }
     }
 }
@@ -690,6 +1039,8 @@ impl fmt::Display for StlError {
     }
 }
 
+impl std::error::Error for StlError {}
+
 impl From<ioError> for StlError {
     fn from(_error: ioError) -> Self {
         StlError::AccessFile
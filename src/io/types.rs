@@ -80,6 +80,20 @@ impl<T> From<DataReserve<T>> for FaceDataReserve<T> {
 
 //------------------------------------------------------------------------------
 
+/// Defines how loaders handle non-finite coordinates (NaN or infinite values, e.g. used for missing data)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonFiniteHandling {
+    /// Load such positions as they are (they might e.g. be filtered later on)
+    #[default]
+    Accept,
+    /// Silently skip such positions
+    Skip,
+    /// Fail with an error
+    Error,
+}
+
+//------------------------------------------------------------------------------
+
 /// Wrapper type with additional line information
 #[derive(Debug)]
 pub enum WithLineInfo<T> {
@@ -88,6 +102,15 @@ pub enum WithLineInfo<T> {
     Line(usize, String, T),
 }
 
+impl<T> WithLineInfo<T> {
+    /// Returns the wrapped error, dropping the line information
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::None(x) | Self::Index(_, x) | Self::Line(_, _, x) => x,
+        }
+    }
+}
+
 //------------------------------------------------------------------------------
 
 impl<T> Display for WithLineInfo<T>
@@ -103,6 +126,17 @@ where
     }
 }
 
+impl<T> std::error::Error for WithLineInfo<T>
+where
+    T: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::None(x) | Self::Index(_, x) | Self::Line(_, _, x) => Some(x),
+        }
+    }
+}
+
 //------------------------------------------------------------------------------
 
 /// Result type for errors with additional line information
@@ -23,6 +23,7 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //! Module for interal utility functions for IO operations
 
 use std::{
+    collections::VecDeque,
     fmt,
     io::{BufRead, Error as ioError, Read},
     str::FromStr,
@@ -99,18 +100,14 @@ where
         return Err(FetchLineError);
     }
 
-    // We must drop the '\n' we read_until for sure
-    // And might also have to drop additional whitespace
-    let mut ignore_end = 1;
-    for i in 1..line_buffer.len() {
-        if (line_buffer[line_buffer.len() - i - 1] as char).is_whitespace() {
-            ignore_end += 1;
-        } else {
-            break;
-        }
+    // Drop the '\n' we read_until (if any, the last line might not have one)
+    // and additional whitespace such as the '\r' of CRLF line endings
+    let mut end = line_buffer.len();
+    while end > 0 && (line_buffer[end - 1] as char).is_whitespace() {
+        end -= 1;
     }
 
-    Ok(&line_buffer[0..line_buffer.len() - ignore_end])
+    Ok(&line_buffer[0..end])
 }
 
 //------------------------------------------------------------------------------
@@ -132,3 +129,13 @@ impl From<ioError> for FetchLineError {
         FetchLineError {}
     }
 }
+
+//------------------------------------------------------------------------------
+
+/// Splits the polygon into triangles (0, i, i+1), pushing them to out
+#[inline(always)]
+pub fn fan_triangulate(indices: &[usize], out: &mut VecDeque<[usize; 3]>) {
+    for i in 1..indices.len().saturating_sub(1) {
+        out.push_back([indices[0], indices[i], indices[i + 1]]);
+    }
+}
@@ -190,6 +190,8 @@ impl fmt::Display for XyError {
     }
 }
 
+impl std::error::Error for XyError {}
+
 impl From<ioError> for XyError {
     fn from(_error: ioError) -> Self {
         XyError::AccessFile
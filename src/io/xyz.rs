@@ -25,7 +25,7 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 use crate::*;
 
 use std::{
-    fmt,
+    fmt::{self, Write as fmtWrite},
     io::{BufRead, Error as ioError, Write},
     iter::FusedIterator,
     marker::PhantomData,
@@ -36,17 +36,15 @@ use super::{types::*, utils::*};
 //------------------------------------------------------------------------------
 
 /// Iterator to incrementally load a .xyz file
+/// Comment lines (starting with "#" by default) and a single non-numeric header line before the data are skipped
+/// Blank lines are skipped, invalid lines are returned as errors and iteration continues with the next line
 pub struct XyzIterator<P, R>
 where
     P: IsBuildable3D,
     R: BufRead,
 {
-    read: R,
-    is_done: bool,
-    i_line: usize,
-    line_buffer: Vec<u8>,
-    delim_determined: bool,
-    delim: u8,
+    lines: XyzLines<R>,
+    non_finite: NonFiniteHandling,
     phantom_p: PhantomData<P>,
 }
 
@@ -57,39 +55,50 @@ where
 {
     pub fn new(read: R) -> Self {
         Self {
-            read,
-            is_done: false,
-            i_line: 0,
-            line_buffer: Vec::new(),
-            delim_determined: false,
-            delim: 0,
+            lines: XyzLines::new(read),
+            non_finite: NonFiniteHandling::Accept,
             phantom_p: PhantomData,
         }
     }
 
-    #[inline(always)]
-    fn fetch_one(delim_determined: &mut bool, delim: &mut u8, line: &[u8]) -> XyzResult<P> {
-        if !*delim_determined {
-            *delim = estimate_delimiter(2, &line).ok_or(XyzError::EstimateDelimiter)?;
-            *delim_determined = true;
-        }
+    /// Sets the prefix of comment lines which are skipped ("#" by default), None to disable skipping of comments
+    pub fn comment_prefix(mut self, prefix: Option<&str>) -> Self {
+        self.lines.comment_prefix = prefix.map(|x| x.as_bytes().to_vec());
+        self
+    }
 
-        let mut words = line.split(|x| *x == *delim).skip_empty();
+    /// Sets how positions with non-finite coordinates are handled (accepted by default)
+    pub fn non_finite(mut self, handling: NonFiniteHandling) -> Self {
+        self.non_finite = handling;
+        self
+    }
 
-        let x = words
-            .next()
-            .and_then(|word| from_ascii(word))
-            .ok_or(XyzError::Vertex)?;
+    /// Creates a new iterator which splits on the given delimiter instead of estimating it
+    pub fn new_with_delimiter(read: R, delimiter: &str) -> Self {
+        let mut result = Self::new(read);
+        result.lines.custom_delim = Some(delimiter.to_string());
+        result
+    }
 
-        let y = words
-            .next()
-            .and_then(|word| from_ascii(word))
-            .ok_or(XyzError::Vertex)?;
+    /// Whether p should be returned, according to the non-finite handling
+    #[inline(always)]
+    fn accept(&self, p: &P) -> XyzIOResult<bool> {
+        if p.x().is_finite() && p.y().is_finite() && p.z().is_finite() {
+            return Ok(true);
+        }
+        match self.non_finite {
+            NonFiniteHandling::Accept => Ok(true),
+            NonFiniteHandling::Skip => Ok(false),
+            NonFiniteHandling::Error => Err(XyzError::NonFinite(self.lines.i_line))
+                .line(self.lines.i_line, self.lines.line()),
+        }
+    }
 
-        let z = words
-            .next()
-            .and_then(|word| from_ascii(word))
-            .ok_or(XyzError::Vertex)?;
+    #[inline(always)]
+    fn parse_words(words: &mut dyn Iterator<Item = &[u8]>) -> XyzResult<P> {
+        let x = words.next().and_then(from_ascii).ok_or(XyzError::Vertex)?;
+        let y = words.next().and_then(from_ascii).ok_or(XyzError::Vertex)?;
+        let z = words.next().and_then(from_ascii).ok_or(XyzError::Vertex)?;
 
         Ok(P::new(x, y, z))
     }
@@ -103,22 +112,17 @@ where
     type Item = XyzIOResult<P>;
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.is_done {
-            return None;
-        }
-        if let Ok(line) = fetch_line(&mut self.read, &mut self.line_buffer) {
-            self.i_line += 1;
-            Some(
-                Self::fetch_one(&mut self.delim_determined, &mut self.delim, line)
-                    .line(self.i_line, line)
-                    .map_err(|e| {
-                        self.is_done = true;
-                        e
-                    }),
-            )
-        } else {
-            self.is_done = true;
-            None
+        loop {
+            let result = self
+                .lines
+                .next_parsed(Self::parse_words)?
+                .and_then(|p| self.accept(&p).map(|accepted| (accepted, p)));
+
+            match result {
+                Ok((false, _)) => continue,
+                Ok((true, p)) => return Some(Ok(p)),
+                Err(e) => return Some(Err(e)),
+            }
         }
     }
 }
@@ -132,6 +136,141 @@ where
 
 //------------------------------------------------------------------------------
 
+/// Reads the data lines of a .xyz file, shared by all .xyz loaders
+/// Blank lines, comment lines and a single non-numeric header line before the data are skipped
+struct XyzLines<R>
+where
+    R: BufRead,
+{
+    read: R,
+    is_done: bool,
+    i_line: usize,
+    line_buffer: Vec<u8>,
+    line_len: usize,
+    delim_determined: bool,
+    delim: u8,
+    custom_delim: Option<String>,
+    comment_prefix: Option<Vec<u8>>,
+    data_seen: bool,
+    header_error: Option<WithLineInfo<XyzError>>,
+}
+
+impl<R> XyzLines<R>
+where
+    R: BufRead,
+{
+    fn new(read: R) -> Self {
+        Self {
+            read,
+            is_done: false,
+            i_line: 0,
+            line_buffer: Vec::new(),
+            line_len: 0,
+            delim_determined: false,
+            delim: 0,
+            custom_delim: None,
+            comment_prefix: Some(b"#".to_vec()),
+            data_seen: false,
+            header_error: None,
+        }
+    }
+
+    /// The most recently read line
+    fn line(&self) -> &[u8] {
+        &self.line_buffer[..self.line_len]
+    }
+
+    /// Reads the next data line and passes its words to parse
+    /// Returns None once the input is exhausted
+    fn next_parsed<T, F>(&mut self, mut parse: F) -> Option<XyzIOResult<T>>
+    where
+        F: FnMut(&mut dyn Iterator<Item = &[u8]>) -> XyzResult<T>,
+    {
+        if self.is_done {
+            return None;
+        }
+        while let Ok(line) = fetch_line(&mut self.read, &mut self.line_buffer) {
+            self.i_line += 1;
+            self.line_len = line.len();
+
+            if trim_start(line).is_empty() {
+                continue;
+            }
+
+            if let Some(prefix) = &self.comment_prefix {
+                if trim_start(line).starts_with(prefix) {
+                    continue;
+                }
+            }
+
+            let fetched = Self::parse_line(
+                &mut self.delim_determined,
+                &mut self.delim,
+                &self.custom_delim,
+                line,
+                &mut parse,
+            );
+
+            // a single line without any numbers before the data is considered a header
+            // other invalid lines are reported, even if they are the first ones
+            if !self.data_seen {
+                if let (Err(e), None) = (&fetched, &self.header_error) {
+                    if !has_numeric_word(line) {
+                        self.header_error = Some(WithLineInfo::Line(
+                            self.i_line,
+                            String::from_utf8_lossy(line).to_string(),
+                            e.clone(),
+                        ));
+                        self.delim_determined = false;
+                        continue;
+                    }
+                }
+                self.data_seen = true;
+            }
+
+            return Some(fetched.line(self.i_line, line));
+        }
+        self.is_done = true;
+        // a file only consisting of a header is invalid
+        if !self.data_seen {
+            return self.header_error.take().map(Err);
+        }
+        None
+    }
+
+    #[inline(always)]
+    fn parse_line<T, F>(
+        delim_determined: &mut bool,
+        delim: &mut u8,
+        custom_delim: &Option<String>,
+        line: &[u8],
+        parse: &mut F,
+    ) -> XyzResult<T>
+    where
+        F: FnMut(&mut dyn Iterator<Item = &[u8]>) -> XyzResult<T>,
+    {
+        if let Some(custom_delim) = custom_delim {
+            let text = std::str::from_utf8(line).map_err(|_| XyzError::Vertex)?;
+            let mut words = text
+                .split(custom_delim.as_str())
+                .map(|word| word.trim())
+                .filter(|word| !word.is_empty())
+                .map(|word| word.as_bytes());
+            return parse(&mut words);
+        }
+
+        if !*delim_determined {
+            *delim = estimate_delimiter(2, line).ok_or(XyzError::EstimateDelimiter)?;
+            *delim_determined = true;
+        }
+
+        let d = *delim;
+        parse(&mut line.split(|x| *x == d).skip_empty())
+    }
+}
+
+//------------------------------------------------------------------------------
+
 /// Saves an IsRandomAccessible<Is3D> as x y z coordinates with a specified delimiter between coordinates and positions. E.g. used to create the .xyz file format or .csv files
 pub fn save_xyz<RA, P, W>(
     write: &mut W,
@@ -146,14 +285,53 @@ where
 {
     let n = ra.len();
     for i in 0..n {
-        let ref p = ra[i];
-        let buffer = p.x().to_string()
-            + delim_coord
-            + &p.y().to_string()
-            + delim_coord
-            + &p.z().to_string()
-            + delim_pos;
-        write.write_all(buffer.as_bytes())?;
+        write_xyz_position(write, &ra[i], delim_coord, delim_pos)?;
+    }
+    Ok(())
+}
+
+/// Saves an IsRandomAccessible<Is3D> as x y z coordinates, just like save_xyz.
+/// The positions are collected within a reused buffer which is written every chunk positions, reducing the number of allocations and writes
+pub fn save_xyz_buffered<RA, P, W>(
+    write: &mut W,
+    ra: &RA,
+    delim_coord: &str,
+    delim_pos: &str,
+    chunk: usize,
+) -> XyzResult<()>
+where
+    RA: IsRandomAccessible<P>,
+    P: Is3D,
+    W: Write,
+{
+    let chunk = chunk.max(1);
+    let mut buffer = String::new();
+    let n = ra.len();
+    for i in 0..n {
+        push_xyz_position(&mut buffer, &ra[i], delim_coord, delim_pos);
+        if (i + 1) % chunk == 0 || i + 1 == n {
+            write.write_all(buffer.as_bytes())?;
+            buffer.clear();
+        }
+    }
+    Ok(())
+}
+
+/// Saves the positions of an iterator as x y z coordinates with a specified delimiter between coordinates and positions.
+/// Each position is written as soon as the iterator yields it, so lazily produced positions can be saved without collecting them
+pub fn save_xyz_iter<P, W, I>(
+    write: &mut W,
+    iter: I,
+    delim_coord: &str,
+    delim_pos: &str,
+) -> XyzResult<()>
+where
+    I: IntoIterator<Item = P>,
+    P: Is3D,
+    W: Write,
+{
+    for p in iter {
+        write_xyz_position(write, &p, delim_coord, delim_pos)?;
     }
     Ok(())
 }
@@ -165,8 +343,110 @@ where
     P: IsBuildable3D,
     R: BufRead,
 {
-    let iterator = XyzIterator::new(read);
+    load_xyz_from_iterator(XyzIterator::new(read), ip)
+}
+
+/// Loads a IsPushable<Is3D> as x y z coordinates, split on the given delimiter instead of estimating it. E.g. used to load the .xyz file format or .csv file
+pub fn load_xyz_with_delimiter<IP, P, R>(read: R, ip: &mut IP, delimiter: &str) -> XyzIOResult<()>
+where
+    IP: IsPushable<P>,
+    P: IsBuildable3D,
+    R: BufRead,
+{
+    load_xyz_from_iterator(XyzIterator::new_with_delimiter(read, delimiter), ip)
+}
+
+/// Loads a IsPushable<Is3D> as x y z coordinates, handling positions with non-finite coordinates as defined. E.g. used to load the .xyz file format or .csv file
+pub fn load_xyz_handling_non_finite<IP, P, R>(
+    read: R,
+    ip: &mut IP,
+    handling: NonFiniteHandling,
+) -> XyzIOResult<()>
+where
+    IP: IsPushable<P>,
+    P: IsBuildable3D,
+    R: BufRead,
+{
+    load_xyz_from_iterator(XyzIterator::new(read).non_finite(handling), ip)
+}
+
+/// Loads a IsPushable<Is3D> as x y z coordinates, followed by optional normals (nx ny nz) and / or a scalar (e.g. intensity)
+/// The columns are determined by the first data line: 3 (x y z), 4 (x y z scalar), 6 (x y z nx ny nz) or 7 (x y z nx ny nz scalar)
+/// Sinks of columns which aren't present are left untouched. All further lines must have the same column count
+/// Comments, blank lines and a header are skipped just like by XyzIterator
+/// Zero length normals (commonly written for positions without a normal) are pushed as None, keeping normals aligned with the positions
+pub fn load_xyz_extended<IP, P, R>(
+    read: R,
+    ip: &mut IP,
+    normals: &mut Vec<Option<Norm3D>>,
+    extra: &mut Vec<f64>,
+) -> XyzIOResult<()>
+where
+    IP: IsPushable<P>,
+    P: IsBuildable3D,
+    R: BufRead,
+{
+    load_xyz_extended_from_iterator(XyzIterator::new(read), ip, normals, extra)
+}
+
+/// Loads the columns of a .xyz file just like load_xyz_extended, using the settings (comment prefix, delimiter, non-finite handling) of the iterator
+/// Lines skipped due to non-finite positions don't push normals or scalars either
+pub fn load_xyz_extended_from_iterator<IP, P, R>(
+    mut iterator: XyzIterator<P, R>,
+    ip: &mut IP,
+    normals: &mut Vec<Option<Norm3D>>,
+    extra: &mut Vec<f64>,
+) -> XyzIOResult<()>
+where
+    IP: IsPushable<P>,
+    P: IsBuildable3D,
+    R: BufRead,
+{
+    let mut n_columns = None;
+    let mut values = Vec::new();
+
+    while let Some(fetched) = iterator.lines.next_parsed(|words| {
+        values.clear();
+        for word in words {
+            values.push(from_ascii(word).ok_or(XyzError::Vertex)?);
+        }
+        Ok(())
+    }) {
+        fetched?;
+
+        let n = values.len();
+        match n_columns {
+            None if [3, 4, 6, 7].contains(&n) => n_columns = Some(n),
+            Some(expected) if expected == n => (),
+            _ => {
+                return Err(XyzError::ColumnCount(n))
+                    .line(iterator.lines.i_line, iterator.lines.line())
+            }
+        }
 
+        let p = P::new(values[0], values[1], values[2]);
+        if !iterator.accept(&p)? {
+            continue;
+        }
+
+        ip.push(p);
+        if n >= 6 {
+            normals.push(Norm3D::new(Point3D::new(values[3], values[4], values[5])).ok());
+        }
+        if n == 4 || n == 7 {
+            extra.push(values[n - 1]);
+        }
+    }
+
+    Ok(())
+}
+
+fn load_xyz_from_iterator<IP, P, R>(iterator: XyzIterator<P, R>, ip: &mut IP) -> XyzIOResult<()>
+where
+    IP: IsPushable<P>,
+    P: IsBuildable3D,
+    R: BufRead,
+{
     for p in iterator {
         ip.push(p?)
     }
@@ -174,13 +454,58 @@ where
     Ok(())
 }
 
+/// Whether any word of the line is a number. Header lines don't contain any
+#[inline(always)]
+fn has_numeric_word(line: &[u8]) -> bool {
+    line.split(|x| !(x.is_ascii_alphanumeric() || b"+-.".contains(x)))
+        .any(|word| word.iter().any(u8::is_ascii_digit) && from_ascii::<f64>(word).is_some())
+}
+
+fn write_xyz_position<P, W>(
+    write: &mut W,
+    p: &P,
+    delim_coord: &str,
+    delim_pos: &str,
+) -> XyzResult<()>
+where
+    P: Is3D,
+    W: Write,
+{
+    let mut buffer = String::new();
+    push_xyz_position(&mut buffer, p, delim_coord, delim_pos);
+    write.write_all(buffer.as_bytes())?;
+    Ok(())
+}
+
+#[inline(always)]
+fn push_xyz_position<P>(buffer: &mut String, p: &P, delim_coord: &str, delim_pos: &str)
+where
+    P: Is3D,
+{
+    // safe, since writing to a String can't fail
+    write!(
+        buffer,
+        "{}{}{}{}{}{}",
+        p.x(),
+        delim_coord,
+        p.y(),
+        delim_coord,
+        p.z(),
+        delim_pos
+    )
+    .unwrap();
+}
+
 //------------------------------------------------------------------------------
 
 /// Error type for .xyz file operations
+#[derive(Clone)]
 pub enum XyzError {
     EstimateDelimiter,
     AccessFile,
     Vertex,
+    ColumnCount(usize),
+    NonFinite(usize),
 }
 
 /// Result type for .xyz file operations
@@ -193,8 +518,10 @@ impl fmt::Debug for XyzError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Vertex => write!(f, "Unable to parse vertex"),
+            Self::ColumnCount(n) => write!(f, "Unexpected column count {}", n),
             Self::AccessFile => write!(f, "Unable to access file"),
             Self::EstimateDelimiter => write!(f, "Unable to estimate delimiter"),
+            Self::NonFinite(i_line) => write!(f, "Non-finite coordinate in line {}", i_line),
         }
     }
 }
@@ -205,6 +532,8 @@ impl fmt::Display for XyzError {
     }
 }
 
+impl std::error::Error for XyzError {}
+
 impl From<ioError> for XyzError {
     fn from(_error: ioError) -> Self {
         XyzError::AccessFile
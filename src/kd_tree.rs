@@ -224,6 +224,7 @@ where
         if pc.len() < n || sqr_dist_3d(search, &self.val) < sqr_dist_3d(search, &pc[&pc.len() - 1])
         {
             pc.push(self.val.clone());
+            Self::sort_and_limit(pc, search, n);
         }
 
         let comp = dimension_compare(search, &self.val, self.dimension);
@@ -407,18 +408,22 @@ where
         PSearch: Is3D,
         PFind: Is3D + Clone,
     {
+        let compare = |a: &PFind, b: &PFind| {
+            sqr_dist_3d(search, a)
+                .partial_cmp(&sqr_dist_3d(search, b))
+                .unwrap_or(Ordering::Equal)
+        };
+
+        if max_size == 0 {
+            pc.clear();
+            return;
+        }
+        // partial selection of the max_size closest, so only those have to be sorted
         if pc.len() > max_size {
-            pc.sort_by(|a, b| {
-                sqr_dist_3d(search, a)
-                    .partial_cmp(&sqr_dist_3d(search, b))
-                    .unwrap_or(Ordering::Equal)
-            });
-            let mut result: Vec<PFind>;
-            result = Vec::new();
-            for i in pc.iter().take(max_size) {
-                result.push(i.clone());
-            }
-            *pc = result;
+            pc.select_nth_unstable_by(max_size - 1, compare);
+            pc.truncate(max_size);
         }
+        // always sorting, since the last element is used as the current worst candidate
+        pc.sort_by(compare);
     }
 }
@@ -0,0 +1,154 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! KdTree2D, a KdTree within 2D space https://en.wikipedia.org/wiki/K-d_tree
+
+use crate::*;
+
+use std::cmp::Ordering;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// KdTree2D, a KdTree within 2D space https://en.wikipedia.org/wiki/K-d_tree
+pub struct KdTree2D<P>
+where
+    P: Is2D,
+{
+    root: Option<KdNode2D<P>>,
+}
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct KdNode2D<P>
+where
+    P: Is2D,
+{
+    pub left: Option<Box<KdNode2D<P>>>,
+    pub right: Option<Box<KdNode2D<P>>>,
+    pub val: P,
+    pub dimension: i8,
+}
+
+//------------------------------------------------------------------------------
+
+impl<P> KdTree2D<P>
+where
+    P: Is2D + Clone,
+{
+    /// Returns the number of elements within the tree
+    pub fn size(&self) -> usize {
+        match self.root {
+            None => 0,
+            Some(ref node) => node.size(),
+        }
+    }
+    /// Builds the tree from the given points
+    pub fn build(&mut self, pc: PointCloud2D<P>) -> Result<()> {
+        match pc.len() {
+            0 => Err(ErrorKind::TooFewPoints),
+            _ => {
+                self.root = Some(KdNode2D::new(0, pc.data));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<P> IsCircleSearchable<P> for KdTree2D<P>
+where
+    P: Is2D + Clone,
+{
+    fn in_circle(&self, circle: &Circle, result: &mut Vec<P>) {
+        if let Some(ref node) = self.root {
+            node.in_circle(circle, result);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+impl<P> KdNode2D<P>
+where
+    P: Is2D + Clone,
+{
+    pub fn new(dim: i8, mut pc: Vec<P>) -> Self {
+        let dimension = dim % 2;
+
+        pc.sort_by(|a, b| {
+            let (a, b) = match dimension {
+                0 => (a.x(), b.x()),
+                _ => (a.y(), b.y()),
+            };
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        });
+
+        let median = pc.len() / 2;
+        let pc_right = pc.split_off(median + 1);
+        let val = pc.pop().unwrap(); // safe since median < len
+        let pc_left = pc;
+
+        let left = match pc_left.len() {
+            0 => None,
+            _ => Some(Box::new(KdNode2D::new(dimension + 1, pc_left))),
+        };
+        let right = match pc_right.len() {
+            0 => None,
+            _ => Some(Box::new(KdNode2D::new(dimension + 1, pc_right))),
+        };
+
+        KdNode2D {
+            left,
+            right,
+            val,
+            dimension,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        1 + self.left.as_ref().map_or(0, |n| n.size()) + self.right.as_ref().map_or(0, |n| n.size())
+    }
+
+    pub fn in_circle(&self, circle: &Circle, pc: &mut Vec<P>) {
+        if sqr_dist_2d(&circle.center, &self.val) <= *circle.radius * *circle.radius {
+            pc.push(self.val.clone());
+        }
+
+        let (current_search, current_val) = match self.dimension {
+            0 => (circle.center.x(), self.val.x()),
+            _ => (circle.center.y(), self.val.y()),
+        };
+
+        if let Some(ref node) = self.left {
+            if current_search - *circle.radius <= current_val {
+                node.in_circle(circle, pc);
+            }
+        }
+
+        if let Some(ref node) = self.right {
+            if current_search + *circle.radius >= current_val {
+                node.in_circle(circle, pc);
+            }
+        }
+    }
+}
@@ -174,6 +174,9 @@ pub use self::ray_3d::Ray3D;
 mod plane_3d;
 pub use self::plane_3d::Plane3D;
 
+mod frustum;
+pub use self::frustum::{cull_cloud, Frustum};
+
 mod point_cloud_2d;
 pub use self::point_cloud_2d::PointCloud2D;
 
@@ -189,6 +192,9 @@ pub use self::polygon_2d::Polygon2D;
 mod polygon_3d;
 pub use self::polygon_3d::Polygon3D;
 
+mod poly_line_3d;
+pub use self::poly_line_3d::PolyLine3D;
+
 mod norm_2d;
 pub use self::norm_2d::Norm2D;
 
@@ -207,12 +213,18 @@ pub use self::matrix3::Matrix3;
 mod matrix4;
 pub use self::matrix4::Matrix4;
 
+mod quaternion;
+pub use self::quaternion::Quaternion;
+
 mod matrix3_pipe;
 pub use self::matrix3_pipe::Matrix3Pipe;
 
 mod matrix4_pipe;
 pub use self::matrix4_pipe::Matrix4Pipe;
 
+mod cloud_accumulator;
+pub use self::cloud_accumulator::{CloudAccumulator, CloudStats};
+
 mod compressed_point_3d;
 pub use self::compressed_point_3d::CompressedPoint3D;
 
@@ -222,6 +234,27 @@ pub use self::compressed_point_cloud_3d::CompressedPointCloud3D;
 mod kd_tree;
 pub use self::kd_tree::KdTree;
 
+mod kd_tree_2d;
+pub use self::kd_tree_2d::KdTree2D;
+
+mod spatial_hash_grid_3d;
+pub use self::spatial_hash_grid_3d::SpatialHashGrid3D;
+
+mod bsp_tree_3d;
+pub use self::bsp_tree_3d::BspTree3D;
+
+mod densify;
+pub use self::densify::densify_knn;
+
+mod statistical_outliers;
+pub use self::statistical_outliers::remove_statistical_outliers;
+
+mod farthest_point_sample;
+pub use self::farthest_point_sample::farthest_point_sample;
+
+mod mls_smooth;
+pub use self::mls_smooth::mls_smooth;
+
 mod mesh_3d;
 pub use self::mesh_3d::Mesh3D;
 
@@ -261,6 +294,9 @@ pub use self::cluster::*;
 mod sat_collider;
 pub use self::sat_collider::*;
 
+mod gjk;
+pub use self::gjk::{gjk_distance, gjk_intersect};
+
 mod box_unaligned_3d;
 pub use self::box_unaligned_3d::*;
 
@@ -273,17 +309,67 @@ pub use self::collider_3d::*;
 mod convex_hull_2d;
 pub use self::convex_hull_2d::convex_hull_2d;
 
+mod convex_hull_3d;
+pub use self::convex_hull_3d::convex_hull_3d;
+
 mod douglas_peucker_2d;
 pub use self::douglas_peucker_2d::douglas_peucker_2d;
 
+mod raster_2d;
+pub use self::raster_2d::{rasterize_2d, Raster2D};
+
 pub mod subdivide;
 
 mod unify_faces;
 pub use self::unify_faces::unify_faces;
 
+mod estimate_normals;
+pub use self::estimate_normals::estimate_normals;
+
+mod fit_plane;
+pub use self::fit_plane::fit_plane;
+
+mod ball_pivoting;
+pub use self::ball_pivoting::{ball_pivoting, ReconstructParams};
+
+mod mesh_face_ref;
+pub use self::mesh_face_ref::{mesh_face_tree, MeshFaceRef};
+
+mod mesh_sdf;
+pub use self::mesh_sdf::{
+    closest_point_on_mesh, closest_point_on_mesh_tree, closest_point_on_triangle,
+    mesh_contains_point, mesh_contains_point_tree, mesh_sdf, sample_sdf_grid,
+};
+
+mod transfer_colors;
+pub use self::transfer_colors::transfer_colors_from_mesh;
+
+mod polygonize_sdf_grid;
+pub use self::polygonize_sdf_grid::polygonize_sdf_grid;
+
+mod tsdf;
+pub use self::tsdf::cloud_to_mesh_sdf;
+
 mod heal_mesh;
 pub use self::heal_mesh::heal_mesh;
 
+mod alignment;
+pub use self::alignment::{kabsch, umeyama};
+
+mod extract_faces;
+pub use self::extract_faces::extract_faces;
+
+mod split_vertices_per_face;
+pub use self::split_vertices_per_face::split_vertices_per_face;
+
+mod indexed_mesh;
+pub use self::indexed_mesh::{from_indexed, to_indexed, IndexedMesh};
+
+mod mesh_quality;
+pub use self::mesh_quality::{
+    dihedral_angles, edge_length_stats, estimate_curvature, wireframe_edges, EdgeLengthStats,
+};
+
 mod cluster_vertices;
 pub use self::cluster_vertices::cluster_vertices;
 
@@ -372,6 +458,9 @@ pub use self::is_matrix4_transformable::IsMatrix4Transformable;
 mod is_sphere_searchable;
 pub use self::is_sphere_searchable::IsSphereSearchable;
 
+mod is_circle_searchable;
+pub use self::is_circle_searchable::IsCircleSearchable;
+
 mod is_box_3d_searchable;
 pub use self::is_box_3d_searchable::IsBox3DSearchable;
 
@@ -462,6 +551,9 @@ pub use self::is_sat_object::IsSATObject;
 mod has_colliders_3d;
 pub use self::has_colliders_3d::HasColliders3D;
 
+mod has_triangle_3d;
+pub use self::has_triangle_3d::HasTriangle3D;
+
 mod is_collider_container_3d;
 pub use self::is_collider_container_3d::IsColliderContainer3D;
 
@@ -522,6 +614,12 @@ pub use self::filter_sphere::FilterSphere;
 mod filter_outlier_3d;
 pub use self::filter_outlier_3d::FilterOutlier3D;
 
+mod filter_outlier_2d;
+pub use self::filter_outlier_2d::FilterOutlier2D;
+
+mod filter_corridor_3d;
+pub use self::filter_corridor_3d::FilterCorridor3D;
+
 mod is_index_container;
 pub use self::is_index_container::{IsIndexContainer, IsIndexContainerIterator};
 
@@ -40,6 +40,20 @@ impl LineSegment3D {
     pub fn new(start: Point3D, end: Point3D) -> Self {
         LineSegment3D { start, end }
     }
+    /// Returns the position on the segment closest to p
+    pub fn closest_point<P>(&self, p: &P) -> Point3D
+    where
+        P: Is3D,
+    {
+        let dir = &self.end - &self.start;
+        let sqr_len = dir.dot(&dir);
+        if sqr_len == 0.0 {
+            return self.start.clone();
+        }
+
+        let t = dir.dot(&(Point3D::new_from(p) - self.start.clone())) / sqr_len;
+        &self.start + &dir * t.clamp(0.0, 1.0)
+    }
 }
 
 impl IsMovable3D for LineSegment3D {
@@ -69,6 +69,13 @@ impl Matrix3 {
             ],
         }
     }
+    /// Returns p transformed by this matrix
+    pub fn transform_point<P>(&self, p: &P) -> P
+    where
+        P: IsBuildable2D,
+    {
+        p.multiply_m(self)
+    }
 }
 
 impl Default for Matrix3 {
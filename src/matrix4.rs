@@ -79,6 +79,10 @@ impl Matrix4 {
             ],
         }
     }
+    /// Creates a new matrix which applies scaling with a (possibly different) positive factor per axis
+    pub fn scaling_nonuniform(x: Positive, y: Positive, z: Positive) -> Matrix4 {
+        Self::scale(*x, *y, *z)
+    }
     /// Creates a new matrix which applies rotation
     pub fn rotation(x: Rad, y: Rad, z: Rad) -> Matrix4 {
         let (mut mx, mut my, mut mz) = (Matrix4::default(), Matrix4::default(), Matrix4::default());
@@ -217,6 +221,26 @@ impl Matrix4 {
         result.data[3][3] = 1.0;
         Ok(result)
     }
+    /// Returns the product self * other
+    /// Applying the result is equal to first applying other and then self
+    pub fn multiply(&self, other: &Matrix4) -> Matrix4 {
+        self * other
+    }
+    /// Applies this matrix to the homogeneous coordinates (x, y, z, 1) of p
+    /// The result is divided by the resulting w, unless w is 0
+    pub fn transform<P>(&self, p: &P) -> P
+    where
+        P: IsBuildable3D,
+    {
+        let d = &self.data;
+        let row = |i: usize| d[i][0] * p.x() + d[i][1] * p.y() + d[i][2] * p.z() + d[i][3];
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        if w == 0.0 {
+            P::new(x, y, z)
+        } else {
+            P::new(x / w, y / w, z / w)
+        }
+    }
 }
 
 //------------------------------------------------------------------------------
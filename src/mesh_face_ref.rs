@@ -0,0 +1,103 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+//! MeshFaceRef, a reference to a face of a mesh which can be stored within an AABBTree3D
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+/// MeshFaceRef, a reference to a face of a mesh which can be stored within an AABBTree3D.
+/// The bounding box is padded slightly, so faces which are flat along an axis are supported
+pub struct MeshFaceRef {
+    fid: FId,
+    bb: BoundingBox3D,
+}
+
+impl MeshFaceRef {
+    /// Creates a new reference to the face fid of the mesh
+    pub fn new<M, P>(mesh: &M, fid: FId) -> Result<Self>
+    where
+        M: IsMesh<P, Face3>,
+        P: Is3D,
+    {
+        let [a, b, c] = mesh.face_vertices(fid).ok_or(ErrorKind::IncorrectFaceID)?;
+
+        let min = Point3D::new(
+            a.x().min(b.x()).min(c.x()),
+            a.y().min(b.y()).min(c.y()),
+            a.z().min(b.z()).min(c.z()),
+        );
+        let max = Point3D::new(
+            a.x().max(b.x()).max(c.x()),
+            a.y().max(b.y()).max(c.y()),
+            a.z().max(b.z()).max(c.z()),
+        );
+
+        // pad relative to the magnitude of the coordinates and the size of the face
+        let magnitude = 1.0
+            + [min.x, min.y, min.z, max.x, max.y, max.z]
+                .iter()
+                .fold(0.0, |m: f64, x| m.max(x.abs()));
+        let pad = magnitude * 1e-9 + 1e-6 * dist_3d(&min, &max);
+        let bb = BoundingBox3D::new(
+            &Point3D::new(min.x - pad, min.y - pad, min.z - pad),
+            &Point3D::new(max.x + pad, max.y + pad, max.z + pad),
+        )?;
+
+        Ok(Self { fid, bb })
+    }
+
+    /// The id of the referenced face
+    pub fn fid(&self) -> FId {
+        self.fid
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Creates an AABBTree3D of all faces of the mesh, as used for the mesh queries
+pub fn mesh_face_tree<M, P>(mesh: &M) -> Result<AABBTree3D<MeshFaceRef>>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let faces = (0..mesh.num_faces())
+        .map(|i| MeshFaceRef::new(mesh, FId(i)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(AABBTree3D::new(faces, 16, 4))
+}
+
+//------------------------------------------------------------------------------
+
+impl HasBoundingBox3D for MeshFaceRef {
+    fn bounding_box(&self) -> BoundingBox3D {
+        self.bb.clone()
+    }
+}
+
+impl HasBoundingBox3DMaybe for MeshFaceRef {
+    fn bounding_box_maybe(&self) -> Option<BoundingBox3D> {
+        Some(self.bb.clone())
+    }
+}
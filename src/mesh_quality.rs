@@ -0,0 +1,229 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Edge based analysis of meshes, such as quality metrics to detect sliver triangles or sharp creases, curvature estimation and wireframe extraction
+
+use crate::*;
+
+use fnv::FnvHashMap;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+/// EdgeLengthStats, statistics of the edge lengths of a mesh as calculated by edge_length_stats
+pub struct EdgeLengthStats {
+    /// The number of unique edges
+    pub count: usize,
+    /// The length of the shortest edge
+    pub min: f64,
+    /// The length of the longest edge
+    pub max: f64,
+    /// The average edge length
+    pub mean: f64,
+    /// The standard deviation of the edge lengths
+    pub std_dev: f64,
+}
+
+/// Calculates statistics of the lengths of all unique edges of a mesh.
+/// Edges shared by several faces are only considered once.
+/// Fails with ErrorKind::TooFewPoints if the mesh has no faces
+pub fn edge_length_stats<M, P>(mesh: &M) -> Result<EdgeLengthStats>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let edges = edge_faces(mesh);
+    if edges.is_empty() {
+        return Err(ErrorKind::TooFewPoints);
+    }
+
+    let lengths: Vec<f64> = edges
+        .keys()
+        .map(|(a, b)| {
+            let va = mesh.vertex(VId(*a)).unwrap(); // safe
+            let vb = mesh.vertex(VId(*b)).unwrap(); // safe
+            dist_3d(&va, &vb)
+        })
+        .collect();
+
+    let n = lengths.len() as f64;
+    let mean = lengths.iter().sum::<f64>() / n;
+    let variance = lengths.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / n;
+
+    Ok(EdgeLengthStats {
+        count: lengths.len(),
+        min: lengths.iter().cloned().fold(f64::INFINITY, f64::min),
+        max: lengths.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        mean,
+        std_dev: variance.sqrt(),
+    })
+}
+
+/// Calculates the dihedral angles (in radians) of a mesh.
+/// That is the angle between the normals of the two faces sharing an edge, 0.0 for coplanar faces.
+/// Boundary edges, non-manifold edges and edges of degenerated faces don't yield an angle
+pub fn dihedral_angles<M, P>(mesh: &M) -> Vec<f64>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    let mut result = Vec::new();
+
+    for fs in edge_faces(mesh).values() {
+        if let [f1, f2] = fs[..] {
+            if let Some(angle) = dihedral_angle(mesh, f1, f2) {
+                result.push(angle);
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the selected edges of a mesh as pairs of their vertices, each undirected edge only once.
+/// The edges are sorted by their vertex ids
+pub fn wireframe_edges<M, P>(mesh: &M, selection: EdgeSelection) -> Vec<(P, P)>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    let edges = edge_faces(mesh);
+    let mut keys: Vec<_> = edges
+        .iter()
+        .filter(|(_, fs)| match selection {
+            EdgeSelection::All => true,
+            EdgeSelection::Boundary => fs.len() == 1,
+            EdgeSelection::Crease(threshold) => match fs[..] {
+                [f1, f2] => dihedral_angle(mesh, f1, f2).is_some_and(|angle| angle > threshold),
+                _ => false,
+            },
+        })
+        .map(|(key, _)| *key)
+        .collect();
+    keys.sort_unstable();
+
+    keys.into_iter()
+        .map(|(a, b)| {
+            (
+                mesh.vertex(VId(a)).unwrap(), // safe
+                mesh.vertex(VId(b)).unwrap(), // safe
+            )
+        })
+        .collect()
+}
+
+/// Estimates the mean curvature of each vertex via the cotangent weighted Laplacian of its one-ring (Meyer et al.).
+/// The area of each vertex is a third of the area of its faces (barycentric cells).
+/// The curvature is positive where the surface bends away from the face normals (e.g. everywhere on a sphere with outwards normals), negative where it bends towards them.
+/// Assumes a manifold mesh with consistently oriented faces. Vertices on the boundary, without faces or only with degenerate ones get a curvature of 0
+pub fn estimate_curvature<M, P>(mesh: &M) -> Vec<f64>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let n = mesh.num_vertices();
+    let mut laplacians = vec![Point3D::default(); n];
+    let mut areas = vec![0.0; n];
+    let mut normals = vec![Point3D::default(); n];
+
+    for i in 0..mesh.num_faces() {
+        let face = mesh.face_vertex_ids(FId(i)).unwrap(); // safe
+        let ids = [face.a.0, face.b.0, face.c.0];
+        let ps = mesh
+            .face_vertices(FId(i))
+            .unwrap() // safe
+            .map(|p| Point3D::new_from(&p));
+
+        let normal = face_winding(&ps[0], &ps[1], &ps[2]);
+        let double_area = *normal.abs();
+        if double_area == 0.0 {
+            continue;
+        }
+
+        for corner in 0..3 {
+            let (next, prev) = ((corner + 1) % 3, (corner + 2) % 3);
+            let (u, v) = (&ps[next] - &ps[corner], &ps[prev] - &ps[corner]);
+            // cotangent of the angle at corner, weighting the opposite edge
+            let weighted_edge = (&ps[prev] - &ps[next]) * (u.dot(&v) / double_area);
+            laplacians[ids[next]] = &laplacians[ids[next]] + &weighted_edge;
+            laplacians[ids[prev]] = &laplacians[ids[prev]] - &weighted_edge;
+
+            areas[ids[corner]] += double_area / 6.0;
+            normals[ids[corner]] = &normals[ids[corner]] + &normal;
+        }
+    }
+
+    for (a, b) in edge_faces(mesh)
+        .into_iter()
+        .filter(|(_, fs)| fs.len() == 1)
+        .map(|(key, _)| key)
+    {
+        areas[a] = 0.0;
+        areas[b] = 0.0;
+    }
+
+    laplacians
+        .iter()
+        .zip(areas.iter())
+        .zip(normals.iter())
+        .map(|((laplacian, area), normal)| {
+            let normal_length = *normal.abs();
+            if *area == 0.0 || normal_length == 0.0 {
+                return 0.0;
+            }
+            // the Laplacian is 2 * H * n (pointing towards the center of curvature)
+            -laplacian.dot(normal) / (normal_length * 4.0 * area)
+        })
+        .collect()
+}
+
+//------------------------------------------------------------------------------
+
+/// Maps each unique edge (smaller vertex id first) to the faces using it
+fn edge_faces<M, P>(mesh: &M) -> FnvHashMap<(usize, usize), Vec<FId>>
+where
+    M: IsMesh<P, Face3>,
+{
+    let mut result: FnvHashMap<(usize, usize), Vec<FId>> = FnvHashMap::default();
+
+    for i in 0..mesh.num_faces() {
+        let face = mesh.face_vertex_ids(FId(i)).unwrap(); // safe
+        for (a, b) in [(face.a, face.b), (face.b, face.c), (face.c, face.a)] {
+            let key = if a.0 < b.0 { (a.0, b.0) } else { (b.0, a.0) };
+            result.entry(key).or_default().push(FId(i));
+        }
+    }
+
+    result
+}
+
+fn dihedral_angle<M, P>(mesh: &M, f1: FId, f2: FId) -> Option<f64>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    let normal = |fid| {
+        let [v1, v2, v3] = mesh.face_vertices(fid)?;
+        Norm3D::new(face_winding(&v1, &v2, &v3)).ok()
+    };
+    Some(normal(f1)?.dot(&normal(f2)?).clamp(-1.0, 1.0).acos())
+}
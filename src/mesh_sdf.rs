@@ -0,0 +1,296 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Signed distance field sampling of meshes
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Calculates the point on the triangle a, b, c which is closest to p
+pub fn closest_point_on_triangle<P>(p: &P, a: &P, b: &P, c: &P) -> Point3D
+where
+    P: Is3D,
+{
+    // Ericson, Real-Time Collision Detection, 5.1.5
+    let (p, a, b, c) = (
+        Point3D::new_from(p),
+        Point3D::new_from(a),
+        Point3D::new_from(b),
+        Point3D::new_from(c),
+    );
+
+    let ab = &b - &a;
+    let ac = &c - &a;
+    let ap = &p - &a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = &p - &b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = &p - &c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let bc = &c - &b;
+        return b + bc * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Calculates the point on the surface of the mesh which is closest to p
+/// Fails if the mesh has no faces
+pub fn closest_point_on_mesh<M, P>(mesh: &M, p: &dyn Is3D) -> Result<Point3D>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let p = Point3D::new(p.x(), p.y(), p.z());
+    let mut result: Option<(f64, Point3D)> = None;
+
+    for i in 0..mesh.num_faces() {
+        let [a, b, c] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+        let candidate = closest_point_on_triangle(
+            &p,
+            &Point3D::new_from(&a),
+            &Point3D::new_from(&b),
+            &Point3D::new_from(&c),
+        );
+        let sqr_dist = sqr_dist_3d(&p, &candidate);
+        match result {
+            Some((min, _)) if min <= sqr_dist => (),
+            _ => result = Some((sqr_dist, candidate)),
+        }
+    }
+
+    result.map(|(_, x)| x).ok_or(ErrorKind::TooFewPoints)
+}
+
+/// Checks whether p is within the volume of the mesh by counting the surface crossings of a ray
+/// Only meaningful for closed meshes
+pub fn mesh_contains_point<M, P>(mesh: &M, p: &dyn Is3D) -> bool
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let ray = Ray3D::new(parity_ray(p));
+
+    let mut n_crossings = 0;
+    for i in 0..mesh.num_faces() {
+        let [a, b, c] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+        if intersection_ray_triangle(
+            &ray,
+            &Point3D::new_from(&a),
+            &Point3D::new_from(&b),
+            &Point3D::new_from(&c),
+        )
+        .is_some()
+        {
+            n_crossings += 1;
+        }
+    }
+
+    n_crossings % 2 == 1
+}
+
+/// Calculates the face of the mesh and the point on it which are closest to p, using the tree of its faces (see mesh_face_tree)
+/// Fails if the tree is empty or doesn't match the mesh
+pub fn closest_point_on_mesh_tree<M, P>(
+    mesh: &M,
+    tree: &AABBTree3D<MeshFaceRef>,
+    p: &dyn Is3D,
+) -> Result<(FId, Point3D)>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let p = Point3D::new(p.x(), p.y(), p.z());
+    let closest_on_face = |face: &MeshFaceRef| -> Result<(f64, FId, Point3D)> {
+        let [a, b, c] = mesh
+            .face_vertices(face.fid())
+            .ok_or(ErrorKind::IncorrectFaceID)?;
+        let closest = closest_point_on_triangle(
+            &p,
+            &Point3D::new_from(&a),
+            &Point3D::new_from(&b),
+            &Point3D::new_from(&c),
+        );
+        Ok((sqr_dist_3d(&p, &closest), face.fid(), closest))
+    };
+
+    // the face with the closest bounding box yields an upper bound for the distance
+    let mut best = closest_on_face(tree.nearest(&p).ok_or(ErrorKind::TooFewPoints)?)?;
+
+    // every face which might be closer collides with a box of the upper bound's size
+    let mut candidates = Vec::new();
+    tree.bb_colliding(&search_box(&p, best.0.sqrt())?, &mut candidates);
+    for face in candidates {
+        let candidate = closest_on_face(face)?;
+        if candidate.0 < best.0 {
+            best = candidate;
+        }
+    }
+
+    Ok((best.1, best.2))
+}
+
+/// Checks whether p is within the volume of the mesh by counting the surface crossings of a ray, using the tree of its faces (see mesh_face_tree)
+/// Only meaningful for closed meshes
+pub fn mesh_contains_point_tree<M, P>(
+    mesh: &M,
+    tree: &AABBTree3D<MeshFaceRef>,
+    p: &dyn Is3D,
+) -> bool
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let ray = Ray3D::new(parity_ray(p));
+
+    // faces might be stored within several nodes of the tree, each must only be counted once
+    let mut crossed = Vec::new();
+    tree.for_each_intersection_candidate(&ray.line, &mut |face| {
+        if let Some([a, b, c]) = mesh.face_vertices(face.fid()) {
+            if intersection_ray_triangle(
+                &ray,
+                &Point3D::new_from(&a),
+                &Point3D::new_from(&b),
+                &Point3D::new_from(&c),
+            )
+            .is_some()
+            {
+                crossed.push(face.fid());
+            }
+        }
+    });
+    crossed.sort();
+    crossed.dedup();
+
+    crossed.len() % 2 == 1
+}
+
+/// Calculates the signed distance of p to the surface of the mesh (negative within the mesh), using the tree of its faces (see mesh_face_tree)
+/// Only meaningful for closed meshes, fails if the mesh has no faces
+pub fn mesh_sdf<M, P>(mesh: &M, tree: &AABBTree3D<MeshFaceRef>, p: &dyn Is3D) -> Result<f64>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let (_, closest) = closest_point_on_mesh_tree(mesh, tree, p)?;
+    let dist = (&closest - &Point3D::new(p.x(), p.y(), p.z())).abs();
+
+    if mesh_contains_point_tree(mesh, tree, p) {
+        Ok(-*dist)
+    } else {
+        Ok(*dist)
+    }
+}
+
+/// Samples the signed distance field of the mesh on a regular grid within bounds
+/// resolution defines the number of samples per dimension, where the first and last sample lie on the bounds.
+/// The result is ordered with x changing fastest, then y, then z.
+/// Fails if any resolution is below 2 or the mesh has no faces
+pub fn sample_sdf_grid<M, P>(
+    mesh: &M,
+    bounds: &BoundingBox3D,
+    resolution: [usize; 3],
+) -> Result<Vec<f64>>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    if resolution.iter().any(|r| *r < 2) {
+        return Err(ErrorKind::NumberInWrongRange);
+    }
+
+    let tree = mesh_face_tree(mesh)?;
+
+    let min = bounds.min_p();
+    let [sx, sy, sz] = bounds.sizes();
+    let step = |size: Positive, res: usize| *size / (res - 1) as f64;
+    let (dx, dy, dz) = (
+        step(sx, resolution[0]),
+        step(sy, resolution[1]),
+        step(sz, resolution[2]),
+    );
+
+    let mut result = Vec::with_capacity(resolution[0] * resolution[1] * resolution[2]);
+    for iz in 0..resolution[2] {
+        for iy in 0..resolution[1] {
+            for ix in 0..resolution[0] {
+                let p = Point3D::new(
+                    min.x() + ix as f64 * dx,
+                    min.y() + iy as f64 * dy,
+                    min.z() + iz as f64 * dz,
+                );
+                result.push(mesh_sdf(mesh, &tree, &p)?);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+//------------------------------------------------------------------------------
+
+/// Ray used for the inside / outside tests, skewed to avoid hitting edges of axis aligned meshes
+fn parity_ray(p: &dyn Is3D) -> Line3D {
+    Line3D::new(
+        Point3D::new(p.x(), p.y(), p.z()),
+        Norm3D::new(Point3D::new(0.2923, 0.4178, 0.8606)).unwrap(), // safe, since non zero
+    )
+}
+
+/// Cube around p with half edge length of (slightly more than) size
+fn search_box(p: &Point3D, size: f64) -> Result<BoundingBox3D> {
+    let magnitude = 1.0 + p.x.abs().max(p.y.abs()).max(p.z.abs());
+    let size = size * (1.0 + 1e-9) + magnitude * 1e-9;
+    BoundingBox3D::new(
+        &Point3D::new(p.x - size, p.y - size, p.z - size),
+        &Point3D::new(p.x + size, p.y + size, p.z + size),
+    )
+}
@@ -0,0 +1,213 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Moving least squares smoothing of point clouds
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Smooths a point cloud by projecting each position onto a surface fitted to its k nearest neighbours (moving least squares).
+/// The local reference plane is the least squares plane of the neighbours (via PCA).
+/// polynomial_order defines the fitted surface:
+/// 0 projects onto the reference plane,
+/// 1 fits a linear height field above the reference plane,
+/// 2 (and above) fits a quadratic height field above the reference plane, preserving curvature.
+/// Neighbourhoods too small for the requested order fall back to the reference plane, those with less than 3 positions are kept unchanged
+pub fn mls_smooth<P>(pc: &PointCloud3D<P>, k: usize, polynomial_order: u8) -> PointCloud3D<P>
+where
+    P: IsBuildable3D + Clone + Default,
+{
+    if k < 3 || pc.len() < 3 {
+        return pc.clone();
+    }
+
+    let mut tree = KdTree::<P>::default();
+    tree.build(pc.clone()).unwrap(); // safe, since pc isn't empty
+
+    let mut result = PointCloud3D::with_capacity(pc.len());
+    let mut neighbours = Vec::with_capacity(k);
+
+    for p in pc.data.iter() {
+        neighbours.clear();
+        tree.knearest(p, k, &mut neighbours);
+
+        let projected = match LocalFrame::new(&neighbours) {
+            None => p.clone(),
+            Some(frame) => {
+                let [u, v, _] = frame.to_local(p);
+                let h = frame
+                    .fit_height(&neighbours, polynomial_order)
+                    .map(|coeffs| height(&coeffs, u, v))
+                    .unwrap_or(0.0);
+                let [x, y, z] = frame.to_global(u, v, h);
+                P::new(x, y, z)
+            }
+        };
+        result.push(projected);
+    }
+
+    result
+}
+
+//------------------------------------------------------------------------------
+
+/// Frame of the least squares plane of positions, with the normal as third axis
+struct LocalFrame {
+    center: [f64; 3],
+    axes: [[f64; 3]; 3],
+}
+
+impl LocalFrame {
+    fn new<P>(ps: &[P]) -> Option<Self>
+    where
+        P: Is3D,
+    {
+        if ps.len() < 3 {
+            return None;
+        }
+
+        let (center, cov) = covariance_3x3(ps);
+
+        let [(_, normal), (_, second), (largest, first)] = eigen_symmetric_3x3(&cov);
+        if largest <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            center: center.xyz(),
+            axes: [first.xyz(), second.xyz(), normal.xyz()],
+        })
+    }
+
+    fn to_local<P>(&self, p: &P) -> [f64; 3]
+    where
+        P: Is3D,
+    {
+        let d = [
+            p.x() - self.center[0],
+            p.y() - self.center[1],
+            p.z() - self.center[2],
+        ];
+        let dot = |a: &[f64; 3]| a[0] * d[0] + a[1] * d[1] + a[2] * d[2];
+        [dot(&self.axes[0]), dot(&self.axes[1]), dot(&self.axes[2])]
+    }
+
+    fn to_global(&self, u: f64, v: f64, h: f64) -> [f64; 3] {
+        let mut result = self.center;
+        for (x, i) in result.iter_mut().zip(0..3) {
+            *x += u * self.axes[0][i] + v * self.axes[1][i] + h * self.axes[2][i];
+        }
+        result
+    }
+
+    /// Fits the coefficients of height(u, v) to the positions via least squares
+    fn fit_height<P>(&self, ps: &[P], polynomial_order: u8) -> Option<[f64; N_COEFFS]>
+    where
+        P: Is3D,
+    {
+        let n_coeffs = match polynomial_order {
+            0 => return None,
+            1 => 3,
+            _ => N_COEFFS,
+        };
+        if ps.len() < n_coeffs {
+            return None;
+        }
+
+        let mut ata = [[0.0; N_COEFFS]; N_COEFFS];
+        let mut atb = [0.0; N_COEFFS];
+        for p in ps {
+            let [u, v, h] = self.to_local(p);
+            let row = monomials(u, v);
+            for i in 0..n_coeffs {
+                for j in 0..n_coeffs {
+                    ata[i][j] += row[i] * row[j];
+                }
+                atb[i] += row[i] * h;
+            }
+        }
+
+        solve(&mut ata, &mut atb, n_coeffs)
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Number of coefficients of the quadratic height field
+const N_COEFFS: usize = 6;
+
+fn monomials(u: f64, v: f64) -> [f64; N_COEFFS] {
+    [1.0, u, v, u * u, u * v, v * v]
+}
+
+fn height(coeffs: &[f64; N_COEFFS], u: f64, v: f64) -> f64 {
+    coeffs
+        .iter()
+        .zip(monomials(u, v).iter())
+        .map(|(c, m)| c * m)
+        .sum()
+}
+
+//------------------------------------------------------------------------------
+
+/// Solves the upper left n x n system of a * x = b via Gaussian elimination, coefficients beyond n are 0
+fn solve(
+    a: &mut [[f64; N_COEFFS]; N_COEFFS],
+    b: &mut [f64; N_COEFFS],
+    n: usize,
+) -> Option<[f64; N_COEFFS]> {
+    let scale = (0..n).map(|i| a[i][i].abs()).fold(0.0, f64::max);
+    if scale <= 0.0 {
+        return None;
+    }
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|x, y| {
+            a[*x][col]
+                .abs()
+                .partial_cmp(&a[*y][col].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+        if a[pivot][col].abs() <= 1e-12 * scale {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let pivot_row = a[col];
+        for row in (col + 1)..n {
+            let factor = a[row][col] / pivot_row[col];
+            for (x, p) in a[row][col..n].iter_mut().zip(pivot_row[col..n].iter()) {
+                *x -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = [0.0; N_COEFFS];
+    for row in (0..n).rev() {
+        let sum: f64 = ((row + 1)..n).map(|c| a[row][c] * x[c]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
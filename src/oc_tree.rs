@@ -28,7 +28,7 @@ use crate::*;
 
 //------------------------------------------------------------------------------
 
-#[derive(Default, Debug, PartialEq, PartialOrd, Ord, Eq, Clone, Hash)]
+#[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Clone, Hash)]
 /// OcTree https://en.wikipedia.org/wiki/Octree
 pub struct OcTree<P>
 where
@@ -36,6 +36,34 @@ where
 {
     root: Option<OcNode<P>>,
     bb: BoundingBox3D,
+    max_points_per_leaf: usize,
+    max_depth: usize,
+}
+
+impl<P> OcTree<P>
+where
+    P: Is3D,
+{
+    /// Creates a new, empty OcTree.
+    /// During build, nodes are only subdivided if they hold more than max_points_per_leaf positions and are above max_depth
+    pub fn new(max_points_per_leaf: usize, max_depth: usize) -> Self {
+        OcTree {
+            root: None,
+            bb: BoundingBox3D::default(),
+            max_points_per_leaf: max_points_per_leaf.max(1),
+            max_depth,
+        }
+    }
+}
+
+impl<P> Default for OcTree<P>
+where
+    P: Is3D,
+{
+    /// Creates a new, empty OcTree which subdivides until each leaf holds a single position
+    fn default() -> Self {
+        Self::new(1, usize::MAX)
+    }
 }
 
 impl<P> IsTree3D<P> for OcTree<P>
@@ -64,7 +92,13 @@ where
         }
 
         unique_data.extend(set.into_iter());
-        self.root = Some(OcNode::new(&self.bb, unique_data)?);
+        self.root = Some(OcNode::new(
+            &self.bb,
+            unique_data,
+            0,
+            self.max_points_per_leaf,
+            self.max_depth,
+        ));
 
         Ok(())
     }
@@ -84,13 +118,25 @@ where
     }
 }
 
+impl<P> IsBox3DSearchable<P> for OcTree<P>
+where
+    P: IsBuildable3D + Clone,
+{
+    fn in_box(&self, box_3d: &Box3D, result: &mut Vec<P>) {
+        if let Some(ref node) = self.root {
+            let bb = box_3d.bounding_box();
+            node.in_box(&self.bb, &bb.min_p(), &bb.max_p(), result);
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Clone, Hash)]
 /// OcNode, which is a single node used within OcTree
 enum OcNode<P>
 where
     P: Is3D,
 {
-    Leaf(P),
+    Leaf(Vec<P>),
     Node(Internal<P>),
 }
 
@@ -110,42 +156,6 @@ where
     nnn: Option<Box<OcNode<P>>>,
 }
 
-enum Direction {
-    PPP,
-    PPN,
-    PNP,
-    PNN,
-    NPP,
-    NPN,
-    NNP,
-    NNN,
-}
-
-/*
-/// Calculates the direction of one point to another in terms of an enum
-pub fn calc_direction<P>(reference: &Point3D, p: &Point3D) -> Direction where
-    P: Is3D {
-
-    if p.x() >= reference.x() && p.y() >= reference.y() && p.z() >= reference.z() {
-        Direction::PPP
-    } else if p.x() >= reference.x() && p.y() >= reference.y() && p.z() < reference.z() {
-        Direction::PPN
-    } else if p.x() >= reference.x() && p.y() < reference.y() && p.z() >= reference.z() {
-        Direction::PNP
-    } else if p.x() >= reference.x() && p.y() < reference.y() && p.z() < reference.z() {
-        Direction::PNN
-    } else if p.x() < reference.x() && p.y() >= reference.y() && p.z() >= reference.z() {
-        Direction::NPP
-    } else if p.x() < reference.x() && p.y() >= reference.y() && p.z() < reference.z() {
-        Direction::NPN
-    } else if p.x() >= reference.x() && p.y() < reference.y() && p.z() >= reference.z() {
-        Direction::NNP
-    } else { //if p.x() < reference.x() && p.y() < reference.y() && p.z() < reference.z() {
-        Direction::NNN
-    }
-}
-*/
-
 impl<P> OcNode<P>
 where
     P: Is3D,
@@ -153,7 +163,7 @@ where
     /// Returns the size of the oc node
     pub fn size(&self) -> usize {
         match self {
-            Self::Leaf(_) => 1,
+            Self::Leaf(ps) => ps.len(),
             Self::Node(internal) => {
                 let mut result: usize = 0;
                 if let Some(ref n) = internal.ppp {
@@ -191,10 +201,24 @@ where
     P: IsBuildable3D + Clone,
 {
     /// Creates a new OcNode from a min and max position and the data it should hold
-    pub fn new(bb: &BoundingBox3D, pc: Vec<P>) -> Result<OcNode<P>> {
-        if pc.len() == 1 {
-            return Ok(OcNode::Leaf(pc[0].clone()));
+    pub fn new(
+        bb: &BoundingBox3D,
+        pc: Vec<P>,
+        depth: usize,
+        max_points_per_leaf: usize,
+        max_depth: usize,
+    ) -> OcNode<P> {
+        if pc.len() <= max_points_per_leaf || depth >= max_depth {
+            return OcNode::Leaf(pc);
         };
+
+        // the box might be too small to be subdivided further, keep all positions in a leaf then
+        let bbs = match bb.octants() {
+            Ok(bbs) => bbs,
+            Err(_) => return OcNode::Leaf(pc),
+        };
+        let [bbppp, bbppn, bbpnp, bbpnn, bbnpp, bbnpn, bbnnp, bbnnn] = bbs;
+
         let mut pcppp = Vec::new();
         let mut pcppn = Vec::new();
         let mut pcpnp = Vec::new();
@@ -204,43 +228,33 @@ where
         let mut pcnnp = Vec::new();
         let mut pcnnn = Vec::new();
 
-        let bbppp = Self::calc_sub_min_max(Direction::PPP, bb)?;
-        let bbppn = Self::calc_sub_min_max(Direction::PPN, bb)?;
-        let bbpnp = Self::calc_sub_min_max(Direction::PNP, bb)?;
-        let bbpnn = Self::calc_sub_min_max(Direction::PNN, bb)?;
-        let bbnpp = Self::calc_sub_min_max(Direction::NPP, bb)?;
-        let bbnpn = Self::calc_sub_min_max(Direction::NPN, bb)?;
-        let bbnnp = Self::calc_sub_min_max(Direction::NNP, bb)?;
-        let bbnnn = Self::calc_sub_min_max(Direction::NNN, bb)?;
+        // assigning via the direction to the center guarantees each position ends up in exactly one child
+        let middle = bb.center_bb();
 
         for p in pc {
-            if bbppp.contains(&p) {
-                pcppp.push(p);
-            } else if bbppn.contains(&p) {
-                pcppn.push(p);
-            } else if bbpnp.contains(&p) {
-                pcpnp.push(p);
-            } else if bbpnn.contains(&p) {
-                pcpnn.push(p);
-            } else if bbnpp.contains(&p) {
-                pcnpp.push(p);
-            } else if bbnpn.contains(&p) {
-                pcnpn.push(p);
-            } else if bbnnp.contains(&p) {
-                pcnnp.push(p);
-            } else if bbnnn.contains(&p) {
-                pcnnn.push(p);
+            match calc_direction(&middle, &p) {
+                Direction::PPP => pcppp.push(p),
+                Direction::PPN => pcppn.push(p),
+                Direction::PNP => pcpnp.push(p),
+                Direction::PNN => pcpnn.push(p),
+                Direction::NPP => pcnpp.push(p),
+                Direction::NPN => pcnpn.push(p),
+                Direction::NNP => pcnnp.push(p),
+                Direction::NNN => pcnnn.push(p),
             }
         }
 
-        let ppp = Self::build_subnode(pcppp, &bbppp);
-        let ppn = Self::build_subnode(pcppn, &bbppn);
-        let pnp = Self::build_subnode(pcpnp, &bbpnp);
-        let pnn = Self::build_subnode(pcpnn, &bbpnn);
-        let npp = Self::build_subnode(pcnpp, &bbnpp);
-        let npn = Self::build_subnode(pcnpn, &bbnpn);
-        let nnp = Self::build_subnode(pcnnp, &bbnnp);
-        let nnn = Self::build_subnode(pcnnn, &bbnnn);
+        let sub = |pc: Vec<P>, bb: &BoundingBox3D| {
+            Self::build_subnode(pc, bb, depth + 1, max_points_per_leaf, max_depth)
+        };
+        let ppp = sub(pcppp, &bbppp);
+        let ppn = sub(pcppn, &bbppn);
+        let pnp = sub(pcpnp, &bbpnp);
+        let pnn = sub(pcpnn, &bbpnn);
+        let npp = sub(pcnpp, &bbnpp);
+        let npn = sub(pcnpn, &bbnpn);
+        let nnp = sub(pcnnp, &bbnnp);
+        let nnn = sub(pcnnn, &bbnnn);
 
         let result: Internal<P> = Internal {
             ppp,
@@ -253,54 +267,25 @@ where
             nnn,
         };
 
-        Ok(OcNode::Node(result))
-    }
-    /// Calculates the min and max values of sub nodes of an OcTree
-    fn calc_sub_min_max(dir: Direction, bb: &BoundingBox3D) -> Result<BoundingBox3D> {
-        let (min, max) = (bb.min_p(), bb.max_p());
-        let middle = center_3d(&min, &max);
-
-        let px = max.x();
-        let py = max.y();
-        let pz = max.z();
-        let nx = min.x();
-        let ny = min.y();
-        let nz = min.z();
-        let mx = middle.x();
-        let my = middle.y();
-        let mz = middle.z();
-
-        match dir {
-            Direction::PPP => BoundingBox3D::new(&middle, &max),
-            Direction::PPN => {
-                BoundingBox3D::new(&Point3D::new(mx, my, nz), &Point3D::new(px, py, mz))
-            }
-            Direction::PNP => {
-                BoundingBox3D::new(&Point3D::new(mx, ny, mz), &Point3D::new(px, my, pz))
-            }
-            Direction::PNN => {
-                BoundingBox3D::new(&Point3D::new(mx, ny, nz), &Point3D::new(px, my, mz))
-            }
-            Direction::NPP => {
-                BoundingBox3D::new(&Point3D::new(nx, my, mz), &Point3D::new(mx, py, pz))
-            }
-            Direction::NPN => {
-                BoundingBox3D::new(&Point3D::new(nx, my, nz), &Point3D::new(mx, py, mz))
-            }
-            Direction::NNP => {
-                BoundingBox3D::new(&Point3D::new(nx, ny, mz), &Point3D::new(mx, my, pz))
-            }
-            Direction::NNN => BoundingBox3D::new(&min.clone(), &middle),
-        }
+        OcNode::Node(result)
     }
     /// Creates a child node
-    fn build_subnode(pc: Vec<P>, bb: &BoundingBox3D) -> Option<Box<OcNode<P>>> {
+    fn build_subnode(
+        pc: Vec<P>,
+        bb: &BoundingBox3D,
+        depth: usize,
+        max_points_per_leaf: usize,
+        max_depth: usize,
+    ) -> Option<Box<OcNode<P>>> {
         match pc.len() {
             0 => None,
-            _ => match OcNode::new(bb, pc) {
-                Err(_) => None,
-                Ok(x) => Some(Box::new(x)),
-            },
+            _ => Some(Box::new(OcNode::new(
+                bb,
+                pc,
+                depth,
+                max_points_per_leaf,
+                max_depth,
+            ))),
         }
     }
 }
@@ -313,9 +298,13 @@ where
     pub fn collect(&self, depth: i8, maxdepth: i8, pc: &mut PointCloud3D<P>) {
         let only_collect_centers = maxdepth >= 0 && depth > maxdepth;
         match self {
-            &OcNode::Leaf(ref p) => pc.push(p.clone()),
+            OcNode::Leaf(ps) => {
+                for p in ps {
+                    pc.push(p.clone())
+                }
+            }
 
-            &OcNode::Node(ref internal) => {
+            OcNode::Node(internal) => {
                 if let Some(ref n) = internal.ppp {
                     Self::collect_center_or_all(n, only_collect_centers, depth, maxdepth, pc);
                 }
@@ -364,3 +353,61 @@ where
         }
     }
 }
+
+impl<P> OcNode<P>
+where
+    P: IsBuildable3D + Clone,
+{
+    /// Collects all points within [min, max], only descending into children whose bounds overlap it
+    pub fn in_box(&self, bb: &BoundingBox3D, min: &Point3D, max: &Point3D, pc: &mut Vec<P>) {
+        let (bb_min, bb_max) = (bb.min_p(), bb.max_p());
+        if bb_max.x < min.x
+            || bb_max.y < min.y
+            || bb_max.z < min.z
+            || bb_min.x > max.x
+            || bb_min.y > max.y
+            || bb_min.z > max.z
+        {
+            return;
+        }
+
+        match self {
+            Self::Leaf(ps) => {
+                for p in ps {
+                    if p.x() >= min.x
+                        && p.y() >= min.y
+                        && p.z() >= min.z
+                        && p.x() <= max.x
+                        && p.y() <= max.y
+                        && p.z() <= max.z
+                    {
+                        pc.push(p.clone())
+                    }
+                }
+            }
+
+            Self::Node(internal) => {
+                // the children were created from the octants of this box.
+                // Should it still be too small to be split, its children are searched within the whole box, which contains theirs
+                let bbs = bb
+                    .octants()
+                    .unwrap_or_else(|_| std::array::from_fn(|_| bb.clone()));
+                let children = [
+                    &internal.ppp,
+                    &internal.ppn,
+                    &internal.pnp,
+                    &internal.pnn,
+                    &internal.npp,
+                    &internal.npn,
+                    &internal.nnp,
+                    &internal.nnn,
+                ];
+                for (child, bb) in children.iter().zip(bbs.iter()) {
+                    if let Some(ref n) = child {
+                        n.in_box(bb, min, max, pc);
+                    }
+                }
+            }
+        }
+    }
+}
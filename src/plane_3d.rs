@@ -38,6 +38,28 @@ where
     pub v: N,
 }
 
+impl<P, N> Plane3D<P, N>
+where
+    P: Is3D,
+    N: IsNormalized3D,
+{
+    /// Returns the normal of the plane (u x v)
+    pub fn normal(&self) -> Point3D {
+        let normal: Point3D = cross(&self.u, &Point3D::new(self.v.x(), self.v.y(), self.v.z()));
+        normal.normalized().unwrap_or(normal)
+    }
+    /// Returns the signed distance of p to the plane, positive on the side the normal (u x v) points to
+    pub fn signed_distance<P2>(&self, p: &P2) -> f64
+    where
+        P2: Is3D,
+    {
+        let normal = self.normal();
+        normal.x() * (p.x() - self.origin.x())
+            + normal.y() * (p.y() - self.origin.y())
+            + normal.z() * (p.z() - self.origin.z())
+    }
+}
+
 impl<P, N> Default for Plane3D<P, N>
 where
     P: Is3D + Default,
@@ -73,3 +95,13 @@ where
         self.v.clone()
     }
 }
+
+impl<P, N> IsMovable3D for Plane3D<P, N>
+where
+    P: Is3D + IsMovable3D,
+    N: IsNormalized3D,
+{
+    fn move_by(&mut self, x: f64, y: f64, z: f64) {
+        self.origin.move_by(x, y, z);
+    }
+}
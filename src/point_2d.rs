@@ -332,3 +332,27 @@ impl fmt::Display for Point2D {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
+
+impl From<[f64; 2]> for Point2D {
+    fn from(x: [f64; 2]) -> Self {
+        Self::new(x[0], x[1])
+    }
+}
+
+impl From<(f64, f64)> for Point2D {
+    fn from(x: (f64, f64)) -> Self {
+        Self::new(x.0, x.1)
+    }
+}
+
+impl From<Point2D> for [f64; 2] {
+    fn from(p: Point2D) -> Self {
+        [p.x, p.y]
+    }
+}
+
+impl From<Point2D> for (f64, f64) {
+    fn from(p: Point2D) -> Self {
+        (p.x, p.y)
+    }
+}
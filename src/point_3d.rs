@@ -361,3 +361,27 @@ impl fmt::Display for Point3D {
         write!(f, "({}, {}, {})", self.x, self.y, self.z)
     }
 }
+
+impl From<[f64; 3]> for Point3D {
+    fn from(x: [f64; 3]) -> Self {
+        Self::new(x[0], x[1], x[2])
+    }
+}
+
+impl From<(f64, f64, f64)> for Point3D {
+    fn from(x: (f64, f64, f64)) -> Self {
+        Self::new(x.0, x.1, x.2)
+    }
+}
+
+impl From<Point3D> for [f64; 3] {
+    fn from(p: Point3D) -> Self {
+        [p.x, p.y, p.z]
+    }
+}
+
+impl From<Point3D> for (f64, f64, f64) {
+    fn from(p: Point3D) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
@@ -108,6 +108,23 @@ where
     }
 }
 
+impl<P> PointCloud2D<P>
+where
+    P: IsTransFormableTo3D,
+{
+    /// Lifts all positions into 3D space at the given z-coordinate
+    pub fn to_3d<P3>(&self, z: f64) -> PointCloud3D<P3>
+    where
+        P3: IsBuildable3D,
+    {
+        let mut result = PointCloud3D::with_capacity(self.data.len());
+        for p in &self.data {
+            result.push(p.transform_to_3d(z));
+        }
+        result
+    }
+}
+
 //------------------------------------------------------------------------------
 
 impl<P> IsDataContainer<P> for PointCloud2D<P>
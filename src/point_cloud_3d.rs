@@ -23,11 +23,12 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //! PointCloud3D, a collection of positions within 3D space
 
 use std::{
+    cmp::Ordering,
     fmt,
     ops::{Index, IndexMut},
 };
 
-use crate::*;
+use crate::{ball_pivoting::estimate_oriented_normals, *};
 
 //------------------------------------------------------------------------------
 
@@ -75,6 +76,39 @@ where
     pub fn reserve_vertices(&mut self, n: usize) {
         self.data.reserve(n)
     }
+    /// Moves all positions of other into this cloud
+    pub fn append(&mut self, mut other: PointCloud3D<P>) {
+        self.data.append(&mut other.data)
+    }
+    /// Returns the indices of the (up to) k positions closest to search, sorted by ascending distance.
+    /// Only the cell of search and its 26 neighbouring cells of the grid (which must have been created from this cloud) are considered.
+    /// The result is therefore approximate if true neighbours lie beyond one ring of cells
+    pub fn approximate_knn<PSearch>(
+        &self,
+        search: &PSearch,
+        k: usize,
+        grid: &SpatialHashGrid3D,
+    ) -> Vec<usize>
+    where
+        PSearch: Is3D,
+    {
+        let mut candidates: Vec<(f64, usize)> = grid
+            .indices_near(search)
+            .filter_map(|i| self.data.get(i).map(|p| (sqr_dist_3d(search, p), i)))
+            .collect();
+
+        if candidates.len() > k {
+            if k == 0 {
+                return Vec::new();
+            }
+            candidates
+                .select_nth_unstable_by(k - 1, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            candidates.truncate(k);
+        }
+        candidates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        candidates.into_iter().map(|(_, i)| i).collect()
+    }
 }
 
 impl<P> PointCloud3D<P>
@@ -106,6 +140,60 @@ where
             self.data.push(ra[i].clone());
         }
     }
+
+    /// Returns the positions which don't have any position of other within tolerance
+    pub fn subtract_within(&self, other: &PointCloud3D<P>, tolerance: Positive) -> Self
+    where
+        P: Default,
+    {
+        let mut tree = KdTree::<P>::default();
+        if tree.build(other.clone()).is_err() {
+            return self.clone();
+        }
+
+        let sqr_tolerance = *tolerance * *tolerance;
+        let data = self
+            .data
+            .iter()
+            .filter(|p| match tree.nearest(*p) {
+                Some(q) => sqr_dist_3d(*p, &q) > sqr_tolerance,
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        Self { data }
+    }
+
+    /// Splits the cloud into the positions on the positive and negative side of the plane (in this order).
+    /// Positions exactly on the plane are added to the positive side
+    pub fn split_by_plane<PP, N>(&self, plane: &Plane3D<PP, N>) -> (Self, Self)
+    where
+        PP: Is3D,
+        N: IsNormalized3D,
+    {
+        let mut positive = Self::new();
+        let mut negative = Self::new();
+
+        for p in &self.data {
+            if plane.signed_distance(p) >= 0.0 {
+                positive.push(p.clone());
+            } else {
+                negative.push(p.clone());
+            }
+        }
+
+        (positive, negative)
+    }
+
+    /// Reconstructs a mesh from the positions by estimating and orienting their normals and applying ball pivoting
+    pub fn reconstruct<M>(&self, params: ReconstructParams) -> Result<M>
+    where
+        M: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3> + Default,
+    {
+        let normals = estimate_oriented_normals(self, params.k_normals)?;
+        ball_pivoting(self, &normals, &params.radii)
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -334,6 +422,38 @@ where
     }
 }
 
+impl<P> PointCloud3D<P>
+where
+    P: IsEditable3D,
+{
+    /// Scales the cloud with a separate factor per axis, relative to the center of its bounding box
+    pub fn scale_nonuniform(&mut self, fx: Positive, fy: Positive, fz: Positive) {
+        if let Some(bb) = self.bounding_box_maybe() {
+            let c = bb.center_bb();
+            for p in &mut self.data {
+                let x = c.x + *fx * (p.x() - c.x);
+                let y = c.y + *fy * (p.y() - c.y);
+                let z = c.z + *fz * (p.z() - c.z);
+                p.set_xyz(x, y, z);
+            }
+        }
+    }
+    /// Moves the cloud so its center of gravity is at the origin.
+    /// Returns the applied offset, moving by its negation restores the original positions
+    pub fn center_to_origin(&mut self) -> Point3D {
+        let offset = match self.center_of_gravity() {
+            Some(c) => Point3D::new(-c.x, -c.y, -c.z),
+            None => return Point3D::default(),
+        };
+
+        for p in &mut self.data {
+            p.add(&offset);
+        }
+
+        offset
+    }
+}
+
 impl<P> IsScalable for PointCloud3D<P>
 where
     P: IsEditable3D,
@@ -0,0 +1,153 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! PolyLine3D, an open polyline within 3D space
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// PolyLine3D, an open polyline within 3D space
+pub struct PolyLine3D<P>
+where
+    P: Is3D,
+{
+    pc: PointCloud3D<P>,
+    arclengths: Vec<f64>,
+}
+
+impl<P> PolyLine3D<P>
+where
+    P: Is3D,
+{
+    /// Creates a new PolyLine3D connecting the positions of the point cloud in order
+    pub fn new(pc: PointCloud3D<P>) -> Self {
+        let mut arclengths = Vec::with_capacity(pc.len());
+        let mut sum = 0.0;
+        for i in 0..pc.len() {
+            if i > 0 {
+                sum += dist_3d(&pc[i - 1], &pc[i]);
+            }
+            arclengths.push(sum);
+        }
+
+        PolyLine3D { pc, arclengths }
+    }
+    /// Returns the positions of the polyline
+    pub fn points(&self) -> &PointCloud3D<P> {
+        &self.pc
+    }
+    /// Returns the length of the whole polyline
+    pub fn total_length(&self) -> f64 {
+        self.arclengths.last().cloned().unwrap_or(0.0)
+    }
+    /// Returns the position at the given arc length, measured from the first position.
+    /// Arc lengths outside of [0, total_length] are clamped. Returns None for an empty polyline
+    pub fn point_at_arclength(&self, s: f64) -> Option<Point3D> {
+        let n = self.pc.len();
+        if n == 0 {
+            return None;
+        }
+        if n == 1 || s <= 0.0 {
+            return Some(Point3D::new_from(&self.pc[0]));
+        }
+        if s >= self.total_length() {
+            return Some(Point3D::new_from(&self.pc[n - 1]));
+        }
+
+        // index of the first position further along than s, at least 1 due to the checks above
+        let i = self.arclengths.partition_point(|x| *x <= s);
+        let start = Point3D::new_from(&self.pc[i - 1]);
+        let end = Point3D::new_from(&self.pc[i]);
+        let length = self.arclengths[i] - self.arclengths[i - 1];
+        let t = (s - self.arclengths[i - 1]) / length;
+
+        Some(&start + &(end - start.clone()) * t)
+    }
+    /// Returns the arc length and position of the point on the polyline closest to p.
+    /// Returns None for an empty polyline
+    pub fn project(&self, p: &dyn Is3D) -> Option<(f64, Point3D)> {
+        let n = self.pc.len();
+        if n == 0 {
+            return None;
+        }
+
+        let search = Point3D::new(p.x(), p.y(), p.z());
+        let mut best = (
+            0.0,
+            Point3D::new_from(&self.pc[0]),
+            sqr_dist_3d(&search, &self.pc[0]),
+        );
+
+        for i in 1..n {
+            let segment = LineSegment3D::new(
+                Point3D::new_from(&self.pc[i - 1]),
+                Point3D::new_from(&self.pc[i]),
+            );
+            let closest = segment.closest_point(&search);
+            let sqr_dist = sqr_dist_3d(&search, &closest);
+            if sqr_dist < best.2 {
+                let s = self.arclengths[i - 1] + dist_3d(&segment.start, &closest);
+                best = (s, closest, sqr_dist);
+            }
+        }
+
+        Some((best.0, best.1))
+    }
+}
+
+impl<P> Default for PolyLine3D<P>
+where
+    P: Is3D,
+{
+    fn default() -> Self {
+        Self::new(PointCloud3D::new())
+    }
+}
+
+impl<P> From<PointCloud3D<P>> for PolyLine3D<P>
+where
+    P: Is3D,
+{
+    fn from(pc: PointCloud3D<P>) -> Self {
+        Self::new(pc)
+    }
+}
+
+impl<P> HasLength for PolyLine3D<P>
+where
+    P: Is3D,
+{
+    fn length(&self) -> f64 {
+        self.total_length()
+    }
+}
+
+impl<P> HasBoundingBox3DMaybe for PolyLine3D<P>
+where
+    P: Is3D,
+{
+    fn bounding_box_maybe(&self) -> Option<BoundingBox3D> {
+        self.pc.bounding_box_maybe()
+    }
+}
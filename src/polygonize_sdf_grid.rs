@@ -0,0 +1,159 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Extraction of the zero level surface of a signed distance field sampled on a regular grid (marching tetrahedra)
+
+use crate::*;
+
+use fnv::FnvHashMap;
+
+//------------------------------------------------------------------------------
+
+/// Extracts the surface where the signed distance field sampled on a regular grid within bounds is zero.
+/// The layout of values matches sample_sdf_grid: resolution samples per dimension, the first and last lying on the bounds, x changing fastest, then y, then z.
+/// Each grid cell is split into 6 tetrahedra (marching tetrahedra), resulting in a mesh without cracks.
+/// Faces are oriented towards positive values (outside).
+/// Cells with non-finite values are treated as unknown and skipped.
+/// Fails if any resolution is below 2 or the number of values doesn't match the resolution
+pub fn polygonize_sdf_grid<EM, P>(
+    values: &[f64],
+    bounds: &BoundingBox3D,
+    resolution: [usize; 3],
+) -> Result<EM>
+where
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3> + Default,
+    P: IsBuildable3D,
+{
+    if resolution.iter().any(|r| *r < 2) {
+        return Err(ErrorKind::NumberInWrongRange);
+    }
+    let [nx, ny, nz] = resolution;
+    if values.len() != nx * ny * nz {
+        return Err(ErrorKind::DimensionsDontMatch);
+    }
+
+    let min = bounds.min_p();
+    let [sx, sy, sz] = bounds.sizes();
+    let step = [
+        *sx / (nx - 1) as f64,
+        *sy / (ny - 1) as f64,
+        *sz / (nz - 1) as f64,
+    ];
+    let index = |x: usize, y: usize, z: usize| x + nx * (y + ny * z);
+    let position = |i: usize| {
+        let (x, y, z) = (i % nx, (i / nx) % ny, i / (nx * ny));
+        Point3D::new(
+            min.x + x as f64 * step[0],
+            min.y + y as f64 * step[1],
+            min.z + z as f64 * step[2],
+        )
+    };
+
+    let mut mesh = EM::default();
+    // vertex of the surface on the grid edge between two samples
+    let mut edge_vertices: FnvHashMap<(usize, usize), VId> = FnvHashMap::default();
+
+    for z in 0..nz - 1 {
+        for y in 0..ny - 1 {
+            for x in 0..nx - 1 {
+                let corners = CUBE_CORNERS.map(|[dx, dy, dz]| index(x + dx, y + dy, z + dz));
+                for tet in CUBE_TETRAHEDRA.iter() {
+                    let ids = tet.map(|i| corners[i]);
+                    if ids.iter().any(|i| !values[*i].is_finite()) {
+                        continue;
+                    }
+
+                    let (inside, outside): (Vec<usize>, Vec<usize>) =
+                        ids.iter().partition(|i| values[**i] < 0.0);
+
+                    let triangles: Vec<[(usize, usize); 3]> = match (inside.len(), outside.len()) {
+                        (1, 3) => vec![[
+                            (inside[0], outside[0]),
+                            (inside[0], outside[1]),
+                            (inside[0], outside[2]),
+                        ]],
+                        (3, 1) => vec![[
+                            (inside[0], outside[0]),
+                            (inside[1], outside[0]),
+                            (inside[2], outside[0]),
+                        ]],
+                        (2, 2) => {
+                            let (a, b, c, d) = (inside[0], inside[1], outside[0], outside[1]);
+                            vec![[(a, c), (a, d), (b, d)], [(a, c), (b, d), (b, c)]]
+                        }
+                        _ => continue,
+                    };
+
+                    // towards positive values
+                    let outwards = position(outside[0]) - position(inside[0]);
+
+                    for edges in triangles {
+                        let mut vids = [VId(0); 3];
+                        let mut ps = Vec::with_capacity(3);
+                        for (vid, (i, o)) in vids.iter_mut().zip(edges.iter()) {
+                            let (pi, po) = (position(*i), position(*o));
+                            let t = values[*i] / (values[*i] - values[*o]);
+                            let p = &pi + &((&po - &pi) * t);
+                            *vid = *edge_vertices
+                                .entry((*i, *o))
+                                .or_insert_with(|| mesh.add_vertex(P::new(p.x, p.y, p.z)));
+                            ps.push(p);
+                        }
+
+                        let normal = cross(&(&ps[1] - &ps[0]), &(&ps[2] - &ps[0]));
+                        if normal.dot(&outwards) < 0.0 {
+                            vids.swap(1, 2);
+                        }
+                        mesh.try_add_connection(vids[0], vids[1], vids[2])?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(mesh)
+}
+
+//------------------------------------------------------------------------------
+
+/// Offsets of the corners of a grid cell
+const CUBE_CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [1, 1, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [1, 1, 1],
+    [0, 1, 1],
+];
+
+/// Split of a grid cell into tetrahedra along its diagonal 0 -> 6.
+/// Neighbouring cells split their shared faces along the same diagonal
+const CUBE_TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 5, 1, 6],
+    [0, 1, 2, 6],
+    [0, 2, 3, 6],
+    [0, 3, 7, 6],
+    [0, 7, 4, 6],
+    [0, 4, 5, 6],
+];
@@ -0,0 +1,173 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Quaternion, a quaternion used to describe rotations within 3D space
+
+use std::ops::Mul;
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+/// Quaternion, a quaternion used to describe rotations within 3D space
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    /// Creates a new quaternion which doesn't rotate
+    pub fn identity() -> Self {
+        Quaternion {
+            w: 1.0,
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        }
+    }
+    /// Creates a new quaternion which rotates around the axis by the angle
+    /// Returns ErrorKind::NormalizeVecWithoutLength if the axis has no length
+    pub fn from_axis_angle<P>(axis: &P, angle: Rad) -> Result<Self>
+    where
+        P: Is3D,
+    {
+        let l = (axis.x() * axis.x() + axis.y() * axis.y() + axis.z() * axis.z()).sqrt();
+        if l == 0.0 {
+            return Err(ErrorKind::NormalizeVecWithoutLength);
+        }
+        let half = 0.5 * angle.0;
+        let s = half.sin() / l;
+        Ok(Quaternion {
+            w: half.cos(),
+            x: s * axis.x(),
+            y: s * axis.y(),
+            z: s * axis.z(),
+        })
+    }
+    /// Returns the length of this quaternion
+    pub fn abs(&self) -> f64 {
+        (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+    /// Returns this quaternion scaled to a length of 1
+    /// Returns ErrorKind::NormalizeVecWithoutLength for a zero quaternion
+    pub fn normalize(&self) -> Result<Self> {
+        let l = self.abs();
+        if l == 0.0 {
+            return Err(ErrorKind::NormalizeVecWithoutLength);
+        }
+        Ok(Quaternion {
+            w: self.w / l,
+            x: self.x / l,
+            y: self.y / l,
+            z: self.z / l,
+        })
+    }
+    /// Returns the (Hamilton) product self * other
+    /// Rotating by the result is equal to first rotating by other and then by self
+    pub fn multiply(&self, other: &Quaternion) -> Self {
+        Quaternion {
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+        }
+    }
+    /// Returns the conjugate of this quaternion, which is the inverse rotation for unit quaternions
+    pub fn conjugate(&self) -> Self {
+        Quaternion {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+    /// Rotates the point via the sandwich product q * p * q'
+    /// This quaternion should be normalized
+    pub fn rotate_point<P>(&self, p: &P) -> P
+    where
+        P: IsBuildable3D,
+    {
+        let pure = Quaternion {
+            w: 0.0,
+            x: p.x(),
+            y: p.y(),
+            z: p.z(),
+        };
+        let rotated = self.multiply(&pure).multiply(&self.conjugate());
+        P::new(rotated.x, rotated.y, rotated.z)
+    }
+    /// Returns the rotation matrix of this quaternion
+    /// This quaternion should be normalized
+    pub fn to_matrix4(&self) -> Matrix4 {
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+        Matrix4 {
+            data: [
+                [
+                    1.0 - 2.0 * (y * y + z * z),
+                    2.0 * (x * y - w * z),
+                    2.0 * (x * z + w * y),
+                    0.0,
+                ],
+                [
+                    2.0 * (x * y + w * z),
+                    1.0 - 2.0 * (x * x + z * z),
+                    2.0 * (y * z - w * x),
+                    0.0,
+                ],
+                [
+                    2.0 * (x * z - w * y),
+                    2.0 * (y * z + w * x),
+                    1.0 - 2.0 * (x * x + y * y),
+                    0.0,
+                ],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.multiply(&other)
+    }
+}
+
+impl Mul for &Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, other: Self) -> Quaternion {
+        self.multiply(other)
+    }
+}
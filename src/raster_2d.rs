@@ -0,0 +1,117 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Raster2D, an occupancy raster counting the positions of a 2D point cloud per cell
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+/// Raster2D, an occupancy raster counting the positions of a 2D point cloud per cell.
+/// Cells are stored row by row, starting at the minimum of the cloud's bounding box
+pub struct Raster2D {
+    origin: Point2D,
+    cell_size: Positive,
+    nx: usize,
+    ny: usize,
+    counts: Vec<usize>,
+}
+
+impl Raster2D {
+    /// The minimum position of the raster, which is the corner of the cell (0, 0)
+    pub fn origin(&self) -> &Point2D {
+        &self.origin
+    }
+    /// The edge length of a single cell
+    pub fn cell_size(&self) -> Positive {
+        self.cell_size
+    }
+    /// The number of cells in x direction
+    pub fn nx(&self) -> usize {
+        self.nx
+    }
+    /// The number of cells in y direction
+    pub fn ny(&self) -> usize {
+        self.ny
+    }
+    /// The number of positions within the cell, None if the cell is out of bounds
+    pub fn count(&self, ix: usize, iy: usize) -> Option<usize> {
+        if ix >= self.nx || iy >= self.ny {
+            return None;
+        }
+        Some(self.counts[iy * self.nx + ix])
+    }
+    /// The largest count of any cell
+    pub fn max_count(&self) -> usize {
+        self.counts[..].iter().cloned().max().unwrap_or(0)
+    }
+    /// Converts the counts to gray values, row by row. Counts of max or more are mapped to 255
+    pub fn to_gray_bytes(&self, max: usize) -> Vec<u8> {
+        let max = max.max(1) as f64;
+        self.counts[..]
+            .iter()
+            .map(|count| ((*count as f64 / max).min(1.0) * 255.0).round() as u8)
+            .collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Rasterizes a 2D point cloud, counting the positions within each cell of size cell_size.
+/// The raster covers the bounding box of the cloud, fails with ErrorKind::TooFewPoints for empty clouds
+pub fn rasterize_2d<P>(pc: &PointCloud2D<P>, cell_size: Positive) -> Result<Raster2D>
+where
+    P: Is2D,
+{
+    let first = pc.data.first().ok_or(ErrorKind::TooFewPoints)?;
+    let (mut min, mut max) = (
+        Point2D::new(first.x(), first.y()),
+        Point2D::new(first.x(), first.y()),
+    );
+    for p in pc.data.iter() {
+        min.x = min.x.min(p.x());
+        min.y = min.y.min(p.y());
+        max.x = max.x.max(p.x());
+        max.y = max.y.max(p.y());
+    }
+
+    let index = |value: f64, min: f64| ((value - min) / *cell_size).floor() as usize;
+    let nx = index(max.x, min.x) + 1;
+    let ny = index(max.y, min.y) + 1;
+    let mut counts = vec![0; nx * ny];
+
+    for p in pc.data.iter() {
+        // min() guards against rounding issues
+        let ix = index(p.x(), min.x).min(nx - 1);
+        let iy = index(p.y(), min.y).min(ny - 1);
+        counts[iy * nx + ix] += 1;
+    }
+
+    Ok(Raster2D {
+        origin: min,
+        cell_size,
+        nx,
+        ny,
+        counts,
+    })
+}
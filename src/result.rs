@@ -23,7 +23,7 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //! Result, the result type used within rust-3d. Also defining the error enum and several transformation methods between error types.
 
 use std::{
-    fmt,
+    error, fmt,
     io::Error as ioError,
     num::{ParseFloatError, ParseIntError},
     result,
@@ -58,6 +58,10 @@ pub enum ErrorKind {
     ClusterTooBig,
     CantCalculateAngleIfZeroLength,
     TriFace3DNotSpanningVolume,
+    NormalArrayIncorrectLength,
+    CorrespondenceCountMismatch,
+    PointsCollinear,
+    ColorArrayIncorrectLength,
     PlyError(PlyError),
     StlError(StlError),
     PtxError(PtxError),
@@ -67,6 +71,7 @@ pub enum ErrorKind {
     OffError(OffError),
     PslError(PslError),
     GcodeError(GcodeError),
+    PcdError(PcdError),
 }
 
 //------------------------------------------------------------------------------
@@ -104,6 +109,16 @@ impl fmt::Debug for ErrorKind {
                 f,
                 "TriFace3D must be constructed from points spanning a volume"
             ),
+            Self::NormalArrayIncorrectLength => {
+                write!(f, "The provided normal array has an incorrect length")
+            }
+            Self::CorrespondenceCountMismatch => {
+                write!(f, "The number of source and target positions doesn't match")
+            }
+            Self::PointsCollinear => write!(f, "Positions are collinear, but must span a plane"),
+            Self::ColorArrayIncorrectLength => {
+                write!(f, "The provided color array has an incorrect length")
+            }
             Self::PlyError(x) => x.fmt(f),
             Self::StlError(x) => x.fmt(f),
             Self::PtxError(x) => x.fmt(f),
@@ -113,6 +128,31 @@ impl fmt::Debug for ErrorKind {
             Self::OffError(x) => x.fmt(f),
             Self::PslError(x) => x.fmt(f),
             Self::GcodeError(x) => x.fmt(f),
+            Self::PcdError(x) => x.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl error::Error for ErrorKind {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::PlyError(x) => Some(x),
+            Self::StlError(x) => Some(x),
+            Self::PtxError(x) => Some(x),
+            Self::XyError(x) => Some(x),
+            Self::XyzError(x) => Some(x),
+            Self::ObjError(x) => Some(x),
+            Self::OffError(x) => Some(x),
+            Self::PslError(x) => Some(x),
+            Self::GcodeError(x) => Some(x),
+            Self::PcdError(x) => Some(x),
+            _ => None,
         }
     }
 }
@@ -140,6 +180,16 @@ impl From<ioError> for ErrorKind {
     }
 }
 
+impl<E> From<WithLineInfo<E>> for ErrorKind
+where
+    ErrorKind: From<E>,
+{
+    /// Drops the line information, use Display of WithLineInfo to keep it
+    fn from(error: WithLineInfo<E>) -> Self {
+        error.into_inner().into()
+    }
+}
+
 impl From<PlyError> for ErrorKind {
     fn from(error: PlyError) -> Self {
         Self::PlyError(error)
@@ -176,8 +226,26 @@ impl From<OffError> for ErrorKind {
     }
 }
 
+impl From<PtxError> for ErrorKind {
+    fn from(error: PtxError) -> Self {
+        Self::PtxError(error)
+    }
+}
+
+impl From<PslError> for ErrorKind {
+    fn from(error: PslError) -> Self {
+        Self::PslError(error)
+    }
+}
+
 impl From<GcodeError> for ErrorKind {
     fn from(error: GcodeError) -> Self {
         Self::GcodeError(error)
     }
 }
+
+impl From<PcdError> for ErrorKind {
+    fn from(error: PcdError) -> Self {
+        Self::PcdError(error)
+    }
+}
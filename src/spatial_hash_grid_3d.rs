@@ -0,0 +1,85 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! SpatialHashGrid3D, a hash grid of the indices of positions for fast, approximate neighbour queries
+
+use crate::*;
+
+use fnv::FnvHashMap;
+
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+/// SpatialHashGrid3D, a hash grid of the indices of positions for fast, approximate neighbour queries.
+/// Only non-empty cells are stored
+pub struct SpatialHashGrid3D {
+    cell_size: Positive,
+    cells: FnvHashMap<[i64; 3], Vec<usize>>,
+}
+
+impl SpatialHashGrid3D {
+    /// Creates a new grid of the indices of the positions within pc
+    pub fn new<P>(pc: &PointCloud3D<P>, cell_size: Positive) -> Self
+    where
+        P: Is3D,
+    {
+        let mut result = Self {
+            cell_size,
+            cells: FnvHashMap::default(),
+        };
+        for (i, p) in pc.data.iter().enumerate() {
+            let cell = result.cell_of(p);
+            result.cells.entry(cell).or_default().push(i);
+        }
+        result
+    }
+    /// Returns the edge length of the cells
+    pub fn cell_size(&self) -> Positive {
+        self.cell_size
+    }
+    /// Returns the cell containing the position
+    pub fn cell_of<P>(&self, p: &P) -> [i64; 3]
+    where
+        P: Is3D,
+    {
+        let index = |x: f64| (x / *self.cell_size).floor() as i64;
+        [index(p.x()), index(p.y()), index(p.z())]
+    }
+    /// Returns the indices within the cell
+    pub fn indices_in_cell(&self, cell: [i64; 3]) -> &[usize] {
+        self.cells.get(&cell).map_or(&[], |x| &x[..])
+    }
+    /// Iterates the indices within the cell containing the position and its 26 neighbouring cells
+    pub fn indices_near<P>(&self, p: &P) -> impl Iterator<Item = usize> + '_
+    where
+        P: Is3D,
+    {
+        let [cx, cy, cz] = self.cell_of(p);
+        (-1..=1)
+            .flat_map(move |x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| [x, y, z])))
+            .flat_map(move |[x, y, z]| {
+                self.indices_in_cell([cx + x, cy + y, cz + z])
+                    .iter()
+                    .cloned()
+            })
+    }
+}
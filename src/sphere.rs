@@ -73,7 +73,7 @@ impl Is3D for Sphere {
 
     #[inline(always)]
     fn z(&self) -> f64 {
-        self.center.y()
+        self.center.z()
     }
 }
 
@@ -163,3 +163,9 @@ impl IsScalable for Sphere {
         self.radius *= factor;
     }
 }
+
+impl IsMovable3D for Sphere {
+    fn move_by(&mut self, x: f64, y: f64, z: f64) {
+        self.center.move_by(x, y, z);
+    }
+}
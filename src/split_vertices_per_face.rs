@@ -0,0 +1,52 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Algorithm to duplicate shared vertices, so every face of a mesh owns its vertices
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Algorithm to duplicate shared vertices, so every face of a mesh owns its vertices.
+/// Face i of the result uses the vertices 3 * i, 3 * i + 1 and 3 * i + 2, as needed for flat shading or non-indexed formats.
+/// This is the inverse of welding vertices
+pub fn split_vertices_per_face<M, P, EM>(mesh: &M) -> EM
+where
+    M: IsMesh<P, Face3>,
+    EM: IsFaceEditableMesh<P, Face3> + IsVertexEditableMesh<P, Face3> + Default,
+{
+    let nf = mesh.num_faces();
+
+    let mut result = EM::default();
+    result.reserve_vertices(3 * nf);
+    result.reserve_faces(nf);
+
+    for i in 0..nf {
+        let [a, b, c] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+        let a = result.add_vertex(a);
+        let b = result.add_vertex(b);
+        let c = result.add_vertex(c);
+        result.try_add_connection(a, b, c).unwrap(); // safe since all ids are new and unique
+    }
+
+    result
+}
@@ -0,0 +1,82 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Statistical outlier removal of point clouds
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Removes statistical outliers of a point cloud.
+/// For each position the mean distance to its k nearest neighbours is calculated.
+/// Positions whose mean distance is more than std_ratio standard deviations above the mean of all mean distances are removed
+pub fn remove_statistical_outliers<P>(
+    pc: &PointCloud3D<P>,
+    k: usize,
+    std_ratio: f64,
+) -> PointCloud3D<P>
+where
+    P: Is3D + Clone + Default,
+{
+    if k == 0 || pc.len() < 2 {
+        return pc.clone();
+    }
+
+    let mut tree = KdTree::<P>::default();
+    tree.build(pc.clone()).unwrap(); // safe, since pc isn't empty
+
+    let mut neighbours = Vec::with_capacity(k + 1);
+    let mean_distances: Vec<f64> = pc
+        .data
+        .iter()
+        .map(|p| {
+            neighbours.clear();
+            // +1 since p itself is part of the tree and its closest neighbour
+            tree.knearest(p, k + 1, &mut neighbours);
+            let n = neighbours.len() - 1;
+            neighbours
+                .iter()
+                .skip(1)
+                .map(|q| dist_3d(p, q))
+                .sum::<f64>()
+                / n as f64
+        })
+        .collect();
+
+    let n = mean_distances.len() as f64;
+    let mean = mean_distances.iter().sum::<f64>() / n;
+    let variance = mean_distances
+        .iter()
+        .map(|d| (d - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    let max_distance = mean + std_ratio * variance.sqrt();
+
+    let mut result = PointCloud3D::new();
+    for (p, d) in pc.data.iter().zip(mean_distances.iter()) {
+        if *d <= max_distance {
+            result.push(p.clone());
+        }
+    }
+
+    result
+}
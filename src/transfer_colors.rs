@@ -0,0 +1,128 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Transfer of vertex colors from a mesh onto a point cloud
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Transfers the vertex colors of a mesh onto a point cloud.
+/// For each position the closest point on the mesh's surface is searched and the colors
+/// of the corresponding face's vertices are interpolated via its barycentric coordinates.
+/// Fails if the mesh has no faces or if mesh_colors doesn't match the mesh's vertex count
+pub fn transfer_colors_from_mesh<M, P>(
+    pc: &PointCloud3D<P>,
+    mesh: &M,
+    mesh_colors: &[Rgb],
+) -> Result<Vec<Rgb>>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    if mesh_colors.len() != mesh.num_vertices() {
+        return Err(ErrorKind::ColorArrayIncorrectLength);
+    }
+
+    if mesh.num_faces() == 0 {
+        return Err(ErrorKind::TooFewPoints);
+    }
+
+    let tree = mesh_face_tree(mesh)?;
+
+    pc.data
+        .iter()
+        .map(|p| {
+            let (fid, closest) = closest_point_on_mesh_tree(mesh, &tree, p)?;
+            let vids = mesh
+                .face_vertex_ids(fid)
+                .ok_or(ErrorKind::IncorrectFaceID)?;
+            let [a, b, c] = mesh.face_vertices(fid).ok_or(ErrorKind::IncorrectFaceID)?;
+            let weights = barycentric(
+                &closest,
+                &Point3D::new(a.x(), a.y(), a.z()),
+                &Point3D::new(b.x(), b.y(), b.z()),
+                &Point3D::new(c.x(), c.y(), c.z()),
+            );
+
+            Ok(interpolate(
+                [
+                    &mesh_colors[vids.a.0],
+                    &mesh_colors[vids.b.0],
+                    &mesh_colors[vids.c.0],
+                ],
+                weights,
+            ))
+        })
+        .collect()
+}
+
+//------------------------------------------------------------------------------
+
+/// Barycentric coordinates of p (which must be within the plane of a, b, c).
+/// Degenerate triangles fall back to the corner closest to p
+fn barycentric(p: &Point3D, a: &Point3D, b: &Point3D, c: &Point3D) -> [f64; 3] {
+    let v0 = b - a;
+    let v1 = c - a;
+    let v2 = p - a;
+    let d00 = v0.dot(&v0);
+    let d01 = v0.dot(&v1);
+    let d11 = v1.dot(&v1);
+    let d20 = v2.dot(&v0);
+    let d21 = v2.dot(&v1);
+    let denom = d00 * d11 - d01 * d01;
+
+    if denom.abs() <= f64::EPSILON * d00 * d11 {
+        let dists = [sqr_dist_3d(p, a), sqr_dist_3d(p, b), sqr_dist_3d(p, c)];
+        return if dists[0] <= dists[1] && dists[0] <= dists[2] {
+            [1.0, 0.0, 0.0]
+        } else if dists[1] <= dists[2] {
+            [0.0, 1.0, 0.0]
+        } else {
+            [0.0, 0.0, 1.0]
+        };
+    }
+
+    let v = (d11 * d20 - d01 * d21) / denom;
+    let w = (d00 * d21 - d01 * d20) / denom;
+    let u = 1.0 - v - w;
+
+    // p is on the triangle, only rounding errors might cause slightly negative weights
+    let weights = [u.max(0.0), v.max(0.0), w.max(0.0)];
+    let sum: f64 = weights.iter().sum();
+    [weights[0] / sum, weights[1] / sum, weights[2] / sum]
+}
+
+//------------------------------------------------------------------------------
+
+fn interpolate(colors: [&Rgb; 3], weights: [f64; 3]) -> Rgb {
+    let channel = |f: &dyn Fn(&Rgb) -> u8| {
+        let value: f64 = colors
+            .iter()
+            .zip(weights.iter())
+            .map(|(c, w)| f(c) as f64 * w)
+            .sum();
+        value.round().clamp(0.0, 255.0) as u8
+    };
+
+    Rgb::new(channel(&|c| c.r), channel(&|c| c.g), channel(&|c| c.b))
+}
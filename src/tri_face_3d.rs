@@ -26,6 +26,7 @@ use crate::*;
 
 //------------------------------------------------------------------------------
 
+#[derive(Debug, PartialEq, Clone)]
 /// Face with 3 corners in 3D space
 pub struct TriFace3D {
     a: Point3D,
@@ -99,6 +100,14 @@ impl HasBoundingBox3D for TriFace3D {
 
 //------------------------------------------------------------------------------
 
+impl HasTriangle3D for TriFace3D {
+    fn triangle(&self) -> [Point3D; 3] {
+        [self.a.clone(), self.b.clone(), self.c.clone()]
+    }
+}
+
+//------------------------------------------------------------------------------
+
 impl HasBoundingBox3DMaybe for TriFace3D {
     fn bounding_box_maybe(&self) -> Option<BoundingBox3D> {
         Some(self.bounding_box())
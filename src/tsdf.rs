@@ -0,0 +1,118 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+//! Surface reconstruction of oriented point clouds via a truncated signed distance field (TSDF)
+
+use crate::*;
+
+//------------------------------------------------------------------------------
+
+/// Reconstructs a mesh from a point cloud with normals pointing outwards.
+/// Each position writes its signed distance along its normal to all grid samples within truncation (TSDF fusion).
+/// Samples receiving multiple distances use their mean, samples out of reach of all positions stay unknown.
+/// The zero level surface of the resulting field is extracted via polygonize_sdf_grid.
+/// voxel_size defines the spacing of the grid, truncation should span a few voxels.
+/// Fails if the number of normals doesn't match the number of positions or pc is empty
+pub fn cloud_to_mesh_sdf<P, N, EM, PM>(
+    pc: &PointCloud3D<P>,
+    normals: &[N],
+    voxel_size: Positive,
+    truncation: Positive,
+) -> Result<EM>
+where
+    P: Is3D,
+    N: IsNormalized3D,
+    EM: IsFaceEditableMesh<PM, Face3> + IsVertexEditableMesh<PM, Face3> + Default,
+    PM: IsBuildable3D,
+{
+    if normals.len() != pc.len() {
+        return Err(ErrorKind::NormalArrayIncorrectLength);
+    }
+
+    let first = pc.data.first().ok_or(ErrorKind::TooFewPoints)?;
+    let mut lower = first.xyz();
+    let mut upper = first.xyz();
+    for p in pc.data.iter() {
+        for (i, x) in p.xyz().iter().enumerate() {
+            lower[i] = lower[i].min(*x);
+            upper[i] = upper[i].max(*x);
+        }
+    }
+
+    let voxel = *voxel_size;
+    let margin = *truncation + voxel;
+    let min = Point3D::new(lower[0] - margin, lower[1] - margin, lower[2] - margin);
+    let resolution =
+        [0, 1, 2].map(|i| ((upper[i] - lower[i] + 2.0 * margin) / voxel).ceil() as usize + 1);
+    let [nx, ny, nz] = resolution;
+
+    let mut sum_distances = vec![0.0; nx * ny * nz];
+    let mut counts = vec![0usize; nx * ny * nz];
+    let reach = (*truncation / voxel).ceil() as isize;
+
+    for (p, n) in pc.data.iter().zip(normals.iter()) {
+        let cell = [
+            ((p.x() - min.x) / voxel).round() as isize,
+            ((p.y() - min.y) / voxel).round() as isize,
+            ((p.z() - min.z) / voxel).round() as isize,
+        ];
+
+        for z in (cell[2] - reach).max(0)..=(cell[2] + reach).min(nz as isize - 1) {
+            for y in (cell[1] - reach).max(0)..=(cell[1] + reach).min(ny as isize - 1) {
+                for x in (cell[0] - reach).max(0)..=(cell[0] + reach).min(nx as isize - 1) {
+                    let sample = Point3D::new(
+                        min.x + x as f64 * voxel,
+                        min.y + y as f64 * voxel,
+                        min.z + z as f64 * voxel,
+                    );
+                    if dist_3d(&sample, p) > *truncation {
+                        continue;
+                    }
+                    let distance = n.x() * (sample.x - p.x())
+                        + n.y() * (sample.y - p.y())
+                        + n.z() * (sample.z - p.z());
+
+                    let i = x as usize + nx * (y as usize + ny * z as usize);
+                    sum_distances[i] += distance;
+                    counts[i] += 1;
+                }
+            }
+        }
+    }
+
+    let values: Vec<f64> = sum_distances
+        .iter()
+        .zip(&counts)
+        .map(|(sum, count)| match *count {
+            0 => f64::NAN,
+            n => sum / n as f64,
+        })
+        .collect();
+
+    let max = Point3D::new(
+        min.x + (nx - 1) as f64 * voxel,
+        min.y + (ny - 1) as f64 * voxel,
+        min.z + (nz - 1) as f64 * voxel,
+    );
+
+    polygonize_sdf_grid(&values, &BoundingBox3D::new(&min, &max)?, resolution)
+}
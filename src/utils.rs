@@ -124,6 +124,179 @@ where
         .collect()
 }
 
+/// Calculates the normal of each face from its vertex winding ((v2 - v1) x (v3 - v1)).
+/// Degenerate faces (e.g. with collinear vertices) result in None
+pub fn recompute_face_normals<M, P>(mesh: &M) -> Vec<Option<Norm3D>>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    (0..mesh.num_faces())
+        .map(|i| {
+            let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+            Norm3D::new(face_winding(&v1, &v2, &v3)).ok()
+        })
+        .collect()
+}
+
+/// Calculates the normal of each vertex by averaging the normals of its incident faces.
+/// If area_weighted is set, larger faces contribute more to the average.
+/// The result has the same order as the vertices of the mesh.
+/// Vertices belonging to no face (or whose face normals cancel out) result in None.
+/// Exports requiring a normal per vertex (e.g. save_ply_ascii_with_normals) can replace those by a fallback, e.g. via unwrap_or(Norm3D::norm_z()) like normals_of_mesh
+pub fn vertex_normals<M, P>(mesh: &M, area_weighted: bool) -> Vec<Option<Norm3D>>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    let mut sums = vec![Point3D::default(); mesh.num_vertices()];
+
+    for i in 0..mesh.num_faces() {
+        let face = mesh.face_vertex_ids(FId(i)).unwrap(); // safe since iterating num_faces
+        let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+
+        // length of the winding is twice the area of the face
+        let winding = face_winding(&v1, &v2, &v3);
+        let contribution = if area_weighted {
+            winding
+        } else {
+            match Norm3D::new(winding) {
+                Ok(n) => Point3D::new(n.x(), n.y(), n.z()),
+                Err(_) => continue,
+            }
+        };
+        for vid in [face.a, face.b, face.c] {
+            sums[vid.0] = add(&sums[vid.0], &contribution);
+        }
+    }
+
+    sums.into_iter().map(|x| Norm3D::new(x).ok()).collect()
+}
+
+/// Calculates the surface area of the mesh as sum of the areas of its faces
+pub fn mesh_surface_area<M, P>(mesh: &M) -> f64
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    (0..mesh.num_faces())
+        .map(|i| {
+            let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+            0.5 * *face_winding(&v1, &v2, &v3).abs()
+        })
+        .sum()
+}
+
+/// Calculates the signed volume enclosed by the mesh as sum of the signed volumes of the tetrahedra spanned by the origin and each face.
+/// The result is only meaningful for closed meshes. It's positive if the faces are wound counter-clockwise when viewed from outside and negative for inverted meshes
+pub fn mesh_signed_volume<M, P>(mesh: &M) -> f64
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    (0..mesh.num_faces())
+        .map(|i| {
+            let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe since iterating num_faces
+            v1.dot(&cross(&v2, &Point3D::new_from(&v3))) / 6.0
+        })
+        .sum()
+}
+
+/// Returns the faces whose given normal deviates from the normal derived from their vertex winding by more than tolerance (in radians).
+/// Faces with a degenerated winding or a zero length normal are returned as well.
+/// Returns ErrorKind::NormalArrayIncorrectLength if there isn't exactly one normal per face
+pub fn faces_with_inconsistent_normals<M, P, N>(
+    mesh: &M,
+    normals: &[N],
+    tolerance: f64,
+) -> Result<Vec<FId>>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+    N: Is3D,
+{
+    let nf = mesh.num_faces();
+    if normals.len() != nf {
+        return Err(ErrorKind::NormalArrayIncorrectLength);
+    }
+
+    let mut result = Vec::new();
+
+    for (i, n) in normals.iter().enumerate() {
+        let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe
+        match face_winding(&v1, &v2, &v3).rad_to(n) {
+            Ok(angle) if angle.0 <= tolerance => (),
+            _ => result.push(FId(i)),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Collects the vertices of a mesh into a point cloud
+pub fn mesh_vertices_to_cloud<M, P>(mesh: &M) -> PointCloud3D<P>
+where
+    M: IsMesh<P, Face3>,
+    P: Is3D,
+{
+    let n = mesh.num_vertices();
+    let mut pc = PointCloud3D::with_capacity(n);
+
+    for i in 0..n {
+        pc.push(mesh.vertex(VId(i)).unwrap()); // safe
+    }
+
+    pc
+}
+
+/// Collects the centers of the faces of a mesh into a point cloud
+pub fn mesh_face_centers_to_cloud<M, P>(mesh: &M) -> PointCloud3D<P>
+where
+    M: IsMesh<P, Face3>,
+    P: IsBuildable3D,
+{
+    let nf = mesh.num_faces();
+    let mut pc = PointCloud3D::with_capacity(nf);
+
+    for i in 0..nf {
+        let [v1, v2, v3] = mesh.face_vertices(FId(i)).unwrap(); // safe
+        pc.push(P::new(
+            (v1.x() + v2.x() + v3.x()) / 3.0,
+            (v1.y() + v2.y() + v3.y()) / 3.0,
+            (v1.z() + v2.z() + v3.z()) / 3.0,
+        ));
+    }
+
+    pc
+}
+
+/// Simple, seedable pseudo random number generator (SplitMix64), to keep randomized algorithms deterministic
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    /// Returns the next pseudo random number
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// Returns the next pseudo random number within [0.0, 1.0)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    /// Returns the next pseudo random index within [0, n), n must be > 0
+    pub fn next_index(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
 /// Estimates the used delimiter within a string
 pub fn estimate_delimiter(minimum_count: usize, line: &[u8]) -> Option<u8> {
     for candidate in [b' ', b';', b',', b'\t'].iter() {
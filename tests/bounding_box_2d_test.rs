@@ -79,3 +79,46 @@ fn bounding_box_2d_test() {
     assert!(!bb1.contains(&Point2D::new(5.0, 5.0)));
     assert!(bb1.contains(&Point2D::new(0.5, 0.5)));
 }
+
+#[test]
+fn aabb_tree_2d_median_test() {
+    let square = |x: f64, y: f64, size: f64| {
+        BoundingBox2D::new(
+            &Point2D::new(x - size, y - size),
+            &Point2D::new(x + size, y + size),
+        )
+        .unwrap()
+    };
+
+    let sorted = |result: Vec<&BoundingBox2D>| {
+        let mut result = result.into_iter().cloned().collect::<Vec<_>>();
+        result.sort();
+        result.dedup();
+        result
+    };
+
+    // all centers on a line along the y axis, which is not considered first
+    let mut line = Vec::new();
+    for y in 0..1024 {
+        line.push(square(0.0, y as f64, 0.3));
+    }
+
+    let median = AABBTree2D::new_with_strategy(line.clone(), 64, 1, SplitStrategy::Median);
+    let center = AABBTree2D::new_with_strategy(line, 64, 1, SplitStrategy::Center);
+    assert_eq!(10, median.depth());
+
+    for i in 0..100 {
+        let search = square(0.1 * i as f64 - 2.0, 11.0 * i as f64, 1.0 + 0.1 * i as f64);
+        let mut expected = Vec::new();
+        center.bb_colliding(&search, &mut expected);
+        let mut result = Vec::new();
+        median.bb_colliding(&search, &mut result);
+
+        assert_eq!(sorted(expected), sorted(result));
+    }
+
+    // there's no split if all centers coincide
+    let coincident = vec![square(1.0, 2.0, 0.5); 20];
+    let median = AABBTree2D::new_with_strategy(coincident, 64, 1, SplitStrategy::Median);
+    assert_eq!(0, median.depth());
+}
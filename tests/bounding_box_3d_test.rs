@@ -22,6 +22,8 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 #![deny(warnings)]
 
+mod common;
+
 use rust_3d::*;
 
 #[test]
@@ -83,3 +85,553 @@ fn test_bounding_box_3d() {
     assert!(!bb1.contains(&Point3D::new(5.0, 5.0, 5.0)));
     assert!(bb1.contains(&Point3D::new(0.5, 0.5, 0.5)));
 }
+
+#[test]
+fn test_aabb_tree_3d_traced_ray_debug() {
+    let mut boxes = Vec::new();
+    for i in 0..8 {
+        let min = Point3D::new(2.0 * i as f64, 0.0, 0.0);
+        let max = Point3D::new(2.0 * i as f64 + 1.0, 1.0, 1.0);
+        boxes.push(BoundingBox3D::new(&min, &max).unwrap());
+    }
+    let tree = AABBTree3D::new(boxes, 10, 1);
+
+    let ray = Ray3D::new(Line3D::new(
+        Point3D::new(-1.0, 0.5, 0.5),
+        Norm3D::new(Point3D::new(1.0, 0.0, 0.0)).unwrap(),
+    ));
+
+    let mut hits = 0;
+    let mut traversed = Vec::new();
+    tree.for_each_intersection_candidate_traced(
+        &ray.line,
+        &mut |_| hits += 1,
+        Some(&mut traversed),
+    );
+    assert_eq!(8, hits);
+    assert!(!traversed.is_empty());
+
+    let mut untraced_hits = 0;
+    tree.for_each_intersection_candidate(&ray.line, &mut |_| untraced_hits += 1);
+    assert_eq!(hits, untraced_hits);
+
+    let mut buffer = Vec::new();
+    io::save_obj_ray_debug(&mut buffer, &ray, &traversed).unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert_eq!(
+        2 + 8 * traversed.len(),
+        text.lines().filter(|l| l.starts_with("v ")).count()
+    );
+    assert_eq!(
+        1 + 12 * traversed.len(),
+        text.lines().filter(|l| l.starts_with("l ")).count()
+    );
+}
+
+#[test]
+fn test_which_child_first() {
+    let near =
+        BoundingBox3D::new(&Point3D::new(1.0, 0.0, 0.0), &Point3D::new(2.0, 1.0, 1.0)).unwrap();
+    let far =
+        BoundingBox3D::new(&Point3D::new(5.0, 0.0, 0.0), &Point3D::new(6.0, 1.0, 1.0)).unwrap();
+    let missed =
+        BoundingBox3D::new(&Point3D::new(1.0, 5.0, 0.0), &Point3D::new(2.0, 6.0, 1.0)).unwrap();
+
+    let ray = Ray3D::new(Line3D::new(
+        Point3D::new(0.0, 0.5, 0.5),
+        Norm3D::new(Point3D::new(1.0, 0.0, 0.0)).unwrap(),
+    ));
+
+    assert_eq!(Some(1.0), ray_entry(&ray, &near));
+    assert_eq!(None, ray_entry(&ray, &missed));
+
+    assert_eq!(
+        [Some((TreeChild::Left, 1.0)), Some((TreeChild::Right, 5.0))],
+        which_child_first(&ray, &near, &far)
+    );
+    assert_eq!(
+        [Some((TreeChild::Right, 1.0)), Some((TreeChild::Left, 5.0))],
+        which_child_first(&ray, &far, &near)
+    );
+    assert_eq!(
+        [Some((TreeChild::Right, 1.0)), None],
+        which_child_first(&ray, &missed, &near)
+    );
+
+    let mut boxes = Vec::new();
+    for i in (0..8).rev() {
+        let min = Point3D::new(2.0 * i as f64, 0.0, 0.0);
+        let max = Point3D::new(2.0 * i as f64 + 1.0, 1.0, 1.0);
+        boxes.push(BoundingBox3D::new(&min, &max).unwrap());
+    }
+    let tree = AABBTree3D::new(boxes, 10, 1);
+
+    let ray = Ray3D::new(Line3D::new(
+        Point3D::new(-1.0, 0.5, 0.5),
+        Norm3D::new(Point3D::new(1.0, 0.0, 0.0)).unwrap(),
+    ));
+
+    let mut visited = Vec::new();
+    tree.for_each_intersection_candidate_near_to_far(&ray, &mut |bb| {
+        visited.push(bb.min_p().x);
+        true
+    });
+    assert_eq!(vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0], visited);
+
+    let mut visited = Vec::new();
+    assert!(
+        !tree.for_each_intersection_candidate_near_to_far(&ray, &mut |bb| {
+            visited.push(bb.min_p().x);
+            false
+        })
+    );
+    assert_eq!(vec![0.0], visited);
+}
+
+#[test]
+fn test_bounding_box_3d_octants() {
+    let bb =
+        BoundingBox3D::new(&Point3D::new(-1.0, 0.0, 2.0), &Point3D::new(3.0, 2.0, 8.0)).unwrap();
+    let center = bb.center_bb();
+    let volume = |b: &BoundingBox3D| *b.size_x() * *b.size_y() * *b.size_z();
+
+    let octants = bb.octants().unwrap();
+    let directions = [
+        Direction::PPP,
+        Direction::PPN,
+        Direction::PNP,
+        Direction::PNN,
+        Direction::NPP,
+        Direction::NPN,
+        Direction::NNP,
+        Direction::NNN,
+    ];
+
+    let mut total = 0.0;
+    for (octant, direction) in octants.iter().zip(directions.iter()) {
+        let (min, max) = (octant.min_p(), octant.max_p());
+        assert!(min.x >= -1.0 && min.y >= 0.0 && min.z >= 2.0);
+        assert!(max.x <= 3.0 && max.y <= 2.0 && max.z <= 8.0);
+        assert_eq!(*direction, calc_direction(&center, &octant.center_bb()));
+        assert!((volume(octant) - volume(&bb) / 8.0).abs() < 1e-9);
+        total += volume(octant);
+    }
+    // equal volumes, all inside and in distinct directions => no gaps or overlaps
+    assert!((total - volume(&bb)).abs() < 1e-9);
+
+    let tiny = BoundingBox3D::new(
+        &Point3D::new(1.0, 1.0, 1.0),
+        &Point3D::new(1.0 + f64::EPSILON, 2.0, 2.0),
+    )
+    .unwrap();
+    assert!(tiny.octants().is_err());
+}
+
+#[test]
+fn test_primitives_move_and_scale() {
+    let mut sphere = Sphere {
+        center: Point3D::new(1.0, 2.0, 3.0),
+        radius: Positive::one(),
+    };
+    sphere.move_by(1.0, 1.0, 1.0);
+    sphere.scale(Positive::new(2.0).unwrap());
+    assert_eq!(Point3D::new(2.0, 3.0, 4.0), sphere.center);
+    assert_eq!(4.0, sphere.z());
+    assert_eq!(2.0, *sphere.radius);
+
+    let mut bb =
+        BoundingBox3D::new(&Point3D::new(0.0, 0.0, 0.0), &Point3D::new(2.0, 2.0, 2.0)).unwrap();
+    bb.move_by(1.0, 0.0, -1.0);
+    bb.scale(Positive::new(0.5).unwrap());
+    assert_eq!(Point3D::new(1.5, 0.5, -0.5), bb.min_p());
+    assert_eq!(Point3D::new(2.5, 1.5, 0.5), bb.max_p());
+
+    let mut plane = Plane3D::<Point3D, Norm3D>::default();
+    plane.move_by(0.0, 0.0, 2.0);
+    assert_eq!(Point3D::new(0.0, 0.0, 2.0), plane.origin);
+    assert_eq!(-2.0, plane.signed_distance(&Point3D::default()));
+}
+
+#[test]
+fn test_bounding_box_3d_clip_line() {
+    let bb =
+        BoundingBox3D::new(&Point3D::new(0.0, 0.0, 0.0), &Point3D::new(2.0, 2.0, 2.0)).unwrap();
+    let eps = 1e-9;
+
+    let line = Line3D::new(Point3D::new(-5.0, 1.0, 1.0), Norm3D::norm_x());
+    let clipped = bb.clip_line(&line).unwrap();
+    assert!(dist_3d(&clipped.start, &Point3D::new(0.0, 1.0, 1.0)) < eps);
+    assert!(dist_3d(&clipped.end, &Point3D::new(2.0, 1.0, 1.0)) < eps);
+
+    // lines are infinite in both directions
+    let line = Line3D::new(Point3D::new(5.0, 1.0, 1.0), Norm3D::norm_x());
+    let clipped = bb.clip_line(&line).unwrap();
+    assert!(dist_3d(&clipped.start, &Point3D::new(0.0, 1.0, 1.0)) < eps);
+    assert!(dist_3d(&clipped.end, &Point3D::new(2.0, 1.0, 1.0)) < eps);
+
+    let diagonal = Line3D::new(
+        Point3D::new(1.0, 1.0, 1.0),
+        Norm3D::new(Point3D::new(1.0, 1.0, 1.0)).unwrap(),
+    );
+    let clipped = bb.clip_line(&diagonal).unwrap();
+    assert!(dist_3d(&clipped.start, &Point3D::new(0.0, 0.0, 0.0)) < eps);
+    assert!(dist_3d(&clipped.end, &Point3D::new(2.0, 2.0, 2.0)) < eps);
+
+    let miss = Line3D::new(Point3D::new(0.0, 5.0, 1.0), Norm3D::norm_x());
+    assert!(bb.clip_line(&miss).is_none());
+}
+
+#[test]
+fn frustum_test() {
+    let plane = |o: [f64; 3], u: [f64; 3], v: [f64; 3]| {
+        Plane3D::new(
+            Point3D::new(o[0], o[1], o[2]),
+            Norm3D::new(Point3D::new(u[0], u[1], u[2])).unwrap(),
+            Norm3D::new(Point3D::new(v[0], v[1], v[2])).unwrap(),
+        )
+    };
+    let (x, y, z) = ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]);
+    // the cube [-2, 2]^3, with normals pointing inside
+    let from_planes = Frustum::new(&[
+        plane([-2.0, 0.0, 0.0], y, z),
+        plane([2.0, 0.0, 0.0], z, y),
+        plane([0.0, -2.0, 0.0], z, x),
+        plane([0.0, 2.0, 0.0], x, z),
+        plane([0.0, 0.0, -2.0], x, y),
+        plane([0.0, 0.0, 2.0], y, x),
+    ]);
+    let from_matrix = Frustum::from_view_projection(&Matrix4::scale(0.5, 0.5, 0.5)).unwrap();
+    assert!(Frustum::from_view_projection(&Matrix4::zeroes()).is_err());
+
+    let bb = |min: [f64; 3], max: [f64; 3]| {
+        BoundingBox3D::new::<Point3D, Point3D>(&min.into(), &max.into()).unwrap()
+    };
+
+    for frustum in [&from_planes, &from_matrix] {
+        assert!(frustum.contains_point(&Point3D::new(0.0, 0.0, 0.0)));
+        assert!(frustum.contains_point(&Point3D::new(2.0, -2.0, 1.9)));
+        assert!(!frustum.contains_point(&Point3D::new(0.0, 2.1, 0.0)));
+        assert!(frustum.is_allowed(&Point3D::new(1.0, 1.0, 1.0)));
+
+        assert_eq!(
+            Intersection::Inside,
+            frustum.intersects_bb(&bb([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0]))
+        );
+        assert_eq!(
+            Intersection::Partial,
+            frustum.intersects_bb(&bb([1.0, 1.0, 1.0], [3.0, 3.0, 3.0]))
+        );
+        assert_eq!(
+            Intersection::Outside,
+            frustum.intersects_bb(&bb([2.5, 0.0, 0.0], [3.0, 1.0, 1.0]))
+        );
+
+        let mut pc = PointCloud3D::<Point3D>::new();
+        for i in 0..10 {
+            pc.push(Point3D::new(i as f64 - 5.0, 0.0, 0.0));
+        }
+        let expected: std::collections::HashSet<usize> = (3..8).collect();
+        assert_eq!(View::Restricted(expected), cull_cloud(&pc, frustum));
+
+        let mut boxes = Vec::new();
+        for i in 0..8 {
+            boxes.push(bb(
+                [2.0 * i as f64, 0.0, 0.0],
+                [2.0 * i as f64 + 1.0, 1.0, 1.0],
+            ));
+        }
+        let tree = AABBTree3D::new(boxes, 10, 1);
+        let mut culled = Vec::new();
+        tree.bb_in_frustum(frustum, &mut culled);
+        let mut culled = culled.iter().map(|x| x.min_p().x).collect::<Vec<_>>();
+        culled.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(vec![0.0, 2.0], culled);
+    }
+}
+
+#[test]
+fn aabb_tree_3d_nearest_test() {
+    let mut random = common::random_generator(7, 0.0, 1.0);
+
+    let mut boxes = Vec::new();
+    for _ in 0..300 {
+        let min = Point3D::new(10.0 * random(), 10.0 * random(), 10.0 * random());
+        let max = Point3D::new(
+            min.x + 0.1 + random(),
+            min.y + 0.1 + random(),
+            min.z + 0.1 + random(),
+        );
+        boxes.push(BoundingBox3D::new(&min, &max).unwrap());
+    }
+
+    assert!(AABBTree3D::<BoundingBox3D>::new(Vec::new(), 10, 4)
+        .nearest(&Point3D::new(0.0, 0.0, 0.0))
+        .is_none());
+
+    for strategy in [SplitStrategy::Center, SplitStrategy::Median] {
+        let tree = AABBTree3D::new_with_strategy(boxes.clone(), 20, 4, strategy);
+
+        for _ in 0..100 {
+            let p = Point3D::new(
+                14.0 * random() - 2.0,
+                14.0 * random() - 2.0,
+                14.0 * random() - 2.0,
+            );
+            let expected = boxes
+                .iter()
+                .map(|bb| *bb.sqr_distance(&p))
+                .fold(f64::MAX, f64::min);
+            let nearest = tree.nearest(&p).unwrap();
+            assert_eq!(expected, *nearest.sqr_distance(&p));
+        }
+    }
+
+    // inside of a box
+    let tree = AABBTree3D::new(boxes.clone(), 20, 4);
+    let center = boxes[17].center_bb();
+    assert_eq!(0.0, *tree.nearest(&center).unwrap().sqr_distance(&center));
+}
+
+#[test]
+fn aabb_tree_3d_first_hit_test() {
+    let tri = |z: f64, offset: f64| {
+        TriFace3D::new(
+            Point3D::new(-1.0 + offset, -1.0, z),
+            Point3D::new(1.0 + offset, -1.0, z + 0.1),
+            Point3D::new(offset, 1.0, z),
+        )
+        .unwrap()
+    };
+
+    let mut faces = Vec::new();
+    for i in 0..20 {
+        faces.push(tri(i as f64 - 5.5, 0.0));
+        faces.push(tri(i as f64, 10.0));
+    }
+    let tree = AABBTree3D::new(faces.clone(), 10, 2);
+
+    let ray = |anchor: Point3D, dir: Point3D| Line3D::new(anchor, Norm3D::new(dir).unwrap());
+
+    // hits behind the anchor are ignored
+    let (t, hit) = tree
+        .first_hit(&ray(
+            Point3D::new(0.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, 1.0),
+        ))
+        .unwrap();
+    assert!((t - 0.525).abs() < 1e-9);
+    assert_eq!(&faces[12], hit);
+
+    let (t, hit) = tree
+        .first_hit(&ray(
+            Point3D::new(0.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, -1.0),
+        ))
+        .unwrap();
+    assert!((t - 0.475).abs() < 1e-9);
+    assert_eq!(&faces[10], hit);
+
+    assert!(tree
+        .first_hit(&ray(
+            Point3D::new(5.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, 1.0)
+        ))
+        .is_none());
+    assert!(tree
+        .first_hit(&ray(
+            Point3D::new(0.0, 0.0, 20.0),
+            Point3D::new(0.0, 0.0, 1.0)
+        ))
+        .is_none());
+
+    // compared to testing all faces
+    for i in 0..50 {
+        let f = i as f64;
+        let r = ray(
+            Point3D::new(5.0 * (0.3 * f).sin(), 0.5 * (0.7 * f).cos(), f - 25.0),
+            Point3D::new((0.9 * f).cos(), 0.2 * (1.1 * f).sin(), (0.5 * f).sin()),
+        );
+        let expected = faces
+            .iter()
+            .filter_map(|x| intersection_line_triangle_parameter(&r, &x.triangle()))
+            .filter(|t| *t > 0.0)
+            .fold(None, |acc: Option<f64>, t| {
+                Some(acc.map_or(t, |a| a.min(t)))
+            });
+        assert_eq!(expected, tree.first_hit(&r).map(|(t, _)| t));
+    }
+}
+
+#[test]
+fn intersect_ray_triangle_test() {
+    let a = Point3D::new(0.0, 0.0, 1.0);
+    let b = Point3D::new(3.0, 0.0, 1.0);
+    let c = Point3D::new(0.0, 3.0, 1.0);
+    let below = Point3D::new(1.0, 1.0, 0.0);
+
+    // dead center, the triangle faces the ray
+    let down = Line3D {
+        anchor: Point3D::new(1.0, 1.0, 5.0),
+        dir: Norm3D::norm_z_neg(),
+    };
+    let (t, u, v) = intersect_ray_triangle(&down, &a, &b, &c, true).unwrap();
+    assert!((4.0 - t).abs() < 1e-12);
+    assert!((1.0 / 3.0 - u).abs() < 1e-12);
+    assert!((1.0 / 3.0 - v).abs() < 1e-12);
+
+    // from below it's a backface
+    let up = Line3D {
+        anchor: below.clone(),
+        dir: Norm3D::norm_z(),
+    };
+    assert!(intersect_ray_triangle(&up, &a, &b, &c, true).is_none());
+    let (t, u, v) = intersect_ray_triangle(&up, &a, &b, &c, false).unwrap();
+    assert!((1.0 - t).abs() < 1e-12);
+    assert!((1.0 / 3.0 - u).abs() < 1e-12);
+    assert!((1.0 / 3.0 - v).abs() < 1e-12);
+
+    // barycentric coordinates reproduce the hit
+    let oblique = Line3D {
+        anchor: Point3D::new(-1.0, 0.0, 3.0),
+        dir: Norm3D::new(Point3D::new(1.0, 0.5, -1.0)).unwrap(),
+    };
+    let (t, u, v) = intersect_ray_triangle(&oblique, &a, &b, &c, true).unwrap();
+    let hit = &oblique.anchor + &(&Point3D::new_from(&oblique.dir) * t);
+    let expected = &(&(&a * (1.0 - u - v)) + &(&b * u)) + &(&c * v);
+    assert!(dist_3d(&hit, &expected) < 1e-12);
+
+    // just outside of the edge b c (x + y = 3)
+    let outside = Line3D {
+        anchor: Point3D::new(1.501, 1.5, 5.0),
+        dir: Norm3D::norm_z_neg(),
+    };
+    assert!(intersect_ray_triangle(&outside, &a, &b, &c, false).is_none());
+    let inside = Line3D {
+        anchor: Point3D::new(1.499, 1.5, 5.0),
+        dir: Norm3D::norm_z_neg(),
+    };
+    assert!(intersect_ray_triangle(&inside, &a, &b, &c, false).is_some());
+
+    // behind the anchor
+    let away = Line3D {
+        anchor: Point3D::new(1.0, 1.0, 5.0),
+        dir: Norm3D::norm_z(),
+    };
+    assert!(intersect_ray_triangle(&away, &a, &b, &c, false).is_none());
+
+    // parallel
+    let parallel = Line3D {
+        anchor: Point3D::new(-1.0, 1.0, 1.0),
+        dir: Norm3D::norm_x(),
+    };
+    assert!(intersect_ray_triangle(&parallel, &a, &b, &c, false).is_none());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn aabb_tree_3d_parallel_test() {
+    let mut random = common::random_generator(11, 0.0, 1.0);
+
+    // enough boxes for the parallel path to be used
+    let mut boxes = Vec::new();
+    for _ in 0..30_000 {
+        let min = Point3D::new(100.0 * random(), 100.0 * random(), 100.0 * random());
+        let max = Point3D::new(
+            min.x + 0.1 + random(),
+            min.y + 0.1 + random(),
+            min.z + 0.1 + random(),
+        );
+        boxes.push(BoundingBox3D::new(&min, &max).unwrap());
+    }
+
+    for strategy in [SplitStrategy::Center, SplitStrategy::Median] {
+        let sequential = AABBTree3D::new_with_strategy(boxes.clone(), 30, 4, strategy);
+        let parallel = AABBTree3D::new_parallel_with_strategy(boxes.clone(), 30, 4, strategy);
+
+        for _ in 0..100 {
+            let min = Point3D::new(100.0 * random(), 100.0 * random(), 100.0 * random());
+            let max = Point3D::new(
+                min.x + 10.0 * random() + 0.1,
+                min.y + 10.0 * random() + 0.1,
+                min.z + 10.0 * random() + 0.1,
+            );
+            let search = BoundingBox3D::new(&min, &max).unwrap();
+
+            let mut expected = Vec::new();
+            sequential.bb_colliding(&search, &mut expected);
+            let mut result = Vec::new();
+            parallel.bb_colliding(&search, &mut result);
+
+            assert_eq!(expected, result);
+        }
+    }
+}
+
+#[test]
+fn aabb_tree_3d_median_test() {
+    let mut random = common::random_generator(3, 0.0, 1.0);
+
+    let cube = |x: f64, y: f64, z: f64, size: f64| {
+        BoundingBox3D::new(
+            &Point3D::new(x - size, y - size, z - size),
+            &Point3D::new(x + size, y + size, z + size),
+        )
+        .unwrap()
+    };
+
+    let mut scattered = Vec::new();
+    for _ in 0..1024 {
+        scattered.push(cube(
+            10.0 * random(),
+            10.0 * random(),
+            10.0 * random(),
+            0.05 + 0.5 * random(),
+        ));
+    }
+
+    // all centers within a plane and on lines along the axes
+    let mut planar = Vec::new();
+    for x in 0..32 {
+        for y in 0..32 {
+            planar.push(cube(x as f64, y as f64, 0.0, 0.3));
+        }
+    }
+
+    let sorted = |result: Vec<&BoundingBox3D>| {
+        let mut result = result.into_iter().cloned().collect::<Vec<_>>();
+        result.sort();
+        result.dedup();
+        result
+    };
+
+    for boxes in [scattered, planar] {
+        let median = AABBTree3D::new_with_strategy(boxes.clone(), 64, 1, SplitStrategy::Median);
+        let center = AABBTree3D::new_with_strategy(boxes.clone(), 64, 1, SplitStrategy::Center);
+
+        // 1024 elements with a bucket size of 1 require exactly 10 halvings
+        assert_eq!(10, median.depth());
+
+        for _ in 0..100 {
+            let search = cube(
+                12.0 * random() - 1.0,
+                12.0 * random() - 1.0,
+                12.0 * random() - 1.0,
+                0.1 + 2.0 * random(),
+            );
+            let mut expected = Vec::new();
+            center.bb_colliding(&search, &mut expected);
+            let mut result = Vec::new();
+            median.bb_colliding(&search, &mut result);
+
+            assert_eq!(sorted(expected), sorted(result));
+        }
+    }
+
+    // there's no split if all centers coincide
+    let coincident = vec![cube(1.0, 2.0, 3.0, 0.5); 20];
+    let median = AABBTree3D::new_with_strategy(coincident, 64, 1, SplitStrategy::Median);
+    assert_eq!(0, median.depth());
+    let mut result = Vec::new();
+    median.bb_colliding(&cube(1.0, 2.0, 3.0, 0.1), &mut result);
+    assert_eq!(20, result.len());
+}
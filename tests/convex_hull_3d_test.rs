@@ -0,0 +1,73 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+#![deny(warnings)]
+
+use rust_3d::*;
+
+type M = Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>>;
+
+#[test]
+fn convex_hull_3d_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for x in 0..2 {
+        for y in 0..2 {
+            for z in 0..2 {
+                pc.push(Point3D::new(x as f64, y as f64, z as f64));
+            }
+        }
+    }
+    pc.push(Point3D::new(0.5, 0.5, 0.5));
+    pc.push(Point3D::new(0.2, 0.7, 0.1));
+    pc.push(Point3D::new(0.5, 0.5, 1.0));
+
+    let hull: M = convex_hull_3d(&pc).unwrap();
+    assert_eq!(8, hull.num_vertices());
+    assert_eq!(12, hull.num_faces());
+
+    let center = Point3D::new(0.5, 0.5, 0.5);
+    for i in 0..hull.num_faces() {
+        let [a, b, c] = hull.face_vertices(FId(i)).unwrap();
+        let n = normal_of_face(&a, &b, &c);
+        assert!(n.dot(&(&a - &center)) > 0.0);
+    }
+}
+
+#[test]
+fn convex_hull_3d_degenerate_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(0.0, 0.0, 0.0));
+    pc.push(Point3D::new(1.0, 1.0, 1.0));
+    assert!(convex_hull_3d::<_, _, M>(&pc).is_err());
+
+    pc.push(Point3D::new(2.0, 2.0, 2.0));
+    assert!(convex_hull_3d::<_, _, M>(&pc).is_err());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(0.0, 0.0, 0.0));
+    pc.push(Point3D::new(2.0, 2.0, 2.0));
+    pc.push(Point3D::new(2.0, 0.0, 0.0));
+    pc.push(Point3D::new(1.5, 0.5, 0.5));
+    let flat: M = convex_hull_3d(&pc).unwrap();
+    assert_eq!(3, flat.num_vertices());
+    assert_eq!(2, flat.num_faces());
+}
@@ -82,3 +82,46 @@ fn filter_pc_3d_test() {
         }
     }
 }
+
+#[test]
+fn filter_pc_3d_selection_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..10 {
+        pc.push(Point3D::new(i as f64, 0.0, 0.0));
+    }
+    let indices_of = |view: View| -> Vec<usize> {
+        match view {
+            View::Full => panic!("view should be restricted"),
+            View::Restricted(indices) => {
+                let mut indices: Vec<usize> = indices.into_iter().collect();
+                indices.sort();
+                indices
+            }
+        }
+    };
+
+    let stride = FilterRandomAccessible::new(FilterAllow::new())
+        .with_selection(IndexSelection::Stride { step: 3, offset: 1 });
+    let mut view = View::Full;
+    stride.filter(&pc, &mut view);
+    assert_eq!(vec![1, 4, 7], indices_of(view));
+
+    let range = FilterRandomAccessible::new(FilterAllow::new())
+        .with_selection(IndexSelection::Range { start: 8, end: 20 });
+    let mut view = View::Full;
+    range.filter(&pc, &mut view);
+    assert_eq!(vec![8, 9], indices_of(view));
+
+    // combined with a restricted view and a filter
+    let filter = FilterBox3D::new(Box3D {
+        center: Point3D::new(2.0, 0.0, 0.0),
+        size_x: Positive::new(5.0).unwrap(),
+        size_y: Positive::new(1.0).unwrap(),
+        size_z: Positive::new(1.0).unwrap(),
+    });
+    let even = FilterRandomAccessible::new(filter)
+        .with_selection(IndexSelection::Stride { step: 2, offset: 0 });
+    let mut view = View::Restricted((0..7).collect());
+    even.filter(&pc, &mut view);
+    assert_eq!(vec![0, 2, 4], indices_of(view));
+}
@@ -53,3 +53,59 @@ fn filter_box_2d_test() {
         "box_2d",
     );
 }
+
+#[test]
+fn filter_outlier_2d_test() {
+    let mut pc = PointCloud2D::<Point2D>::new();
+    // dense cluster
+    for i in 0..10 {
+        for j in 0..10 {
+            pc.push(Point2D::new(i as f64 * 0.1, j as f64 * 0.1));
+        }
+    }
+    // isolated noise
+    let outliers = [
+        Point2D::new(5.0, 5.0),
+        Point2D::new(-3.0, 0.5),
+        Point2D::new(0.5, 7.0),
+        Point2D::new(1.5, 1.5),
+    ];
+    for p in outliers.iter() {
+        pc.push(p.clone());
+    }
+
+    let mut tree = KdTree2D::default();
+    tree.build(pc.clone()).unwrap();
+    assert_eq!(pc.len(), tree.size());
+
+    let circle = Circle {
+        center: Point2D::new(0.45, 0.45),
+        radius: Positive::new(0.22).unwrap(),
+    };
+    let mut found = Vec::new();
+    tree.in_circle(&circle, &mut found);
+    let expected = pc
+        .data
+        .iter()
+        .filter(|p| dist_2d(*p, &circle.center) <= *circle.radius)
+        .count();
+    assert_eq!(expected, found.len());
+
+    // each point finds itself, so require two further neighbours
+    let filter = FilterOutlier2D::new(tree, Positive::new(0.15).unwrap(), 3);
+
+    for p in pc.data.iter().take(100) {
+        assert!(filter.is_allowed(p));
+    }
+    for p in outliers.iter() {
+        assert!(!filter.is_allowed(p));
+    }
+
+    let filter_pc = FilterRandomAccessible::new(filter);
+    let mut view = View::Full;
+    filter_pc.filter(&pc, &mut view);
+    match view {
+        View::Restricted(indices) => assert_eq!(100, indices.len()),
+        View::Full => panic!("outliers weren't removed"),
+    }
+}
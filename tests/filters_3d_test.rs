@@ -60,3 +60,75 @@ fn filter_sphere_test() {
     });
     test_filter_3d::<_, Point3D>(filter, "tests/data/expected_filter_sphere.xyz", "sphere");
 }
+
+#[test]
+fn filter_sphere_boundary_test() {
+    let mut filter = FilterSphere::build(Point3D::new(1.0, 2.0, 3.0), Positive::new(2.0).unwrap());
+
+    assert_eq!(3.0, filter.z());
+    assert!(filter.is_allowed(&Point3D::new(1.0, 2.0, 3.0)));
+    assert!(filter.is_allowed(&Point3D::new(3.0, 2.0, 3.0)));
+    assert!(filter.is_allowed(&Point3D::new(1.0, 0.0, 3.0)));
+    assert!(filter.is_allowed(&Point3D::new(1.0, 2.0, 5.0)));
+    assert!(!filter.is_allowed(&Point3D::new(3.001, 2.0, 3.0)));
+    assert!(!filter.is_allowed(&Point3D::new(2.5, 3.5, 3.0)));
+
+    assert_eq!(
+        BoundingBox3D::new(&Point3D::new(-1.0, 0.0, 1.0), &Point3D::new(3.0, 4.0, 5.0)).unwrap(),
+        filter.bounding_box()
+    );
+
+    filter.scale(Positive::new(2.0).unwrap());
+    assert!(filter.is_allowed(&Point3D::new(5.0, 2.0, 3.0)));
+    assert!(!filter.is_allowed(&Point3D::new(5.001, 2.0, 3.0)));
+}
+
+#[test]
+fn filter_corridor_3d_test() {
+    let polyline = vec![
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(10.0, 0.0, 0.0),
+        Point3D::new(10.0, 10.0, 0.0),
+    ];
+    let filter = FilterCorridor3D::new(polyline, Positive::new(1.0).unwrap());
+
+    assert!(filter.is_allowed(&Point3D::new(5.0, 0.5, 0.5)));
+    assert!(filter.is_allowed(&Point3D::new(10.5, 5.0, 0.0)));
+    assert!(filter.is_allowed(&Point3D::new(-0.5, 0.0, 0.5)));
+    assert!(filter.is_allowed(&Point3D::new(10.0, 11.0, 0.0)));
+
+    assert!(!filter.is_allowed(&Point3D::new(5.0, 1.5, 0.0)));
+    assert!(!filter.is_allowed(&Point3D::new(5.0, 5.0, 0.0)));
+    assert!(!filter.is_allowed(&Point3D::new(-1.0, -1.0, 0.0)));
+    assert!(!filter.is_allowed(&Point3D::new(10.0, 5.0, 2.0)));
+
+    let empty = FilterCorridor3D::new(Vec::new(), Positive::new(1.0).unwrap());
+    assert!(!empty.is_allowed(&Point3D::new(0.0, 0.0, 0.0)));
+}
+
+#[test]
+fn filter_box_3d_boundary_test() {
+    let filter = FilterBox3D::build(
+        Point3D::new(1.0, 2.0, 3.0),
+        Positive::new(2.0).unwrap(),
+        Positive::new(4.0).unwrap(),
+        Positive::new(6.0).unwrap(),
+    );
+
+    assert!(filter.is_allowed(&Point3D::new(1.0, 2.0, 3.0)));
+    assert!(filter.is_allowed(&Point3D::new(2.0, 4.0, 6.0)));
+    assert!(filter.is_allowed(&Point3D::new(0.0, 0.0, 0.0)));
+    assert!(!filter.is_allowed(&Point3D::new(2.001, 2.0, 3.0)));
+    assert!(!filter.is_allowed(&Point3D::new(1.0, -0.001, 3.0)));
+    assert!(!filter.is_allowed(&Point3D::new(1.0, 2.0, 6.001)));
+
+    let sphere = Sphere {
+        center: Point3D::new(1.0, 2.0, 3.0),
+        radius: Positive::new(1.0).unwrap(),
+    };
+    let two = Positive::new(2.0).unwrap();
+    assert_eq!(
+        FilterBox3D::build(Point3D::new(1.0, 2.0, 3.0), two, two, two),
+        FilterBox3D::from_bb(&sphere)
+    );
+}
@@ -199,3 +199,28 @@ fn filter_deny_test() {
     let filter = FilterDeny::new();
     test_filter_3d::<_, Point3D>(filter, "tests/data/expected_filter_deny.xyz", "deny");
 }
+
+#[test]
+fn filter_and_negate_hole_2d_test() {
+    let outer = FilterBox2D::new(Box2D {
+        center: Point2D::new(0.0, 0.0),
+        size_x: Positive::new(10.0).unwrap(),
+        size_y: Positive::new(10.0).unwrap(),
+    });
+    let hole = FilterBox2D::new(Box2D {
+        center: Point2D::new(0.0, 0.0),
+        size_x: Positive::new(4.0).unwrap(),
+        size_y: Positive::new(4.0).unwrap(),
+    });
+    let filter = FilterAND::new(outer, FilterNegate::new(hole));
+
+    assert!(filter.is_allowed(&Point2D::new(3.0, 0.0)));
+    assert!(filter.is_allowed(&Point2D::new(-4.0, -4.0)));
+    assert!(filter.is_allowed(&Point2D::new(0.0, 5.0)));
+
+    assert!(!filter.is_allowed(&Point2D::new(0.0, 0.0)));
+    assert!(!filter.is_allowed(&Point2D::new(1.5, -1.5)));
+    assert!(!filter.is_allowed(&Point2D::new(2.0, 0.0)));
+    assert!(!filter.is_allowed(&Point2D::new(6.0, 0.0)));
+    assert!(!filter.is_allowed(&Point2D::new(0.0, -5.5)));
+}
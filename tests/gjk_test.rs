@@ -0,0 +1,53 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+#![deny(warnings)]
+
+use rust_3d::*;
+
+fn cube(x: f64, y: f64, z: f64) -> Vec<Point3D> {
+    let mut result = Vec::new();
+    for dx in 0..2 {
+        for dy in 0..2 {
+            for dz in 0..2 {
+                result.push(Point3D::new(x + dx as f64, y + dy as f64, z + dz as f64));
+            }
+        }
+    }
+    result
+}
+
+#[test]
+fn gjk_test() {
+    let a = cube(0.0, 0.0, 0.0);
+
+    assert!((gjk_distance(&a, &cube(2.0, 0.0, 0.0)) - 1.0).abs() < 1e-9);
+    assert!((gjk_distance(&a, &cube(2.0, 2.0, 0.0)) - 2.0f64.sqrt()).abs() < 1e-9);
+    assert!((gjk_distance(&a, &cube(2.0, 2.0, 2.0)) - 3.0f64.sqrt()).abs() < 1e-9);
+    assert!(!gjk_intersect(&a, &cube(2.0, 2.0, 2.0)));
+
+    assert_eq!(0.0, gjk_distance(&a, &cube(0.5, 0.3, 0.2)));
+    assert!(gjk_intersect(&a, &cube(0.5, 0.3, 0.2)));
+    assert!(gjk_intersect(&a, &a));
+
+    assert!(gjk_distance(&a, &[]).is_infinite());
+}
@@ -0,0 +1,111 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+#![deny(warnings)]
+
+use rust_3d::*;
+
+use std::f64::consts::FRAC_PI_2;
+
+fn assert_close(expected: &Point3D, actual: &Point3D) {
+    assert!(
+        dist_3d(expected, actual) < 1e-9,
+        "{} != {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn matrix4_transform_test() {
+    let p = Point3D::new(1.0, 2.0, 3.0);
+
+    assert_close(&p, &Matrix4::identity().transform(&p));
+    assert_close(
+        &Point3D::new(2.0, 1.0, 4.0),
+        &Matrix4::translation(1.0, -1.0, 1.0).transform(&p),
+    );
+    assert_close(
+        &Point3D::new(2.0, -2.0, 9.0),
+        &Matrix4::scale(2.0, -1.0, 3.0).transform(&p),
+    );
+    assert_close(
+        &Point3D::new(-2.0, 1.0, 3.0),
+        &Matrix4::rotation_axis(&Norm3D::norm_z(), Rad(FRAC_PI_2)).transform(&p),
+    );
+
+    // homogeneous coordinates are divided by w
+    let mut m = Matrix4::identity();
+    m.data[3][3] = 2.0;
+    assert_close(&Point3D::new(0.5, 1.0, 1.5), &m.transform(&p));
+
+    // matches IsMatrix4Transformable for affine matrices
+    let m = Matrix4::translation(1.0, 2.0, 3.0) * Matrix4::rotation(Rad(0.3), Rad(0.2), Rad(0.1));
+    assert_close(&p.transformed(&m), &m.transform(&p));
+}
+
+#[test]
+fn matrix4_multiply_order_test() {
+    let p = Point3D::new(1.0, 0.0, 0.0);
+    let translate = Matrix4::translation(1.0, 0.0, 0.0);
+    let rotate = Matrix4::rotation_axis(&Norm3D::norm_z(), Rad(FRAC_PI_2));
+
+    // rotate first, then translate
+    let m = translate.multiply(&rotate);
+    assert_close(&Point3D::new(1.0, 1.0, 0.0), &m.transform(&p));
+    assert_close(
+        &translate.transform(&rotate.transform(&p)),
+        &m.transform(&p),
+    );
+
+    // translate first, then rotate
+    let m = rotate.multiply(&translate);
+    assert_close(&Point3D::new(0.0, 2.0, 0.0), &m.transform(&p));
+
+    assert_eq!(translate.multiply(&rotate), &translate * &rotate);
+}
+
+#[test]
+fn matrix4_rotation_round_trip_test() {
+    let p = Point3D::new(1.5, -2.0, 0.75);
+    let rotate = Matrix4::rotation_axis(&Norm3D::norm_z(), Rad(FRAC_PI_2));
+
+    let mut current = p.clone();
+    for _ in 0..4 {
+        current = rotate.transform(&current);
+    }
+    assert_close(&p, &current);
+
+    let full = rotate.multiply(&rotate).multiply(&rotate).multiply(&rotate);
+    assert_close(&p, &full.transform(&p));
+
+    let mut pc = PointCloud3D::new();
+    pc.push(p.clone());
+    pc.push(Point3D::new(0.0, 3.0, -1.0));
+    let original = pc.clone();
+    for _ in 0..4 {
+        pc.transform(&rotate);
+    }
+    for (expected, actual) in original.data.iter().zip(pc.data.iter()) {
+        assert_close(expected, actual);
+    }
+}
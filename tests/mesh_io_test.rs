@@ -52,3 +52,1021 @@ fn mesh_io_test() {
     assert!(m.num_faces() == 1152);
     assert!(m.num_vertices() == 576);
 }
+
+#[test]
+fn mesh_io_normals_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    load_ply_mesh(
+        &mut BufReader::new(File::open("tests/data/torus_only_vertex_data.ply").unwrap()),
+        &mut m,
+    )
+    .unwrap();
+    let normals = normals_of_mesh(&m);
+
+    save_ply_ascii_with_normals(
+        &mut File::create("tests/tmp/torus_normals_ascii.ply").unwrap(),
+        &m,
+        Some(&normals),
+    )
+    .unwrap();
+    save_ply_binary_with_normals(
+        &mut File::create("tests/tmp/torus_normals_binary.ply").unwrap(),
+        &m,
+        &Precision::P32,
+        Some(&normals),
+    )
+    .unwrap();
+    save_ply_binary_with_normals(
+        &mut File::create("tests/tmp/torus_normals_binary_64.ply").unwrap(),
+        &m,
+        &Precision::P64,
+        Some(&normals),
+    )
+    .unwrap();
+
+    for (path, max_error) in &[
+        ("tests/tmp/torus_normals_ascii.ply", 1e-6),
+        ("tests/tmp/torus_normals_binary.ply", 1e-6),
+        ("tests/tmp/torus_normals_binary_64.ply", 0.0),
+    ] {
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_ply_mesh(&mut BufReader::new(File::open(path).unwrap()), &mut m).unwrap();
+        assert!(m.num_faces() == 1152);
+        assert!(m.num_vertices() == 576);
+
+        let mut loaded = [Vec::new(), Vec::new(), Vec::new()];
+        for (name, values) in ["nx", "ny", "nz"].iter().zip(loaded.iter_mut()) {
+            let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+            load_ply_with_scalar(
+                &mut BufReader::new(File::open(path).unwrap()),
+                &mut m,
+                name,
+                values,
+            )
+            .unwrap();
+            assert_eq!(normals.len(), values.len());
+        }
+        for (i, normal) in normals.iter().enumerate() {
+            assert!((normal.x() - loaded[0][i]).abs() <= *max_error);
+            assert!((normal.y() - loaded[1][i]).abs() <= *max_error);
+            assert!((normal.z() - loaded[2][i]).abs() <= *max_error);
+        }
+    }
+
+    let mut bytes = Vec::new();
+    save_ply_ascii_with_normals(&mut bytes, &m, None::<&[Norm3D]>).unwrap();
+    let mut without = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    assert!(load_ply_with_scalar(bytes.as_slice(), &mut without, "nx", &mut Vec::new()).is_err());
+
+    assert!(save_ply_ascii_with_normals(&mut Vec::new(), &m, Some(&normals[1..])).is_err());
+}
+
+#[test]
+fn mesh_io_stl_unique_quantized_test() {
+    let stl = "solid noisy
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 0 0
+vertex 0 1 0
+endloop
+endfacet
+facet normal 0 0 1
+outer loop
+vertex 1.0000001 0 0
+vertex 1 1 0
+vertex 0 0.9999999 0
+endloop
+endfacet
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 0.0000001 0 0
+vertex 0 1 0
+endloop
+endfacet
+endsolid noisy
+";
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut normals = Vec::<Point3D>::new();
+    load_stl_mesh_unique(stl.as_bytes(), StlFormat::Ascii, &mut m, &mut normals).unwrap();
+    assert_eq!(7, m.num_vertices());
+    assert_eq!(3, m.num_faces());
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut normals = Vec::<Point3D>::new();
+    load_stl_mesh_unique_quantized(
+        stl.as_bytes(),
+        StlFormat::Ascii,
+        Positive::new(0.001).unwrap(),
+        &mut m,
+        &mut normals,
+    )
+    .unwrap();
+    // the degenerated third face is dropped
+    assert_eq!(4, m.num_vertices());
+    assert_eq!(2, m.num_faces());
+    assert_eq!(2, normals.len());
+}
+
+#[test]
+fn mesh_io_bytes_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    load_ply_mesh(
+        &mut BufReader::new(File::open("tests/data/torus_only_vertex_data.ply").unwrap()),
+        &mut m,
+    )
+    .unwrap();
+
+    for format in [
+        PlyFormat::Ascii,
+        PlyFormat::LittleEndian,
+        PlyFormat::BigEndian,
+    ] {
+        let bytes = mesh_to_ply_bytes(&m, format).unwrap();
+        let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_ply_mesh(bytes.as_slice(), &mut loaded).unwrap();
+        assert_eq!(m.num_vertices(), loaded.num_vertices());
+        assert_eq!(m.num_faces(), loaded.num_faces());
+    }
+
+    let bytes = mesh_to_stl_ascii_bytes(&m).unwrap();
+    let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut normals = Vec::<Point3D>::new();
+    load_stl_mesh_unique(
+        bytes.as_slice(),
+        StlFormat::Ascii,
+        &mut loaded,
+        &mut normals,
+    )
+    .unwrap();
+    assert_eq!(m.num_vertices(), loaded.num_vertices());
+    assert_eq!(m.num_faces(), loaded.num_faces());
+}
+
+#[test]
+fn mesh_io_stl_header_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    m.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 1.0, 0.0),
+    );
+
+    let mut buffer = Vec::new();
+    save_stl_ascii(&mut buffer, &m).unwrap();
+    assert!(String::from_utf8(buffer)
+        .unwrap()
+        .starts_with("solid STL generated by rust-3d\n"));
+
+    let mut buffer = Vec::new();
+    save_stl_ascii_with_options(&mut buffer, &m, &StlSaveOptions::new("part_42".to_string()))
+        .unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(text.starts_with("solid part_42\n"));
+    assert!(text.ends_with("endsolid part_42\n"));
+
+    let mut buffer = Vec::new();
+    assert!(save_stl_ascii_with_options(
+        &mut buffer,
+        &m,
+        &StlSaveOptions::new("two\nlines".to_string())
+    )
+    .is_err());
+}
+
+#[test]
+fn mesh_io_stl_ascii_whitespace_test() {
+    let stl = "solid messy\r\n\tfacet normal 0 0 1  \r\n\t\touter \t loop\r\n\t\t\tvertex  0\t0 0\r\n\t\t\tvertex 1 0  0 \r\n\t\t\tvertex\t0 1 0\r\n\t\tendloop \r\n\tendfacet\t\r\n  facet normal 0 0 1\r\n    outer   loop\r\n      vertex 1 0 0\r\n      vertex 1 1 0\r\n      vertex 0 1 0\r\n    endloop\r\n  endfacet\r\nendsolid messy";
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut normals = Vec::<Point3D>::new();
+    load_stl_mesh_unique(stl.as_bytes(), StlFormat::Ascii, &mut m, &mut normals).unwrap();
+    assert_eq!(4, m.num_vertices());
+    assert_eq!(2, m.num_faces());
+    assert_eq!(2, normals.len());
+}
+
+#[test]
+fn mesh_io_ply_writer_test() {
+    let writer = PlyWriter::new(PlyFormat::Ascii)
+        .positions(PlyType::Double)
+        .vertex_property("intensity", PlyType::Float)
+        .vertex_property("segment", PlyType::UShort)
+        .with_faces();
+
+    let mut ascii = Vec::new();
+    {
+        let mut elements = writer.clone().begin(&mut ascii, 3, 1).unwrap();
+        elements.push_vertex(&[0.0, 0.0, 0.0, 0.5, 1.0]).unwrap();
+        elements.push_vertex(&[1.0, 0.0, 0.0, 0.25, 2.0]).unwrap();
+        assert!(elements.push_face(&[0, 1, 2]).is_err());
+        assert!(elements.push_vertex(&[0.0, 1.0, 0.0]).is_err());
+        elements.push_vertex(&[0.0, 1.0, 0.0, 0.125, 3.0]).unwrap();
+        match elements.push_face(&[0; 256]) {
+            Err(PlyError::FaceTooLarge(256)) => (),
+            _ => panic!("expected a too large face to be rejected"),
+        }
+        elements.push_face(&[0, 1, 2]).unwrap();
+        elements.finish().unwrap();
+    }
+    assert_eq!(
+        "ply
+format ascii 1.0
+comment Created by rust-3d
+element vertex 3
+property double x
+property double y
+property double z
+property float intensity
+property ushort segment
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0.5 1
+1 0 0 0.25 2
+0 1 0 0.125 3
+3 0 1 2
+",
+        String::from_utf8(ascii.clone()).unwrap()
+    );
+
+    for format in [PlyFormat::BigEndian, PlyFormat::LittleEndian] {
+        let mut binary = Vec::new();
+        let mut elements = PlyWriter::new(format)
+            .vertex_property("intensity", PlyType::UChar)
+            .positions(PlyType::Float)
+            .with_faces()
+            .begin(&mut binary, 3, 1)
+            .unwrap();
+        elements.push_vertex(&[7.0, 0.0, 0.0, 0.0]).unwrap();
+        elements.push_vertex(&[7.0, 1.0, 0.0, 0.0]).unwrap();
+        elements.push_vertex(&[7.0, 0.0, 1.0, 0.0]).unwrap();
+        elements.push_face(&[0, 1, 2]).unwrap();
+        elements.finish().unwrap();
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_ply_mesh(&mut binary.as_slice(), &mut m).unwrap();
+        assert_eq!(3, m.num_vertices());
+        assert_eq!(1, m.num_faces());
+        assert_eq!(Point3D::new(0.0, 1.0, 0.0), m.vertex(VId(2)).unwrap());
+    }
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    load_ply_mesh(&mut ascii.as_slice(), &mut m).unwrap();
+    assert_eq!(Point3D::new(1.0, 0.0, 0.0), m.vertex(VId(1)).unwrap());
+
+    let mut unfinished = Vec::new();
+    let elements = writer.clone().begin(&mut unfinished, 3, 1).unwrap();
+    assert!(elements.finish().is_err());
+
+    assert!(PlyWriter::new(PlyFormat::Ascii)
+        .vertex_property("two words", PlyType::Int)
+        .begin(&mut Vec::new(), 0, 0)
+        .is_err());
+}
+
+#[test]
+fn mesh_io_ply_scalar_test() {
+    for format in [
+        PlyFormat::Ascii,
+        PlyFormat::BigEndian,
+        PlyFormat::LittleEndian,
+    ] {
+        let mut bytes = Vec::new();
+        let mut elements = PlyWriter::new(format)
+            .vertex_property("segment", PlyType::UChar)
+            .vertex_property("y", PlyType::Float)
+            .vertex_property("x", PlyType::Float)
+            .vertex_property("intensity", PlyType::Double)
+            .vertex_property("z", PlyType::Float)
+            .with_faces()
+            .begin(&mut bytes, 3, 1)
+            .unwrap();
+        elements.push_vertex(&[1.0, 0.0, 0.0, 0.5, 0.0]).unwrap();
+        elements.push_vertex(&[2.0, 0.0, 1.0, 0.25, 0.0]).unwrap();
+        elements.push_vertex(&[3.0, 1.0, 0.0, 0.125, 2.0]).unwrap();
+        elements.push_face(&[0, 1, 2]).unwrap();
+        elements.finish().unwrap();
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        let mut intensities = Vec::new();
+        load_ply_with_scalar(bytes.as_slice(), &mut m, "intensity", &mut intensities).unwrap();
+        assert_eq!(vec![0.5, 0.25, 0.125], intensities);
+        assert_eq!(1, m.num_faces());
+        assert_eq!(Point3D::new(0.0, 1.0, 2.0), m.vertex(VId(2)).unwrap());
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        let mut segments = Vec::new();
+        load_ply_with_scalar(bytes.as_slice(), &mut m, "segment", &mut segments).unwrap();
+        assert_eq!(vec![1.0, 2.0, 3.0], segments);
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        assert!(
+            load_ply_with_scalar(bytes.as_slice(), &mut m, "missing", &mut Vec::new()).is_err()
+        );
+    }
+}
+
+#[test]
+fn mesh_io_ply_binary_misaligned_test() {
+    for format in [PlyFormat::BigEndian, PlyFormat::LittleEndian] {
+        for with_faces in [false, true] {
+            let mut writer = PlyWriter::new(format)
+                .positions(PlyType::Float)
+                .vertex_property("intensity", PlyType::Float);
+            if with_faces {
+                writer = writer.with_faces();
+            }
+
+            let mut bytes = Vec::new();
+            let n_faces = if with_faces { 1 } else { 0 };
+            let mut elements = writer.begin(&mut bytes, 3, n_faces).unwrap();
+            elements.push_vertex(&[0.0, 0.0, 0.0, 0.5]).unwrap();
+            elements.push_vertex(&[1.0, 0.0, 0.0, 0.5]).unwrap();
+            elements.push_vertex(&[0.0, 1.0, 0.0, 0.5]).unwrap();
+            if with_faces {
+                elements.push_face(&[0, 1, 2]).unwrap();
+            }
+            elements.finish().unwrap();
+
+            let mut pc = PointCloud3D::<Point3D>::new();
+            load_ply_points(bytes.as_slice(), &mut pc).unwrap();
+            assert_eq!(3, pc.len());
+
+            // a header listing fewer properties than written misaligns all vertices
+            let declaration = b"property float intensity\n";
+            let start = bytes
+                .windows(declaration.len())
+                .position(|w| w == declaration)
+                .unwrap();
+            bytes.drain(start..start + declaration.len());
+
+            let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+            let mut pc = PointCloud3D::<Point3D>::new();
+            assert!(load_ply_either(bytes.as_slice(), &mut m, &mut pc).is_err());
+
+            let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+            let mut xs = Vec::new();
+            assert!(load_ply_with_scalar(bytes.as_slice(), &mut m, "x", &mut xs).is_err());
+
+            if !with_faces {
+                let mut pc = PointCloud3D::<Point3D>::new();
+                assert!(load_ply_points(bytes.as_slice(), &mut pc).is_err());
+            }
+        }
+    }
+}
+
+#[test]
+fn mesh_io_error_conversion_test() {
+    fn load(bytes: &[u8]) -> Result<usize> {
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_ply_mesh(bytes, &mut m)?;
+        Ok(m.num_vertices())
+    }
+
+    assert_eq!(
+        3,
+        load(b"ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\nelement face 0\nproperty list uchar uint vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n")
+            .unwrap()
+    );
+
+    match load(b"nope\n") {
+        Err(ErrorKind::PlyError(PlyError::LoadStartNotFound)) => (),
+        _ => panic!("expected the .ply error to be converted"),
+    }
+
+    let with_line = Err::<(), _>(PlyError::InvalidVertex).line(7, b"1 2");
+    assert!(matches!(
+        with_line.unwrap_err().into_inner(),
+        PlyError::InvalidVertex
+    ));
+}
+
+#[test]
+fn mesh_io_error_pipeline_test() {
+    fn pipeline(xyz: &[u8], stl: &[u8]) -> Result<(usize, usize)> {
+        let mut pc = PointCloud3D::<Point3D>::default();
+        load_xyz(xyz, &mut pc)?;
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        let mut normals = Vec::<Point3D>::new();
+        load_stl_mesh_duped(stl, StlFormat::Ascii, &mut m, &mut normals)?;
+
+        Ok((pc.len(), m.num_faces()))
+    }
+
+    let stl = b"solid s\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendfacet\nendsolid s\n";
+
+    assert_eq!((2, 1), pipeline(b"0 0 0\n1 1 1\n", stl).unwrap());
+
+    match pipeline(b"0 0 0\n1 a 1\n", stl) {
+        Err(e @ ErrorKind::XyzError(XyzError::Vertex)) => {
+            assert!(e.to_string().contains("vertex"))
+        }
+        _ => panic!("expected the .xyz error to be converted"),
+    }
+
+    match pipeline(b"0 0 0\n", &stl[..60]) {
+        Err(ErrorKind::StlError(_)) => (),
+        _ => panic!("expected the .stl error to be converted"),
+    }
+}
+
+#[test]
+fn mesh_io_stl_binary_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    m.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 1.0, 0.0),
+    );
+    m.add_face(
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(1.0, 1.0, 0.5),
+        Point3D::new(0.0, 1.0, 0.0),
+    );
+
+    let bytes = mesh_to_stl_binary_bytes(&m).unwrap();
+    assert_eq!(80 + 4 + 2 * 50, bytes.len());
+    assert!(bytes.starts_with(b"STL generated by rust-3d\0"));
+    assert_eq!(2u32.to_le_bytes(), bytes[80..84]);
+    // normal of the first face
+    assert_eq!(1.0f32.to_le_bytes(), bytes[92..96]);
+
+    let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut normals = Vec::<Point3D>::new();
+    load_stl_mesh_duped(
+        &mut bytes.as_slice(),
+        StlFormat::Binary,
+        &mut loaded,
+        &mut normals,
+    )
+    .unwrap();
+    assert_eq!(m.num_faces(), loaded.num_faces());
+    for i in 0..m.num_faces() {
+        assert_eq!(
+            m.face_vertices(FId(i)).unwrap(),
+            loaded.face_vertices(FId(i)).unwrap()
+        );
+    }
+    assert_eq!(0.0, normals[0].x());
+    assert_eq!(1.0, normals[0].z());
+
+    // degenerate faces are written with a zero normal
+    let mut degenerate = m.clone();
+    degenerate.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 1.0, 1.0),
+        Point3D::new(2.0, 2.0, 2.0),
+    );
+    let degenerate_bytes = mesh_to_stl_binary_bytes(&degenerate).unwrap();
+    assert!(degenerate_bytes[184..196].iter().all(|x| *x == 0));
+    let text = String::from_utf8(mesh_to_stl_ascii_bytes(&degenerate).unwrap()).unwrap();
+    assert!(text.contains("facet normal 0e0 0e0 0e0\n"));
+
+    let mut buffer = Vec::new();
+    assert!(
+        save_stl_binary_with_options(&mut buffer, &m, &StlSaveOptions::new("x".repeat(81)))
+            .is_err()
+    );
+    let mut buffer = Vec::new();
+    save_stl_binary_with_options(&mut buffer, &m, &StlSaveOptions::new("x".repeat(80))).unwrap();
+    assert_eq!(bytes.len(), buffer.len());
+}
+
+#[test]
+fn mesh_io_stl_normal_handling_test() {
+    let stl = "solid normals\n\
+               facet normal 0 0 0\n\
+               outer loop\n\
+               vertex 0 0 0\n\
+               vertex 2 0 0\n\
+               vertex 0 2 0\n\
+               endloop\n\
+               endfacet\n\
+               facet normal 1 0 0\n\
+               outer loop\n\
+               vertex 0 0 0\n\
+               vertex 0 0 1\n\
+               vertex 1 0 0\n\
+               endloop\n\
+               endfacet\n\
+               facet normal 0 0 0\n\
+               outer loop\n\
+               vertex 0 0 0\n\
+               vertex 1 1 1\n\
+               vertex 2 2 2\n\
+               endloop\n\
+               endfacet\n\
+               endsolid normals\n";
+
+    let load = |handling| {
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        let mut normals = Vec::<Point3D>::new();
+        load_stl_mesh_duped_with_normal_handling(
+            stl.as_bytes(),
+            StlFormat::Ascii,
+            handling,
+            &mut m,
+            &mut normals,
+        )
+        .unwrap();
+        assert_eq!(3, m.num_faces());
+        normals
+    };
+
+    let zero = Point3D::new(0.0, 0.0, 0.0);
+    let (z, x, y) = (
+        Point3D::new(0.0, 0.0, 1.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 1.0, 0.0),
+    );
+
+    assert_eq!(
+        vec![zero.clone(), x.clone(), zero.clone()],
+        load(NormalHandling::AsStored)
+    );
+    assert_eq!(
+        vec![z.clone(), y.clone(), zero.clone()],
+        load(NormalHandling::Recompute)
+    );
+    assert_eq!(vec![z, x, zero], load(NormalHandling::RecomputeIfZero));
+}
+
+#[test]
+fn mesh_io_ply_save_formats_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    m.add_face(
+        Point3D::new(0.1, 1.0 / 3.0, std::f64::consts::PI),
+        Point3D::new(1.0e-17, -2.5, 123456789.12345679),
+        Point3D::new(-0.7, 0.3, f64::MIN_POSITIVE),
+    );
+    m.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 1.0, 0.0),
+    );
+
+    for format in [
+        PlyFormat::Ascii,
+        PlyFormat::LittleEndian,
+        PlyFormat::BigEndian,
+    ] {
+        let mut bytes = Vec::new();
+        save_ply(&mut bytes, &m, format).unwrap();
+
+        let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_ply_mesh(bytes.as_slice(), &mut loaded).unwrap();
+        assert_eq!(m.num_faces(), loaded.num_faces());
+        for i in 0..m.num_vertices() {
+            // exact, also for ASCII
+            assert_eq!(m.vertex(VId(i)).unwrap(), loaded.vertex(VId(i)).unwrap());
+        }
+        for i in 0..m.num_faces() {
+            assert_eq!(
+                m.face_vertex_ids(FId(i)).unwrap(),
+                loaded.face_vertex_ids(FId(i)).unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn mesh_io_ply_colors_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    m.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 1.0, 0.0),
+    );
+    let colors = vec![
+        Rgb { r: 255, g: 0, b: 7 },
+        Rgb { r: 0, g: 128, b: 0 },
+        Rgb { r: 1, g: 2, b: 3 },
+    ];
+
+    let mut ascii = Vec::new();
+    save_ply_ascii_colored(&mut ascii, &m, &colors).unwrap();
+    let mut binary = Vec::new();
+    save_ply_binary_colored(&mut binary, &m, &Precision::P64, &colors).unwrap();
+
+    for bytes in [ascii, binary] {
+        let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        let mut loaded_colors = Vec::new();
+        load_ply_with_colors(bytes.as_slice(), &mut loaded, &mut loaded_colors).unwrap();
+        assert_eq!(colors, loaded_colors);
+        assert_eq!(1, loaded.num_faces());
+    }
+
+    // interleaved properties with alpha and float channels
+    for format in [
+        PlyFormat::Ascii,
+        PlyFormat::LittleEndian,
+        PlyFormat::BigEndian,
+    ] {
+        let mut bytes = Vec::new();
+        let mut elements = PlyWriter::new(format)
+            .vertex_property("blue", PlyType::UChar)
+            .vertex_property("x", PlyType::Float)
+            .vertex_property("alpha", PlyType::UChar)
+            .vertex_property("y", PlyType::Double)
+            .vertex_property("green", PlyType::Float)
+            .vertex_property("z", PlyType::Float)
+            .vertex_property("red", PlyType::UChar)
+            .begin(&mut bytes, 2, 0)
+            .unwrap();
+        elements
+            .push_vertex(&[3.0, 1.0, 100.0, 2.0, 0.5, 3.0, 200.0])
+            .unwrap();
+        elements
+            .push_vertex(&[0.0, 4.0, 255.0, 5.0, 1.0, 6.0, 1.0])
+            .unwrap();
+        elements.finish().unwrap();
+
+        let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        let mut loaded_colors = Vec::new();
+        load_ply_with_colors(bytes.as_slice(), &mut loaded, &mut loaded_colors).unwrap();
+        assert_eq!(
+            vec![
+                Rgb {
+                    r: 200,
+                    g: 128,
+                    b: 3
+                },
+                Rgb { r: 1, g: 255, b: 0 }
+            ],
+            loaded_colors
+        );
+        assert_eq!(Point3D::new(4.0, 5.0, 6.0), loaded.vertex(VId(1)).unwrap());
+    }
+
+    // without colors
+    let mut bytes = Vec::new();
+    save_ply_ascii(&mut bytes, &m).unwrap();
+    let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut loaded_colors = Vec::new();
+    load_ply_with_colors(bytes.as_slice(), &mut loaded, &mut loaded_colors).unwrap();
+    assert!(loaded_colors.is_empty());
+    assert_eq!(3, loaded.num_vertices());
+}
+
+#[test]
+fn mesh_io_ply_quads_test() {
+    let corners = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+        [1.0, 1.0, 1.0],
+        [0.0, 1.0, 1.0],
+    ];
+    let quads = [
+        [0, 3, 2, 1],
+        [4, 5, 6, 7],
+        [0, 1, 5, 4],
+        [1, 2, 6, 5],
+        [2, 3, 7, 6],
+        [3, 0, 4, 7],
+    ];
+
+    for format in [
+        PlyFormat::Ascii,
+        PlyFormat::LittleEndian,
+        PlyFormat::BigEndian,
+    ] {
+        let mut bytes = Vec::new();
+        let mut elements = PlyWriter::new(format)
+            .positions(PlyType::Float)
+            .with_faces()
+            .begin(&mut bytes, corners.len(), quads.len())
+            .unwrap();
+        for corner in corners.iter() {
+            elements.push_vertex(corner).unwrap();
+        }
+        for quad in quads.iter() {
+            elements.push_face(quad).unwrap();
+        }
+        elements.finish().unwrap();
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_ply_mesh(bytes.as_slice(), &mut m).unwrap();
+        assert_eq!(8, m.num_vertices());
+        assert_eq!(12, m.num_faces());
+        assert_eq!(
+            Face3::new(VId(0), VId(3), VId(2)),
+            m.face_vertex_ids(FId(0)).unwrap()
+        );
+        assert_eq!(
+            Face3::new(VId(0), VId(2), VId(1)),
+            m.face_vertex_ids(FId(1)).unwrap()
+        );
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        let mut intensities = Vec::new();
+        assert!(load_ply_with_scalar(bytes.as_slice(), &mut m, "x", &mut intensities).is_ok());
+        assert_eq!(12, m.num_faces());
+
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        for fr in PlyMeshIterator::<Point3D, _>::new(bytes.as_slice()).unwrap() {
+            if let FaceDataReserve::Face([a, b, c]) = fr.unwrap() {
+                m.add_vertex(Point3D::new(a as f64, b as f64, c as f64));
+            }
+        }
+        assert_eq!(12, m.num_vertices());
+    }
+
+    // faces with fewer than 3 vertices are still invalid
+    let text = "ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n0 0 0\n1 0 0\n2 0 1\n";
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    assert!(load_ply_mesh(text.as_bytes(), &mut m).is_err());
+}
+
+#[test]
+fn mesh_io_obj_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    load_obj_mesh(
+        BufReader::new(File::open("tests/data/cube.obj").unwrap()),
+        &mut m,
+    )
+    .unwrap();
+
+    assert_eq!(8, m.num_vertices());
+    assert_eq!(12, m.num_faces());
+    assert_eq!(Point3D::new(1.0, 1.0, 1.0), m.vertex(VId(6)).unwrap());
+    // fan triangulated 'f 1 4 3 2'
+    assert_eq!(
+        Face3::new(VId(0), VId(3), VId(2)),
+        m.face_vertex_ids(FId(0)).unwrap()
+    );
+    assert_eq!(
+        Face3::new(VId(0), VId(2), VId(1)),
+        m.face_vertex_ids(FId(1)).unwrap()
+    );
+    // 'f 2/1/4 3/2/4 7/3/4 6/4/4'
+    assert_eq!(
+        Face3::new(VId(1), VId(6), VId(5)),
+        m.face_vertex_ids(FId(7)).unwrap()
+    );
+    // relative 'f -5 -1 -2'
+    assert_eq!(
+        Face3::new(VId(3), VId(7), VId(6)),
+        m.face_vertex_ids(FId(8)).unwrap()
+    );
+
+    // the cube is closed, so all faces add up to its surface
+    let area: f64 = (0..m.num_faces())
+        .map(|i| {
+            let [a, b, c] = m.face_vertices(FId(i)).unwrap();
+            0.5 * *cross(&conn(&a, &b), &conn(&a, &c)).abs()
+        })
+        .sum();
+    assert!((6.0 - area).abs() < 1e-9);
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let result = load_obj_mesh(
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nf 1 0 3\n".as_bytes(),
+        &mut m,
+    );
+    match result {
+        Err(WithLineInfo::Line(5, _, ObjError::InvalidMeshIndices)) => (),
+        _ => panic!("index 0 should be rejected"),
+    }
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let result = load_obj_mesh("v 0 0 0\nv 1 0 0\nf 1 -3 2\n".as_bytes(), &mut m);
+    match result {
+        Err(WithLineInfo::Line(3, _, ObjError::InvalidMeshIndices)) => (),
+        _ => panic!("relative index before the first vertex should be rejected"),
+    }
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let result = load_obj_mesh("v 0 0 0\nv 1 0 0\nf 1 2\n".as_bytes(), &mut m);
+    match result {
+        Err(WithLineInfo::Line(3, _, ObjError::Face)) => (),
+        _ => panic!("faces need at least 3 vertices"),
+    }
+}
+
+#[test]
+fn mesh_io_obj_save_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    m.add_vertex(Point3D::new(0.0, 0.0, 0.0));
+    m.add_vertex(Point3D::new(1.0, 0.0, 0.0));
+    m.add_vertex(Point3D::new(0.0, 1.0, 0.0));
+    m.add_vertex(Point3D::new(0.0, 0.0, 1.5));
+    for [a, b, c] in [[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]] {
+        m.try_add_connection(VId(a), VId(b), VId(c)).unwrap();
+    }
+
+    let check = |bytes: &[u8]| {
+        assert_eq!(Some(&b'\n'), bytes.last());
+        let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_obj_mesh(bytes, &mut loaded).unwrap();
+        assert_eq!(m.num_vertices(), loaded.num_vertices());
+        assert_eq!(m.num_faces(), loaded.num_faces());
+        for i in 0..m.num_vertices() {
+            assert_eq!(m.vertex(VId(i)).unwrap(), loaded.vertex(VId(i)).unwrap());
+        }
+        for i in 0..m.num_faces() {
+            assert_eq!(
+                m.face_vertex_ids(FId(i)).unwrap(),
+                loaded.face_vertex_ids(FId(i)).unwrap()
+            );
+        }
+    };
+
+    let mut bytes = Vec::new();
+    save_obj_mesh(&mut bytes, &m).unwrap();
+    check(&bytes);
+    let text = String::from_utf8(bytes).unwrap();
+    assert!(text.starts_with("v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1.5\nf 1 3 2\n"));
+    assert_eq!(8, text.lines().count());
+
+    let vertex_normals = normals_of_mesh(&m);
+    let mut bytes = Vec::new();
+    save_obj_mesh_with_vertex_normals(&mut bytes, &m, &vertex_normals).unwrap();
+    check(&bytes);
+    let text = String::from_utf8(bytes).unwrap();
+    assert_eq!(4, text.lines().filter(|l| l.starts_with("vn ")).count());
+    assert!(text.contains("\nf 1//1 3//3 2//2\n"));
+
+    let face_normals: Vec<Norm3D> = recompute_face_normals(&m)
+        .into_iter()
+        .map(|n| n.unwrap())
+        .collect();
+    let mut bytes = Vec::new();
+    save_obj_mesh_with_face_normals(&mut bytes, &m, &face_normals).unwrap();
+    check(&bytes);
+    let text = String::from_utf8(bytes).unwrap();
+    assert!(text.contains("\nvn 0 0 -1\n"));
+    assert!(text.ends_with("\nf 3//4 1//4 4//4\n"));
+
+    let mut bytes = Vec::new();
+    assert!(matches!(
+        save_obj_mesh_with_face_normals(&mut bytes, &m, &vertex_normals[..3]),
+        Err(ObjError::NormalArrayIncorrectLength)
+    ));
+}
+
+#[test]
+fn mesh_io_off_test() {
+    let cube = "OFF
+# unit cube with quads
+
+8 6 12
+0 0 0
+1 0 0
+1 1 0
+0 1 0
+0 0 1
+1 0 1
+1 1 1
+0 1 1
+# faces
+4 0 3 2 1
+4 4 5 6 7
+4 0 1 5 4
+4 1 2 6 5
+3 2 3 7
+3 2 7 6
+4 3 0 4 7
+";
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    load_off_mesh(cube.as_bytes(), &mut m).unwrap();
+    assert_eq!(8, m.num_vertices());
+    assert_eq!(12, m.num_faces());
+    assert_eq!(
+        Face3::new(VId(0), VId(3), VId(2)),
+        m.face_vertex_ids(FId(0)).unwrap()
+    );
+    assert_eq!(
+        Face3::new(VId(0), VId(2), VId(1)),
+        m.face_vertex_ids(FId(1)).unwrap()
+    );
+    assert_eq!(
+        Face3::new(VId(2), VId(7), VId(6)),
+        m.face_vertex_ids(FId(9)).unwrap()
+    );
+
+    let mut bytes = Vec::new();
+    save_off_mesh(&mut bytes, &m).unwrap();
+    let text = String::from_utf8(bytes.clone()).unwrap();
+    assert!(text.starts_with("OFF\n8 12 0\n0 0 0\n1 0 0\n"));
+    assert!(text.ends_with("3 3 4 7\n"));
+
+    let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    load_off_mesh(bytes.as_slice(), &mut loaded).unwrap();
+    assert_eq!(8, loaded.num_vertices());
+    assert_eq!(12, loaded.num_faces());
+    for i in 0..m.num_vertices() {
+        assert_eq!(m.vertex(VId(i)).unwrap(), loaded.vertex(VId(i)).unwrap());
+    }
+    for i in 0..m.num_faces() {
+        assert_eq!(
+            m.face_vertex_ids(FId(i)).unwrap(),
+            loaded.face_vertex_ids(FId(i)).unwrap()
+        );
+    }
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let result = load_off_mesh(
+        "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n2 0 1\n".as_bytes(),
+        &mut m,
+    );
+    match result {
+        Err(WithLineInfo::Line(6, _, OffError::FaceVertexCount)) => (),
+        _ => panic!("faces need at least 3 vertices"),
+    }
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let result = load_off_mesh(
+        "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n4 0 1 2\n".as_bytes(),
+        &mut m,
+    );
+    match result {
+        Err(WithLineInfo::Line(6, _, OffError::Face)) => (),
+        _ => panic!("faces need as many indices as specified"),
+    }
+}
+
+#[test]
+fn mesh_io_error_trait_test() {
+    use std::error::Error;
+
+    fn load_invalid() -> std::result::Result<(), Box<dyn Error>> {
+        let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+        load_ply_mesh(&mut "not a ply file\n".as_bytes(), &mut m)?;
+        Ok(())
+    }
+
+    let err = load_invalid().unwrap_err();
+    assert!(!err.to_string().is_empty());
+    assert!(err.source().unwrap().is::<PlyError>());
+
+    let err: Box<dyn Error> = Box::new(ErrorKind::from(PlyError::LoadStartNotFound));
+    assert!(err.source().unwrap().is::<PlyError>());
+    assert!(ErrorKind::IndexOutOfBounds.source().is_none());
+    assert!(!ErrorKind::IndexOutOfBounds.to_string().is_empty());
+}
+
+#[test]
+fn mesh_io_stl_progress_test() {
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    for i in 0..30 {
+        let x = i as f64;
+        m.add_face(
+            Point3D::new(x, 0.0, 0.0),
+            Point3D::new(x + 1.0, 0.0, 0.0),
+            Point3D::new(x, 1.0, 0.0),
+        );
+    }
+
+    // cancelling after 10 faces keeps the faces loaded so far
+    let bytes = mesh_to_stl_binary_bytes(&m).unwrap();
+    let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut normals = Vec::<Point3D>::new();
+    let mut calls = Vec::new();
+    let result = load_stl_mesh_duped_with_progress(
+        bytes.as_slice(),
+        StlFormat::Binary,
+        &mut loaded,
+        &mut normals,
+        1,
+        &mut |done, total| {
+            calls.push((done, total));
+            done < 10
+        },
+    );
+    match result {
+        Err(WithLineInfo::None(StlError::Cancelled)) => (),
+        _ => panic!("expected the load to be cancelled"),
+    }
+    assert_eq!(10, loaded.num_faces());
+    assert_eq!(10, calls.len());
+    assert_eq!((10, 30), calls[9]);
+
+    // the total of ascii files is unknown
+    let bytes = mesh_to_stl_ascii_bytes(&m).unwrap();
+    let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let mut calls = Vec::new();
+    load_stl_mesh_duped_with_progress(
+        bytes.as_slice(),
+        StlFormat::Ascii,
+        &mut loaded,
+        &mut normals,
+        7,
+        &mut |done, total| {
+            calls.push((done, total));
+            true
+        },
+    )
+    .unwrap();
+    assert_eq!(30, loaded.num_faces());
+    assert_eq!(vec![(7, 0), (14, 0), (21, 0), (28, 0)], calls);
+}
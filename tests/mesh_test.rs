@@ -22,7 +22,10 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 #![deny(warnings)]
 
-use rust_3d::*;
+use rust_3d::{
+    io::{load_ply_with_scalar, save_ply_ascii_with_normals},
+    *,
+};
 
 #[test]
 fn mesh_test() {
@@ -82,3 +85,663 @@ fn mesh_test() {
         Some([p1, p2, p3]) => assert!(p1.x() == 1.0 && p2.x() == 2.0 && p3.x() == 3.0),
     };
 }
+
+fn unit_cube() -> Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for x in 0..2 {
+        for y in 0..2 {
+            for z in 0..2 {
+                pc.push(Point3D::new(x as f64, y as f64, z as f64));
+            }
+        }
+    }
+    convex_hull_3d(&pc).unwrap()
+}
+
+#[test]
+fn mesh_sdf_test() {
+    let cube = unit_cube();
+    let tree = mesh_face_tree(&cube).unwrap();
+    let sdf = |x, y, z| mesh_sdf(&cube, &tree, &Point3D::new(x, y, z)).unwrap();
+
+    assert!((sdf(0.5, 0.5, 0.5) + 0.5).abs() < 1e-9);
+    assert!((sdf(0.5, 0.5, 0.9) + 0.1).abs() < 1e-9);
+    assert!((sdf(3.0, 0.5, 0.5) - 2.0).abs() < 1e-9);
+    assert!((sdf(2.0, 2.0, 0.5) - 2.0f64.sqrt()).abs() < 1e-9);
+
+    let empty = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let empty_tree = mesh_face_tree(&empty).unwrap();
+    assert!(mesh_sdf(&empty, &empty_tree, &Point3D::new(0.0, 0.0, 0.0)).is_err());
+
+    // the tree queries match the ones checking every face
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..200 {
+        let t = i as f64;
+        pc.push(Point3D::new(
+            (0.37 * t).sin() * (0.11 * t).cos(),
+            (0.37 * t).sin() * (0.11 * t).sin(),
+            (0.37 * t).cos(),
+        ));
+    }
+    let sphere: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> = convex_hull_3d(&pc).unwrap();
+    let tree = mesh_face_tree(&sphere).unwrap();
+    for i in 0..300 {
+        let t = i as f64;
+        let p = Point3D::new(
+            1.5 * (0.7 * t).sin(),
+            1.5 * (1.3 * t).cos(),
+            1.5 * (0.3 * t).sin(),
+        );
+        let expected = closest_point_on_mesh(&sphere, &p).unwrap();
+        let (fid, closest) = closest_point_on_mesh_tree(&sphere, &tree, &p).unwrap();
+        assert!((dist_3d(&p, &expected) - dist_3d(&p, &closest)).abs() < 1e-9);
+        let [a, b, c] = sphere.face_vertices(fid).unwrap();
+        assert!(dist_3d(&closest, &closest_point_on_triangle(&p, &a, &b, &c)) < 1e-9);
+        assert_eq!(
+            mesh_contains_point(&sphere, &p),
+            mesh_contains_point_tree(&sphere, &tree, &p)
+        );
+    }
+
+    let bounds = BoundingBox3D::new(
+        &Point3D::new(-1.0, -1.0, -1.0),
+        &Point3D::new(2.0, 2.0, 2.0),
+    )
+    .unwrap();
+    let grid = sample_sdf_grid(&cube, &bounds, [4, 4, 4]).unwrap();
+    assert_eq!(64, grid.len());
+    assert!((grid[0] - 3.0f64.sqrt()).abs() < 1e-9);
+    assert!(grid[1 + 4 + 16] <= 0.0);
+    assert!(sample_sdf_grid(&cube, &bounds, [1, 4, 4]).is_err());
+}
+
+#[test]
+fn point_cloud_reconstruct_test() {
+    let mut grid = PointCloud3D::<Point3D>::new();
+    for x in 0..20 {
+        for y in 0..20 {
+            grid.push(Point3D::new(x as f64, y as f64, 0.0));
+        }
+    }
+    let mesh: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> = grid
+        .reconstruct(ReconstructParams::new(Positive::new(1.0).unwrap()))
+        .unwrap();
+    assert_eq!(400, mesh.num_vertices());
+    assert_eq!(2 * 19 * 19, mesh.num_faces());
+
+    // Fibonacci sphere, which should result in a closed mesh
+    let n = 800;
+    let mut sphere = PointCloud3D::<Point3D>::new();
+    let golden_angle = std::f64::consts::PI * (3.0 - 5.0f64.sqrt());
+    for i in 0..n {
+        let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+        let r = (1.0 - z * z).sqrt();
+        let phi = golden_angle * i as f64;
+        sphere.push(Point3D::new(
+            10.0 * r * phi.cos(),
+            10.0 * r * phi.sin(),
+            10.0 * z,
+        ));
+    }
+    let mesh: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> = sphere
+        .reconstruct(ReconstructParams::new(Positive::new(1.5).unwrap()))
+        .unwrap();
+    assert_eq!(n, mesh.num_vertices());
+    assert_eq!(2 * n - 4, mesh.num_faces());
+
+    assert!(
+        ball_pivoting::<_, Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>>>(
+            &sphere,
+            &[],
+            &[Positive::new(1.5).unwrap()]
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn mesh_to_cloud_test() {
+    let cube = unit_cube();
+
+    let vertices: PointCloud3D<Point3D> = mesh_vertices_to_cloud(&cube);
+    assert_eq!(8, vertices.len());
+    for i in 0..8 {
+        assert_eq!(cube.vertex(VId(i)).unwrap(), vertices[i]);
+    }
+
+    let centers: PointCloud3D<Point3D> = mesh_face_centers_to_cloud(&cube);
+    assert_eq!(cube.num_faces(), centers.len());
+    for i in 0..cube.num_faces() {
+        let [v1, v2, v3] = cube.face_vertices(FId(i)).unwrap();
+        let expected = Point3D::new(
+            (v1.x + v2.x + v3.x) / 3.0,
+            (v1.y + v2.y + v3.y) / 3.0,
+            (v1.z + v2.z + v3.z) / 3.0,
+        );
+        assert!(dist_3d(&expected, &centers[i]) < 1e-12);
+    }
+}
+
+#[test]
+fn extract_faces_test() {
+    let cube = unit_cube();
+
+    let single: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> =
+        extract_faces(&cube, &[FId(3)]).unwrap();
+    assert_eq!(3, single.num_vertices());
+    assert_eq!(1, single.num_faces());
+    assert_eq!(
+        cube.face_vertices(FId(3)).unwrap(),
+        single.face_vertices(FId(0)).unwrap()
+    );
+
+    let all: Vec<FId> = (0..cube.num_faces()).map(FId).collect();
+    let copy: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> =
+        extract_faces(&cube, &all).unwrap();
+    assert_eq!(8, copy.num_vertices());
+    assert_eq!(cube.num_faces(), copy.num_faces());
+
+    assert!(
+        extract_faces::<_, _, Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>>>(
+            &cube,
+            &[FId(cube.num_faces())]
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn faces_with_inconsistent_normals_test() {
+    let cube = unit_cube();
+
+    let mut normals: Vec<Norm3D> = (0..cube.num_faces())
+        .map(|i| {
+            let [v1, v2, v3] = cube.face_vertices(FId(i)).unwrap();
+            normal_of_face(&v1, &v2, &v3)
+        })
+        .collect();
+    assert!(faces_with_inconsistent_normals(&cube, &normals, 0.01)
+        .unwrap()
+        .is_empty());
+
+    normals[2] = Norm3D::new(Point3D::new(
+        -normals[2].x(),
+        -normals[2].y(),
+        -normals[2].z(),
+    ))
+    .unwrap();
+    normals[5] = Norm3D::new(Point3D::new(
+        normals[5].x() + 0.1,
+        normals[5].y() + 0.1,
+        normals[5].z() + 0.1,
+    ))
+    .unwrap();
+    assert_eq!(
+        vec![FId(2), FId(5)],
+        faces_with_inconsistent_normals(&cube, &normals, 0.01).unwrap()
+    );
+    assert_eq!(
+        vec![FId(2)],
+        faces_with_inconsistent_normals(&cube, &normals, 0.5).unwrap()
+    );
+
+    assert!(faces_with_inconsistent_normals(&cube, &normals[1..], 0.01).is_err());
+}
+
+#[test]
+fn mesh_quality_test() {
+    let cube = unit_cube();
+
+    let stats = edge_length_stats(&cube).unwrap();
+    assert_eq!(18, stats.count);
+    assert!((stats.min - 1.0).abs() < 1e-9);
+    assert!((stats.max - 2.0f64.sqrt()).abs() < 1e-9);
+    assert!((stats.mean - (12.0 + 6.0 * 2.0f64.sqrt()) / 18.0).abs() < 1e-9);
+    assert!(stats.std_dev > 0.0);
+
+    let angles = dihedral_angles(&cube);
+    assert_eq!(18, angles.len());
+    assert_eq!(6, angles.iter().filter(|a| a.abs() < 1e-9).count());
+    assert_eq!(
+        12,
+        angles
+            .iter()
+            .filter(|a| (*a - std::f64::consts::FRAC_PI_2).abs() < 1e-9)
+            .count()
+    );
+
+    let mut single = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let a = single.add_vertex(Point3D::new(0.0, 0.0, 0.0));
+    let b = single.add_vertex(Point3D::new(1.0, 0.0, 0.0));
+    let c = single.add_vertex(Point3D::new(0.0, 1.0, 0.0));
+    single.try_add_connection(a, b, c).unwrap();
+    assert!(dihedral_angles(&single).is_empty());
+    assert_eq!(3, edge_length_stats(&single).unwrap().count);
+
+    let empty = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    assert!(edge_length_stats(&empty).is_err());
+}
+
+#[test]
+fn wireframe_edges_test() {
+    let cube = unit_cube();
+
+    assert_eq!(18, wireframe_edges(&cube, EdgeSelection::All).len());
+    assert!(wireframe_edges(&cube, EdgeSelection::Boundary).is_empty());
+    let creases = wireframe_edges(&cube, EdgeSelection::Crease(0.1));
+    assert_eq!(12, creases.len());
+    for (a, b) in creases {
+        assert!((dist_3d(&a, &b) - 1.0).abs() < 1e-9);
+    }
+
+    let mut single = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let a = single.add_vertex(Point3D::new(0.0, 0.0, 0.0));
+    let b = single.add_vertex(Point3D::new(1.0, 0.0, 0.0));
+    let c = single.add_vertex(Point3D::new(0.0, 1.0, 0.0));
+    single.try_add_connection(a, b, c).unwrap();
+    assert_eq!(
+        vec![
+            (Point3D::new(0.0, 0.0, 0.0), Point3D::new(1.0, 0.0, 0.0)),
+            (Point3D::new(0.0, 0.0, 0.0), Point3D::new(0.0, 1.0, 0.0)),
+            (Point3D::new(1.0, 0.0, 0.0), Point3D::new(0.0, 1.0, 0.0)),
+        ],
+        wireframe_edges(&single, EdgeSelection::Boundary)
+    );
+}
+
+#[test]
+fn mesh_change_vertex_test() {
+    let mut cube = unit_cube();
+    let nf = cube.num_faces();
+
+    cube.change_vertex(VId(0), Point3D::new(-1.0, -1.0, -1.0))
+        .unwrap();
+    assert_eq!(Point3D::new(-1.0, -1.0, -1.0), cube.vertex(VId(0)).unwrap());
+    assert_eq!(nf, cube.num_faces());
+    assert!(cube
+        .change_vertex(VId(cube.num_vertices()), Point3D::default())
+        .is_err());
+}
+
+#[test]
+fn split_vertices_per_face_test() {
+    let cube = unit_cube();
+
+    let split: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> = split_vertices_per_face(&cube);
+    assert_eq!(cube.num_faces(), split.num_faces());
+    assert_eq!(3 * cube.num_faces(), split.num_vertices());
+    for i in 0..cube.num_faces() {
+        assert_eq!(
+            cube.face_vertices(FId(i)).unwrap(),
+            split.face_vertices(FId(i)).unwrap()
+        );
+        let face = split.face_vertex_ids(FId(i)).unwrap();
+        assert_eq!(
+            [3 * i, 3 * i + 1, 3 * i + 2],
+            [face.a.0, face.b.0, face.c.0]
+        );
+    }
+}
+
+#[test]
+fn transfer_colors_from_mesh_test() {
+    let mut mesh = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let v0 = mesh.add_vertex(Point3D::new(0.0, 0.0, 0.0));
+    let v1 = mesh.add_vertex(Point3D::new(1.0, 0.0, 0.0));
+    let v2 = mesh.add_vertex(Point3D::new(1.0, 1.0, 0.0));
+    let v3 = mesh.add_vertex(Point3D::new(0.0, 1.0, 0.0));
+    mesh.try_add_connection(v0, v1, v2).unwrap();
+    mesh.try_add_connection(v0, v2, v3).unwrap();
+
+    let colors = vec![
+        Rgb::new(255, 0, 0),
+        Rgb::new(0, 255, 0),
+        Rgb::new(0, 0, 255),
+        Rgb::new(0, 0, 0),
+    ];
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(0.0, 0.0, 5.0));
+    pc.push(Point3D::new(1.0, 0.0, -1.0));
+    pc.push(Point3D::new(0.5, 0.0, 2.0));
+    pc.push(Point3D::new(10.0, -3.0, 0.0));
+    pc.push(Point3D::new(0.0, 0.5, 0.1));
+
+    let transferred = transfer_colors_from_mesh(&pc, &mesh, &colors).unwrap();
+    assert_eq!(
+        vec![
+            Rgb::new(255, 0, 0),
+            Rgb::new(0, 255, 0),
+            Rgb::new(128, 128, 0),
+            Rgb::new(0, 255, 0),
+            Rgb::new(128, 0, 0),
+        ],
+        transferred
+    );
+
+    assert!(transfer_colors_from_mesh(&pc, &mesh, &colors[1..]).is_err());
+
+    let empty = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    assert!(transfer_colors_from_mesh(&pc, &empty, &[]).is_err());
+}
+
+#[test]
+fn indexed_mesh_test() {
+    let cube = unit_cube();
+
+    let (positions, indices) = to_indexed(&cube).unwrap();
+    assert_eq!(cube.num_vertices(), positions.len());
+    assert_eq!(cube.num_faces(), indices.len());
+    assert_eq!(cube.vertex(VId(3)).unwrap().xyz(), positions[3]);
+    let f = cube.face_vertex_ids(FId(2)).unwrap();
+    assert_eq!([f.a.0 as u32, f.b.0 as u32, f.c.0 as u32], indices[2]);
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    from_indexed(&positions, &indices, &mut m).unwrap();
+    assert_eq!(
+        (positions.clone(), indices.clone()),
+        to_indexed(&m).unwrap()
+    );
+
+    // appending offsets the indices by the existing vertices
+    from_indexed(&positions, &indices[..1], &mut m).unwrap();
+    assert_eq!(2 * positions.len(), m.num_vertices());
+    let f = m.face_vertex_ids(FId(indices.len())).unwrap();
+    assert_eq!(positions.len() + indices[0][0] as usize, f.a.0);
+
+    let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    assert!(from_indexed(&positions, &[[0, 1, 8]], &mut m).is_err());
+    assert!(from_indexed(&positions, &[[0, 1, 1]], &mut m).is_err());
+    assert_eq!(0, m.num_vertices());
+}
+
+fn is_closed(m: &Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>>) -> bool {
+    let mut edges = std::collections::HashMap::new();
+    for i in 0..m.num_faces() {
+        let f = m.face_vertex_ids(FId(i)).unwrap();
+        for (a, b) in [(f.a.0, f.b.0), (f.b.0, f.c.0), (f.c.0, f.a.0)] {
+            *edges.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+        }
+    }
+    edges.values().all(|n| *n == 2)
+}
+
+#[test]
+fn polygonize_sdf_grid_test() {
+    let bounds = BoundingBox3D::new(
+        &Point3D::new(-1.0, -1.0, -1.0),
+        &Point3D::new(1.0, 1.0, 1.0),
+    )
+    .unwrap();
+    let n = 21;
+    let mut values = Vec::new();
+    for z in 0..n {
+        for y in 0..n {
+            for x in 0..n {
+                let p = Point3D::new(
+                    -1.0 + 0.1 * x as f64,
+                    -1.0 + 0.1 * y as f64,
+                    -1.0 + 0.1 * z as f64,
+                );
+                values.push(*p.abs() - 0.8);
+            }
+        }
+    }
+
+    let m: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> =
+        polygonize_sdf_grid(&values, &bounds, [n, n, n]).unwrap();
+    assert!(m.num_faces() > 0);
+    assert!(is_closed(&m));
+    for i in 0..m.num_vertices() {
+        assert!((*m.vertex(VId(i)).unwrap().abs() - 0.8).abs() < 0.01);
+    }
+    let expected = 4.0 / 3.0 * std::f64::consts::PI * 0.8f64.powi(3);
+    assert!((mesh_signed_volume(&m) - expected).abs() < 0.05 * expected);
+
+    // unknown samples are skipped
+    let mut partial = values.clone();
+    partial[n * n * n / 2] = f64::NAN;
+    let m: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> =
+        polygonize_sdf_grid(&partial, &bounds, [n, n, n]).unwrap();
+    assert!(m.num_faces() > 0);
+
+    assert!(
+        polygonize_sdf_grid::<Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>>, _>(
+            &values[1..],
+            &bounds,
+            [n, n, n]
+        )
+        .is_err()
+    );
+    assert!(
+        polygonize_sdf_grid::<Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>>, _>(
+            &values,
+            &bounds,
+            [1, n, n * n]
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn cloud_to_mesh_sdf_test() {
+    // evenly distributed positions on the unit sphere
+    let n = 2000;
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut normals = Vec::new();
+    let golden_angle = std::f64::consts::PI * (3.0 - 5.0f64.sqrt());
+    for i in 0..n {
+        let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+        let r = (1.0 - z * z).sqrt();
+        let phi = golden_angle * i as f64;
+        let p = Point3D::new(r * phi.cos(), r * phi.sin(), z);
+        normals.push(Norm3D::new(p.clone()).unwrap());
+        pc.push(p);
+    }
+
+    let m: Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>> = cloud_to_mesh_sdf(
+        &pc,
+        &normals,
+        Positive::new(0.1).unwrap(),
+        Positive::new(0.3).unwrap(),
+    )
+    .unwrap();
+    assert!(m.num_faces() > 0);
+    assert!(is_closed(&m));
+    for i in 0..m.num_vertices() {
+        assert!((*m.vertex(VId(i)).unwrap().abs() - 1.0).abs() < 0.05);
+    }
+    let expected = 4.0 / 3.0 * std::f64::consts::PI;
+    assert!((mesh_signed_volume(&m) - expected).abs() < 0.1 * expected);
+
+    assert!(
+        cloud_to_mesh_sdf::<_, _, Mesh3D<Point3D, PointCloud3D<Point3D>, Vec<usize>>, _>(
+            &pc,
+            &normals[1..],
+            Positive::new(0.1).unwrap(),
+            Positive::new(0.3).unwrap(),
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn estimate_curvature_test() {
+    // UV sphere with radius 0.8, faces oriented outwards
+    let (radius, n_lat, n_lon) = (0.8, 30, 60);
+    let mut sphere = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    sphere.add_vertex(Point3D::new(0.0, 0.0, radius));
+    for i in 1..n_lat {
+        let theta = std::f64::consts::PI * i as f64 / n_lat as f64;
+        for j in 0..n_lon {
+            let phi = 2.0 * std::f64::consts::PI * j as f64 / n_lon as f64;
+            sphere.add_vertex(Point3D::new(
+                radius * theta.sin() * phi.cos(),
+                radius * theta.sin() * phi.sin(),
+                radius * theta.cos(),
+            ));
+        }
+    }
+    let south = sphere.add_vertex(Point3D::new(0.0, 0.0, -radius));
+    let ring = |i: usize, j: usize| VId(1 + (i - 1) * n_lon + j % n_lon);
+    for j in 0..n_lon {
+        sphere
+            .try_add_connection(VId(0), ring(1, j), ring(1, j + 1))
+            .unwrap();
+        sphere
+            .try_add_connection(south, ring(n_lat - 1, j + 1), ring(n_lat - 1, j))
+            .unwrap();
+    }
+    for i in 1..n_lat - 1 {
+        for j in 0..n_lon {
+            sphere
+                .try_add_connection(ring(i, j), ring(i + 1, j), ring(i + 1, j + 1))
+                .unwrap();
+            sphere
+                .try_add_connection(ring(i, j), ring(i + 1, j + 1), ring(i, j + 1))
+                .unwrap();
+        }
+    }
+
+    let curvatures = estimate_curvature(&sphere);
+    assert_eq!(sphere.num_vertices(), curvatures.len());
+    // the poles are fans of thin triangles, which makes the estimate less accurate there
+    let (poles, rings) = curvatures.split_at(1);
+    let rings = &rings[..rings.len() - 1];
+    assert!(rings.iter().all(|c| (c - 1.0 / radius).abs() < 0.01));
+    assert!(poles.iter().all(|c| (c - 1.0 / radius).abs() < 0.35));
+    assert!((curvatures[curvatures.len() - 1] - 1.0 / radius).abs() < 0.35);
+
+    // flat and open, interior vertices have no curvature, boundary vertices are 0 by definition
+    let mut grid = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    for y in 0..4 {
+        for x in 0..4 {
+            grid.add_vertex(Point3D::new(x as f64, y as f64, 0.0));
+        }
+    }
+    for y in 0..3 {
+        for x in 0..3 {
+            let i = y * 4 + x;
+            grid.try_add_connection(VId(i), VId(i + 1), VId(i + 5))
+                .unwrap();
+            grid.try_add_connection(VId(i), VId(i + 5), VId(i + 4))
+                .unwrap();
+        }
+    }
+    assert!(estimate_curvature(&grid).iter().all(|c| c.abs() < 1e-9));
+}
+
+#[test]
+fn recompute_face_normals_test() {
+    let mut mesh = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    mesh.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 1.0, 0.0),
+    );
+    mesh.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(0.0, 0.0, 2.0),
+        Point3D::new(3.0, 0.0, 0.0),
+    );
+    // collinear
+    mesh.add_face(
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 1.0, 1.0),
+        Point3D::new(2.0, 2.0, 2.0),
+    );
+
+    assert_eq!(
+        vec![Some(Norm3D::norm_z()), Some(Norm3D::norm_y()), None],
+        recompute_face_normals(&mesh)
+    );
+}
+
+#[test]
+fn vertex_normals_test() {
+    let mut mesh = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    let origin = mesh.add_vertex(Point3D::new(0.0, 0.0, 0.0));
+    let x1 = mesh.add_vertex(Point3D::new(1.0, 0.0, 0.0));
+    let y1 = mesh.add_vertex(Point3D::new(0.0, 1.0, 0.0));
+    let z2 = mesh.add_vertex(Point3D::new(0.0, 0.0, 2.0));
+    let x3 = mesh.add_vertex(Point3D::new(3.0, 0.0, 0.0));
+    // unused
+    mesh.add_vertex(Point3D::new(5.0, 5.0, 5.0));
+
+    mesh.try_add_connection(origin, x1, y1).unwrap();
+    mesh.try_add_connection(origin, z2, x3).unwrap();
+
+    let assert_normal = |n: &Option<Norm3D>, x: f64, y: f64, z: f64| {
+        let n = n.as_ref().unwrap();
+        assert!((n.x() - x).abs() < 1e-9);
+        assert!((n.y() - y).abs() < 1e-9);
+        assert!((n.z() - z).abs() < 1e-9);
+    };
+
+    let normals = vertex_normals(&mesh, false);
+    assert_eq!(6, normals.len());
+    let s = 0.5f64.sqrt();
+    assert_normal(&normals[0], 0.0, s, s);
+    assert_normal(&normals[1], 0.0, 0.0, 1.0);
+    assert_normal(&normals[2], 0.0, 0.0, 1.0);
+    assert_normal(&normals[3], 0.0, 1.0, 0.0);
+    assert_normal(&normals[4], 0.0, 1.0, 0.0);
+    assert_eq!(None, normals[5]);
+
+    // the second face is 6 times larger than the first one
+    let normals = vertex_normals(&mesh, true);
+    assert_eq!(6, normals.len());
+    let l = 37.0f64.sqrt();
+    assert_normal(&normals[0], 0.0, 6.0 / l, 1.0 / l);
+    assert_normal(&normals[1], 0.0, 0.0, 1.0);
+    assert_normal(&normals[3], 0.0, 1.0, 0.0);
+    assert_eq!(None, normals[5]);
+
+    // exporting requires a normal per vertex
+    let exported: Vec<Norm3D> = normals
+        .into_iter()
+        .map(|n| n.unwrap_or(Norm3D::norm_z()))
+        .collect();
+    let mut bytes = Vec::new();
+    save_ply_ascii_with_normals(&mut bytes, &mesh, Some(&exported)).unwrap();
+    let mut nz = Vec::new();
+    let mut loaded = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    load_ply_with_scalar(bytes.as_slice(), &mut loaded, "nz", &mut nz).unwrap();
+    assert_eq!(1.0, nz[5]);
+
+    // twice the area, pointing to the side the counter-clockwise winding faces
+    assert_eq!(
+        Point3D::new(0.0, 0.0, 2.0),
+        face_winding(
+            &Point3D::new(0.0, 0.0, 0.0),
+            &Point3D::new(2.0, 0.0, 0.0),
+            &Point3D::new(0.0, 1.0, 0.0)
+        )
+    );
+}
+
+#[test]
+fn mesh_area_volume_test() {
+    let cube = unit_cube();
+    assert!((6.0 - mesh_surface_area(&cube)).abs() < 1e-9);
+    assert!((1.0 - mesh_signed_volume(&cube)).abs() < 1e-9);
+
+    // the volume doesn't depend on the position
+    let mut moved = cube.clone();
+    moved.move_by(10.0, -3.0, 2.5);
+    assert!((6.0 - mesh_surface_area(&moved)).abs() < 1e-9);
+    assert!((1.0 - mesh_signed_volume(&moved)).abs() < 1e-9);
+
+    // inverted winding
+    let mut inverted = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    for i in 0..cube.num_faces() {
+        let [a, b, c] = cube.face_vertices(FId(i)).unwrap();
+        inverted.add_face(a, c, b);
+    }
+    assert!((6.0 - mesh_surface_area(&inverted)).abs() < 1e-9);
+    assert!((1.0 + mesh_signed_volume(&inverted)).abs() < 1e-9);
+
+    let empty = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+    assert_eq!(0.0, mesh_surface_area(&empty));
+    assert_eq!(0.0, mesh_signed_volume(&empty));
+}
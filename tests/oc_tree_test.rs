@@ -0,0 +1,200 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+#![deny(warnings)]
+
+use rust_3d::*;
+
+#[test]
+fn oc_tree_keeps_all_points_test() {
+    // a regular grid, where many positions lie exactly on the planes between octants
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for x in 0..9 {
+        for y in 0..9 {
+            for z in 0..9 {
+                pc.push(Point3D::new(x as f64, y as f64, z as f64));
+            }
+        }
+    }
+    let n = pc.len();
+
+    let mut tree = OcTree::<Point3D>::default();
+    tree.build(pc).unwrap();
+
+    assert_eq!(n, tree.size());
+    assert_eq!(n, tree.to_pointcloud().len());
+}
+
+#[test]
+fn oc_tree_leaf_capacity_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..1000 {
+        let t = i as f64 * 0.1;
+        // clustered data with a few outliers
+        let scale = if i % 100 == 0 { 100.0 } else { 0.001 };
+        pc.push(Point3D::new(
+            scale * t.sin(),
+            scale * (1.3 * t).cos(),
+            scale * (0.7 * t).sin(),
+        ));
+    }
+    let n = pc.len();
+
+    for &(max_points_per_leaf, max_depth) in &[(1, usize::MAX), (16, usize::MAX), (1, 3), (4, 0)] {
+        let mut tree = OcTree::<Point3D>::new(max_points_per_leaf, max_depth);
+        tree.build(pc.clone()).unwrap();
+        assert_eq!(n, tree.size());
+        assert_eq!(n, tree.to_pointcloud().len());
+    }
+}
+
+#[test]
+fn oc_tree_unique_collect_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for x in 0..8 {
+        for y in 0..8 {
+            for z in 0..8 {
+                let p = Point3D::new(x as f64, y as f64, z as f64);
+                // every position twice
+                pc.push(p.clone());
+                pc.push(p);
+            }
+        }
+    }
+    let n_unique = pc.len() / 2;
+    let bb = pc.bounding_box_maybe().unwrap();
+
+    let mut tree = OcTree::<Point3D>::new(1, usize::MAX);
+    tree.build(pc).unwrap();
+    assert_eq!(n_unique, tree.size());
+    assert_eq!(n_unique, tree.collect(-1).len());
+
+    // subtrees below the max depth collapse to their centers
+    let collected = tree.collect(0);
+    assert!(collected.len() > 0);
+    assert!(collected.len() <= 64);
+    assert!(collected.len() < n_unique);
+    for p in collected.data.iter() {
+        assert!(bb.contains(p));
+    }
+}
+
+#[test]
+fn oc_tree_in_box_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for x in 0..8 {
+        for y in 0..8 {
+            for z in 0..8 {
+                pc.push(Point3D::new(x as f64, y as f64, z as f64));
+            }
+        }
+    }
+
+    let mut tree = OcTree::<Point3D>::new(1, usize::MAX);
+    tree.build(pc.clone()).unwrap();
+
+    // exactly the lower octant
+    let octant = Box3D {
+        center: Point3D::new(1.75, 1.75, 1.75),
+        size_x: Positive::new(3.5).unwrap(),
+        size_y: Positive::new(3.5).unwrap(),
+        size_z: Positive::new(3.5).unwrap(),
+    };
+    let mut result = Vec::new();
+    tree.in_box(&octant, &mut result);
+    result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut expected: Vec<Point3D> = pc
+        .data
+        .iter()
+        .filter(|p| p.x < 4.0 && p.y < 4.0 && p.z < 4.0)
+        .cloned()
+        .collect();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(64, result.len());
+    assert_eq!(expected, result);
+
+    // positions on the border of the box are included
+    let slice = Box3D {
+        center: Point3D::new(3.5, 3.5, 7.0),
+        size_x: Positive::new(7.0).unwrap(),
+        size_y: Positive::new(7.0).unwrap(),
+        size_z: Positive::new(0.5).unwrap(),
+    };
+    let mut result = Vec::new();
+    tree.in_box(&slice, &mut result);
+    assert_eq!(64, result.len());
+    assert!(result.iter().all(|p| p.z == 7.0));
+
+    // boxes outside of the tree yield nothing
+    let outside = Box3D {
+        center: Point3D::new(100.0, 0.0, 0.0),
+        size_x: Positive::new(1.0).unwrap(),
+        size_y: Positive::new(1.0).unwrap(),
+        size_z: Positive::new(1.0).unwrap(),
+    };
+    let mut result = Vec::new();
+    tree.in_box(&outside, &mut result);
+    assert!(result.is_empty());
+}
+
+#[test]
+fn oc_tree_degenerate_box_test() {
+    // positions too close to each other for their boxes to be split further
+    let e = f64::EPSILON;
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(0.0, 0.0, 0.0));
+    for i in 0..3 {
+        let x = 1.0 + i as f64 * e;
+        pc.push(Point3D::new(x, x, x));
+    }
+
+    let mut tree = OcTree::<Point3D>::new(1, usize::MAX);
+    tree.build(pc.clone()).unwrap();
+    assert_eq!(4, tree.size());
+
+    let around = |x: f64| Box3D {
+        center: Point3D::new(x, x, x),
+        size_x: Positive::new(0.1).unwrap(),
+        size_y: Positive::new(0.1).unwrap(),
+        size_z: Positive::new(0.1).unwrap(),
+    };
+    let mut result = Vec::new();
+    tree.in_box(&around(1.0), &mut result);
+    result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(pc.data[1..].to_vec(), result);
+
+    let mut result = Vec::new();
+    tree.in_box(&around(0.0), &mut result);
+    assert_eq!(vec![Point3D::new(0.0, 0.0, 0.0)], result);
+
+    // the whole tree being too small to be split
+    let tiny = f64::from_bits(1);
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(0.0, 0.0, 0.0));
+    pc.push(Point3D::new(tiny, tiny, tiny));
+    let mut tree = OcTree::<Point3D>::new(1, usize::MAX);
+    tree.build(pc).unwrap();
+    let mut result = Vec::new();
+    tree.in_box(&around(0.0), &mut result);
+    assert_eq!(2, result.len());
+}
@@ -122,3 +122,15 @@ fn point_2d_test() {
     assert!(p1.x() == 1.1);
     assert!(p1.y() == 2.2);
 }
+
+#[test]
+fn point_2d_conversions_test() {
+    let p = Point2D::new(1.0, 2.0);
+    assert_eq!(p, [1.0, 2.0].into());
+    assert_eq!(p, (1.0, 2.0).into());
+
+    let a: [f64; 2] = p.clone().into();
+    assert_eq!([1.0, 2.0], a);
+    let (x, y) = p.into();
+    assert_eq!((1.0, 2.0), (x, y));
+}
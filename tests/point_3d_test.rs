@@ -124,3 +124,88 @@ fn point_3d_test() {
 
     //@todo missing tests for matrix multiplication
 }
+
+#[test]
+fn calc_direction_test() {
+    let reference = Point3D::new(1.0, 2.0, 3.0);
+
+    let cases = [
+        (Point3D::new(2.0, 3.0, 4.0), Direction::PPP),
+        (Point3D::new(2.0, 3.0, 2.0), Direction::PPN),
+        (Point3D::new(2.0, 1.0, 4.0), Direction::PNP),
+        (Point3D::new(2.0, 1.0, 2.0), Direction::PNN),
+        (Point3D::new(0.0, 3.0, 4.0), Direction::NPP),
+        (Point3D::new(0.0, 3.0, 2.0), Direction::NPN),
+        (Point3D::new(0.0, 1.0, 4.0), Direction::NNP),
+        (Point3D::new(0.0, 1.0, 2.0), Direction::NNN),
+        // ties are treated as positive
+        (reference.clone(), Direction::PPP),
+        (Point3D::new(0.0, 2.0, 2.0), Direction::NPN),
+    ];
+
+    for (p, expected) in cases.iter() {
+        assert_eq!(*expected, calc_direction(&reference, p));
+    }
+}
+
+#[test]
+fn nearest_nd_test() {
+    let candidates = vec![
+        Point3D::new(5.0, 0.0, 0.0),
+        Point3D::new(1.0, 1.0, 0.0),
+        Point3D::new(-2.0, 0.0, 0.0),
+    ];
+
+    let (p, sqr_dist) = nearest_nd(&Point3D::new(0.0, 0.0, 0.0), &candidates).unwrap();
+    assert_eq!(&candidates[1], p);
+    assert_eq!(2.0, sqr_dist);
+
+    let (p, sqr_dist) = nearest_nd(&Point3D::new(-2.0, 0.0, 0.0), candidates.iter()).unwrap();
+    assert_eq!(&candidates[2], p);
+    assert_eq!(0.0, sqr_dist);
+
+    assert!(nearest_nd(&Point3D::new(0.0, 0.0, 0.0), &Vec::new()).is_none());
+}
+
+#[test]
+fn point_3d_conversions_test() {
+    let p = Point3D::new(1.0, 2.0, 3.0);
+    assert_eq!(p, [1.0, 2.0, 3.0].into());
+    assert_eq!(p, (1.0, 2.0, 3.0).into());
+
+    let a: [f64; 3] = p.clone().into();
+    assert_eq!([1.0, 2.0, 3.0], a);
+    let (x, y, z) = p.into();
+    assert_eq!((1.0, 2.0, 3.0), (x, y, z));
+
+    let ps: Vec<Point3D> = vec![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]]
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    assert_eq!(Point3D::new(1.0, 1.0, 1.0), ps[1]);
+}
+
+#[test]
+fn point_3d_operators_test() {
+    let a = Point3D::new(1.0, 2.0, 3.0);
+    let b = Point3D::new(-4.0, 0.5, 2.0);
+
+    assert_eq!(Point3D::new(-3.0, 2.5, 5.0), a.clone() + b.clone());
+    assert_eq!(Point3D::new(-3.0, 2.5, 5.0), &a + &b);
+    assert_eq!(Point3D::new(-3.0, 2.5, 5.0), &a + b.clone());
+
+    assert_eq!(Point3D::new(5.0, 1.5, 1.0), a.clone() - b.clone());
+    assert_eq!(Point3D::new(5.0, 1.5, 1.0), &a - &b);
+    assert_eq!(Point3D::new(5.0, 1.5, 1.0), &a - b.clone());
+    assert_eq!(conn(&b, &a), &a - &b);
+
+    assert_eq!(Point3D::new(2.0, 4.0, 6.0), a.clone() * 2.0);
+    assert_eq!(Point3D::new(-0.5, -1.0, -1.5), &a * -0.5);
+
+    assert_eq!(Point3D::new(0.5, 1.0, 1.5), a.clone() / 2.0);
+    assert_eq!(Point3D::new(-2.0, 0.25, 1.0), &b / 2.0);
+
+    assert_eq!(Point3D::new(-1.0, -2.0, -3.0), -a.clone());
+    assert_eq!(Point3D::new(4.0, -0.5, -2.0), -&b);
+    assert_eq!(a.clone(), -(-a.clone()));
+}
@@ -70,3 +70,69 @@ fn point_cloud_2d_test() {
     println!("pc: {}", pc);
     assert!(pc.to_str() == "1.1 2.2\n1.2 2.3\n");
 }
+
+#[test]
+fn test_rasterize_2d() {
+    let mut pc = PointCloud2D::<Point2D>::new();
+    pc.push(Point2D::new(0.0, 0.0));
+    pc.push(Point2D::new(0.5, 0.5));
+    pc.push(Point2D::new(0.9, 0.1));
+    pc.push(Point2D::new(2.5, 0.2));
+    pc.push(Point2D::new(2.0, 1.0));
+
+    let raster = rasterize_2d(&pc, Positive::new(1.0).unwrap()).unwrap();
+    assert_eq!(&Point2D::new(0.0, 0.0), raster.origin());
+    assert_eq!(3, raster.nx());
+    assert_eq!(2, raster.ny());
+    assert_eq!(Some(3), raster.count(0, 0));
+    assert_eq!(Some(0), raster.count(1, 0));
+    assert_eq!(Some(1), raster.count(2, 0));
+    assert_eq!(Some(1), raster.count(2, 1));
+    assert_eq!(None, raster.count(3, 0));
+    assert_eq!(3, raster.max_count());
+    assert_eq!(vec![255, 0, 85, 0, 0, 85], raster.to_gray_bytes(3));
+    assert_eq!(vec![255, 0, 255, 0, 0, 255], raster.to_gray_bytes(0));
+
+    assert!(rasterize_2d(&PointCloud2D::<Point2D>::new(), Positive::new(1.0).unwrap()).is_err());
+}
+
+#[test]
+fn test_point_cloud_2d_to_3d() {
+    let mut pc = PointCloud2D::<Point2D>::new();
+    pc.push(Point2D::new(1.0, 2.0));
+    pc.push(Point2D::new(-3.0, 4.0));
+
+    let pc3d: PointCloud3D<Point3D> = pc.to_3d(5.0);
+    assert_eq!(2, pc3d.len());
+    assert_eq!(Point3D::new(1.0, 2.0, 5.0), pc3d.data[0]);
+    assert_eq!(Point3D::new(-3.0, 4.0, 5.0), pc3d.data[1]);
+
+    assert_eq!(
+        0,
+        PointCloud2D::<Point2D>::new().to_3d::<Point3D>(0.0).len()
+    );
+}
+
+#[test]
+fn test_point_cloud_2d_transform() {
+    let eps = 0.0001;
+    let m = Matrix3::translation(2.0, 3.0) * Matrix3::rotation(Rad(0.5 * std::f64::consts::PI));
+
+    let p = m.transform_point(&Point2D::new(1.0, 0.0));
+    assert!((p.x - 2.0).abs() < eps);
+    assert!((p.y - 4.0).abs() < eps);
+
+    let p =
+        (Matrix3::scale(2.0, 0.5) * Matrix3::identity()).transform_point(&Point2D::new(3.0, 4.0));
+    assert!((p.x - 6.0).abs() < eps);
+    assert!((p.y - 2.0).abs() < eps);
+
+    let mut pc = PointCloud2D::<Point2D>::new();
+    pc.push(Point2D::new(1.0, 0.0));
+    pc.push(Point2D::new(0.0, 1.0));
+    pc.transform(&m);
+    assert!((pc.data[0].x - 2.0).abs() < eps);
+    assert!((pc.data[0].y - 4.0).abs() < eps);
+    assert!((pc.data[1].x - 1.0).abs() < eps);
+    assert!((pc.data[1].y - 3.0).abs() < eps);
+}
@@ -60,3 +60,635 @@ fn point_cloud_3d_io_test() {
         assert!(pc.len() == 20 * 20 * 20);
     }
 }
+
+#[test]
+fn point_cloud_3d_ply_property_order_test() {
+    let expected = [Point3D::new(1.0, 2.0, 3.0), Point3D::new(4.0, 5.0, 6.0)];
+
+    let zyx = "ply\n\
+               format ascii 1.0\n\
+               element vertex 2\n\
+               property float confidence\n\
+               property float z\n\
+               property float intensity\n\
+               property float y\n\
+               property float x\n\
+               end_header\n\
+               0.5 3 0.1 2 1\n\
+               0.5 6 0.1 5 4\n";
+
+    let yzx = "ply\n\
+               format ascii 1.0\n\
+               element vertex 2\n\
+               property float y\n\
+               property float confidence\n\
+               property float z\n\
+               property float x\n\
+               property float intensity\n\
+               end_header\n\
+               2 0.5 3 1 0.1\n\
+               5 0.5 6 4 0.1\n";
+
+    let zxy = "ply\n\
+               format ascii 1.0\n\
+               element vertex 2\n\
+               property float z\n\
+               property float x\n\
+               property float intensity\n\
+               property float y\n\
+               end_header\n\
+               3 1 0.1 2\n\
+               6 4 0.1 5\n";
+
+    for text in &[zyx, yzx, zxy] {
+        let mut pc = PointCloud3D::<Point3D>::new();
+        load_ply_points(&mut BufReader::new(text.as_bytes()), &mut pc).unwrap();
+        assert_eq!(pc.data, expected);
+    }
+
+    // binary with mixed precision, the types have to be assigned in order of occurrence
+    let mut binary = b"ply\n\
+                       format binary_big_endian 1.0\n\
+                       element vertex 1\n\
+                       property double z\n\
+                       property uchar intensity\n\
+                       property float x\n\
+                       property float y\n\
+                       end_header\n"
+        .to_vec();
+    binary.extend_from_slice(&3.0f64.to_be_bytes());
+    binary.push(7);
+    binary.extend_from_slice(&1.0f32.to_be_bytes());
+    binary.extend_from_slice(&2.0f32.to_be_bytes());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_ply_points(&mut BufReader::new(&binary[..]), &mut pc).unwrap();
+    assert_eq!(pc.data, expected[..1].to_vec());
+}
+
+#[test]
+fn point_cloud_3d_xyz_delimiter_test() {
+    // mixing spaces and commas with leading whitespace, which confuses the delimiter estimation
+    let data = "  1.0, 2.0,3.0\n4.0 ,5.0, 6.0\n";
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_xyz_with_delimiter(data.as_bytes(), &mut pc, ",").unwrap();
+    assert_eq!(2, pc.len());
+    assert_eq!(Point3D::new(1.0, 2.0, 3.0), pc[0]);
+    assert_eq!(Point3D::new(4.0, 5.0, 6.0), pc[1]);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_xyz_with_delimiter("1.0::2.0::3.0\n".as_bytes(), &mut pc, "::").unwrap();
+    assert_eq!(vec![Point3D::new(1.0, 2.0, 3.0)], pc.data);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    assert!(load_xyz_with_delimiter("1.0;2.0;3.0\n".as_bytes(), &mut pc, ",").is_err());
+}
+
+#[test]
+fn point_cloud_3d_non_finite_test() {
+    let xyz = "1 2 3\nnan 2 3\n4 inf 6\n7 8 9\n";
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_xyz(xyz.as_bytes(), &mut pc).unwrap();
+    assert_eq!(4, pc.len());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_xyz_handling_non_finite(xyz.as_bytes(), &mut pc, NonFiniteHandling::Skip).unwrap();
+    assert_eq!(
+        vec![Point3D::new(1.0, 2.0, 3.0), Point3D::new(7.0, 8.0, 9.0)],
+        pc.data
+    );
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_xyz_handling_non_finite(xyz.as_bytes(), &mut pc, NonFiniteHandling::Error) {
+        Err(WithLineInfo::Line(2, _, XyzError::NonFinite(2))) => (),
+        _ => panic!("expected a non-finite error in line 2"),
+    }
+
+    let ply = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+end_header
+1 2 3
+4 -inf 6
+7 8 9
+";
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_ply_points(ply.as_bytes(), &mut pc).unwrap();
+    assert_eq!(3, pc.len());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_ply_points_handling_non_finite(ply.as_bytes(), &mut pc, NonFiniteHandling::Skip).unwrap();
+    assert_eq!(2, pc.len());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_ply_points_handling_non_finite(ply.as_bytes(), &mut pc, NonFiniteHandling::Error) {
+        Err(WithLineInfo::None(PlyError::NonFinite(1))) => (),
+        _ => panic!("expected a non-finite error for vertex 1"),
+    }
+}
+
+#[test]
+fn point_cloud_3d_xyz_iter_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..10 {
+        pc.push(Point3D::new(i as f64, 0.5, -1.0));
+    }
+
+    let mut expected = Vec::new();
+    save_xyz(&mut expected, &pc, " ", "\n").unwrap();
+
+    let mut streamed = Vec::new();
+    save_xyz_iter(&mut streamed, pc.data.iter().cloned(), " ", "\n").unwrap();
+    assert_eq!(expected, streamed);
+
+    // filter-and-save without collecting
+    let mut filtered = Vec::new();
+    save_xyz_iter(
+        &mut filtered,
+        (0..10)
+            .map(|i| Point3D::new(i as f64, 0.0, 0.0))
+            .filter(|p| p.x < 2.0),
+        ";",
+        "\n",
+    )
+    .unwrap();
+    assert_eq!("0;0;0\n1;0;0\n", String::from_utf8(filtered).unwrap());
+}
+
+#[test]
+fn point_cloud_3d_xyz_streaming_test() {
+    let mut eager = PointCloud3D::<Point3D>::new();
+    load_xyz(
+        &mut BufReader::new(File::open("tests/data/test_cube.xyz").unwrap()),
+        &mut eager,
+    )
+    .unwrap();
+
+    let streamed = XyzIterator::<Point3D, _>::new(BufReader::new(
+        File::open("tests/data/test_cube.xyz").unwrap(),
+    ))
+    .collect::<std::result::Result<Vec<_>, _>>()
+    .unwrap();
+    assert_eq!(eager.data, streamed);
+
+    // blank lines, also trailing ones, are skipped
+    let streamed = XyzIterator::<Point3D, _>::new("1 2 3\n\n4 5 6\n\n  \n\n".as_bytes())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        vec![Point3D::new(1.0, 2.0, 3.0), Point3D::new(4.0, 5.0, 6.0)],
+        streamed
+    );
+
+    // invalid lines are reported without ending the iteration
+    let results =
+        XyzIterator::<Point3D, _>::new("1 2 3\n4 x 6\n7 8 9\n".as_bytes()).collect::<Vec<_>>();
+    assert_eq!(3, results.len());
+    assert!(results[0].is_ok());
+    match &results[1] {
+        Err(WithLineInfo::Line(2, _, XyzError::Vertex)) => (),
+        _ => panic!("expected a delimiter error in line 2"),
+    }
+    assert_eq!(Point3D::new(7.0, 8.0, 9.0), *results[2].as_ref().unwrap());
+}
+
+#[test]
+fn point_cloud_3d_xyz_error_line_test() {
+    // stray second header, delimiter can't be estimated
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let err = load_xyz("x y z\nx_y_z\n1 2 3\n".as_bytes(), &mut pc).unwrap_err();
+    assert_eq!(
+        "Line #2: 'Unable to estimate delimiter' 'x_y_z'",
+        format!("{}", err)
+    );
+    match err {
+        WithLineInfo::Line(2, line, XyzError::EstimateDelimiter) => assert_eq!("x_y_z", line),
+        _ => panic!("expected a delimiter error in line 2"),
+    }
+
+    // unit suffix, field can't be parsed
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let err = load_xyz("1 2 3\n4 5m 6\r\n".as_bytes(), &mut pc).unwrap_err();
+    let debug = format!("{:?}", err);
+    assert!(debug.contains('2'));
+    assert!(debug.contains("\"4 5m 6\""));
+    match err {
+        WithLineInfo::Line(2, line, XyzError::Vertex) => assert_eq!("4 5m 6", line),
+        _ => panic!("expected a delimiter error in line 2"),
+    }
+}
+
+#[test]
+fn point_cloud_3d_xyz_header_test() {
+    let csv = "# exported scan
+  # units: m
+x,y,z
+1.0,2.0,3.0
+# intermediate comment
+4,5,6
+";
+    let expected = vec![Point3D::new(1.0, 2.0, 3.0), Point3D::new(4.0, 5.0, 6.0)];
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_xyz(csv.as_bytes(), &mut pc).unwrap();
+    assert_eq!(expected, pc.data);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_xyz_with_delimiter(csv.as_bytes(), &mut pc, ",").unwrap();
+    assert_eq!(expected, pc.data);
+
+    // the delimiter is estimated on the first data line, not the header
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_xyz("x;y;z\n1 2 3\n4 5 6\n".as_bytes(), &mut pc).unwrap();
+    assert_eq!(expected, pc.data);
+
+    // only a single header line is skipped
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_xyz("x,y,z\nm,m,m\n1,2,3\n".as_bytes(), &mut pc) {
+        Err(WithLineInfo::Line(2, _, XyzError::Vertex)) => (),
+        _ => panic!("expected a delimiter error in line 2"),
+    }
+
+    // an invalid first data line isn't mistaken for a header
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_xyz("1 2 x\n4 5 6\n".as_bytes(), &mut pc) {
+        Err(WithLineInfo::Line(1, _, XyzError::Vertex)) => (),
+        _ => panic!("expected a vertex error in line 1"),
+    }
+    let mut iter = XyzIterator::<Point3D, _>::new("x y z\n1,2\n4,5,6\n".as_bytes());
+    match iter.next() {
+        Some(Err(WithLineInfo::Line(2, _, XyzError::EstimateDelimiter))) => (),
+        _ => panic!("expected a delimiter error in line 2"),
+    }
+    assert_eq!(Point3D::new(4.0, 5.0, 6.0), iter.next().unwrap().unwrap());
+    assert!(iter.next().is_none());
+
+    // non-numeric lines after the data aren't skipped
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_xyz("1,2,3\nx,y,z\n".as_bytes(), &mut pc) {
+        Err(WithLineInfo::Line(2, _, XyzError::Vertex)) => (),
+        _ => panic!("expected a delimiter error in line 2"),
+    }
+
+    // custom comment prefix
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for p in
+        XyzIterator::<Point3D, _>::new("// header\n1 2 3\n".as_bytes()).comment_prefix(Some("//"))
+    {
+        pc.push(p.unwrap());
+    }
+    assert_eq!(vec![Point3D::new(1.0, 2.0, 3.0)], pc.data);
+
+    // disabled comment skipping treats the first comment as header
+    let mut iter =
+        XyzIterator::<Point3D, _>::new("# a\n# b\n1 2 3\n".as_bytes()).comment_prefix(None);
+    match iter.next() {
+        Some(Err(WithLineInfo::Line(2, _, XyzError::EstimateDelimiter))) => (),
+        _ => panic!("expected a delimiter error in line 2"),
+    }
+    assert_eq!(Point3D::new(1.0, 2.0, 3.0), iter.next().unwrap().unwrap());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn point_cloud_3d_xyz_extended_test() {
+    let xyz = "# scan with normals
+x y z nx ny nz
+1 2 3 0 0 1
+4 5 6 0 2 0
+-1 -2 -3 -1 0 0
+";
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut normals = Vec::new();
+    let mut extra = Vec::new();
+    load_xyz_extended(xyz.as_bytes(), &mut pc, &mut normals, &mut extra).unwrap();
+    assert_eq!(
+        vec![
+            Point3D::new(1.0, 2.0, 3.0),
+            Point3D::new(4.0, 5.0, 6.0),
+            Point3D::new(-1.0, -2.0, -3.0)
+        ],
+        pc.data
+    );
+    assert_eq!(
+        vec![
+            Some(Norm3D::norm_z()),
+            Some(Norm3D::norm_y()),
+            Norm3D::new(Point3D::new(-1.0, 0.0, 0.0)).ok()
+        ],
+        normals
+    );
+    assert!(extra.is_empty());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut normals = Vec::new();
+    let mut extra = Vec::new();
+    load_xyz_extended(
+        "1;2;3;0.5\n4;5;6;7\n".as_bytes(),
+        &mut pc,
+        &mut normals,
+        &mut extra,
+    )
+    .unwrap();
+    assert_eq!(2, pc.len());
+    assert!(normals.is_empty());
+    assert_eq!(vec![0.5, 7.0], extra);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut normals = Vec::new();
+    let mut extra = Vec::new();
+    load_xyz_extended(
+        "1,2,3,1,0,0,9\n".as_bytes(),
+        &mut pc,
+        &mut normals,
+        &mut extra,
+    )
+    .unwrap();
+    assert_eq!(vec![Point3D::new(1.0, 2.0, 3.0)], pc.data);
+    assert_eq!(vec![Some(Norm3D::norm_x())], normals);
+    assert_eq!(vec![9.0], extra);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut normals = Vec::new();
+    let mut extra = Vec::new();
+    load_xyz_extended("1 2 3\n".as_bytes(), &mut pc, &mut normals, &mut extra).unwrap();
+    assert_eq!(1, pc.len());
+    assert!(normals.is_empty() && extra.is_empty());
+
+    // the column count is locked after the first data line
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_xyz_extended(
+        "1 2 3 0 0 1\n1 2 3 0 0 1\n1 2 3 0\n".as_bytes(),
+        &mut pc,
+        &mut Vec::new(),
+        &mut Vec::new(),
+    ) {
+        Err(WithLineInfo::Line(3, _, XyzError::ColumnCount(4))) => (),
+        _ => panic!("expected a column count error in line 3"),
+    }
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_xyz_extended(
+        "1 2 3 4 5\n".as_bytes(),
+        &mut pc,
+        &mut Vec::new(),
+        &mut Vec::new(),
+    ) {
+        Err(WithLineInfo::Line(1, _, XyzError::ColumnCount(5))) => (),
+        _ => panic!("expected a column count error in line 1"),
+    }
+
+    // zero length normals keep the normals aligned with the positions
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut normals = Vec::new();
+    load_xyz_extended(
+        "1 2 3 0 0 0\n4 5 6 0 0 2\n".as_bytes(),
+        &mut pc,
+        &mut normals,
+        &mut Vec::new(),
+    )
+    .unwrap();
+    assert_eq!(2, pc.len());
+    assert_eq!(vec![None, Some(Norm3D::norm_z())], normals);
+
+    // blank lines, comments and a header are skipped like by XyzIterator
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut extra = Vec::new();
+    load_xyz_extended(
+        "\n  # comment\nx y z i\n\n1 2 3 4\n   \n5 6 7 8\n".as_bytes(),
+        &mut pc,
+        &mut Vec::new(),
+        &mut extra,
+    )
+    .unwrap();
+    assert_eq!(2, pc.len());
+    assert_eq!(vec![4.0, 8.0], extra);
+
+    // the settings of the iterator are used
+    let mut pc = PointCloud3D::<Point3D>::new();
+    let mut extra = Vec::new();
+    load_xyz_extended_from_iterator(
+        XyzIterator::new("// header\n1 2 3 4\n5 6 nan 8\n".as_bytes())
+            .comment_prefix(Some("//"))
+            .non_finite(NonFiniteHandling::Skip),
+        &mut pc,
+        &mut Vec::new(),
+        &mut extra,
+    )
+    .unwrap();
+    assert_eq!(vec![Point3D::new(1.0, 2.0, 3.0)], pc.data);
+    assert_eq!(vec![4.0], extra);
+}
+
+/// Records the size of each write
+#[derive(Default)]
+struct RecordingWrite {
+    data: Vec<u8>,
+    write_sizes: Vec<usize>,
+}
+
+impl std::io::Write for RecordingWrite {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        self.write_sizes.push(buf.len());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn point_cloud_3d_xyz_buffered_test() {
+    let n: usize = 1_000_000;
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..n {
+        let f = i as f64;
+        pc.push(Point3D::new(f * 0.001, -f, f / 3.0));
+    }
+
+    let mut expected = Vec::new();
+    save_xyz(&mut expected, &pc, " ", "\n").unwrap();
+
+    let chunk = 4096;
+    let mut buffered = RecordingWrite::default();
+    save_xyz_buffered(&mut buffered, &pc, " ", "\n", chunk).unwrap();
+    assert!(expected == buffered.data);
+
+    // one write per chunk, each only containing its own positions
+    assert_eq!(n.div_ceil(chunk), buffered.write_sizes.len());
+    let mut offset = 0;
+    for (i, size) in buffered.write_sizes.iter().enumerate() {
+        let lines = buffered.data[offset..offset + size]
+            .iter()
+            .filter(|b| **b == b'\n')
+            .count();
+        let expected_lines = if i + 1 == buffered.write_sizes.len() {
+            n - i * chunk
+        } else {
+            chunk
+        };
+        assert_eq!(expected_lines, lines);
+        offset += size;
+    }
+
+    let mut single = Vec::new();
+    save_xyz_buffered(&mut single, &pc, ";", "|", 0).unwrap();
+    let mut unbuffered = Vec::new();
+    save_xyz(&mut unbuffered, &pc, ";", "|").unwrap();
+    assert!(single == unbuffered);
+
+    let mut empty = RecordingWrite::default();
+    save_xyz_buffered(&mut empty, &PointCloud3D::<Point3D>::new(), " ", "\n", 10).unwrap();
+    assert!(empty.write_sizes.is_empty());
+}
+
+#[test]
+fn point_cloud_3d_ply_colored_test() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(0.0, 0.5, 1.0));
+    pc.push(Point3D::new(2.0, 3.0, 4.0));
+    pc.push(Point3D::new(-1.0, -2.0, -3.0));
+    let colors = vec![
+        Rgb { r: 255, g: 0, b: 0 },
+        Rgb { r: 0, g: 128, b: 0 },
+        Rgb { r: 1, g: 2, b: 3 },
+    ];
+
+    for format in [
+        PlyFormat::Ascii,
+        PlyFormat::LittleEndian,
+        PlyFormat::BigEndian,
+    ] {
+        let mut bytes = Vec::new();
+        save_ply_points_colored(&mut bytes, &pc, &colors, format).unwrap();
+
+        let mut loaded = PointCloud3D::<Point3D>::new();
+        load_ply_points(bytes.as_slice(), &mut loaded).unwrap();
+        assert_eq!(pc.data, loaded.data);
+
+        for (name, expected) in [
+            ("red", [255.0, 0.0, 1.0]),
+            ("green", [0.0, 128.0, 2.0]),
+            ("blue", [0.0, 0.0, 3.0]),
+        ] {
+            let mut m = Mesh3D::<Point3D, PointCloud3D<Point3D>, Vec<usize>>::default();
+            let mut channel = Vec::new();
+            load_ply_with_scalar(bytes.as_slice(), &mut m, name, &mut channel).unwrap();
+            assert_eq!(expected.to_vec(), channel);
+            assert_eq!(0, m.num_faces());
+        }
+    }
+
+    let mut bytes = Vec::new();
+    assert!(save_ply_points_colored(&mut bytes, &pc, &colors[..2], PlyFormat::Ascii).is_err());
+}
+
+#[test]
+fn point_cloud_3d_pcd_test() {
+    let expected = vec![
+        Point3D::new(0.5, 1.0, -2.0),
+        Point3D::new(3.25, 0.0, 0.0),
+        Point3D::new(-1.0, 2.5, 7.0),
+    ];
+
+    let mut ascii = PointCloud3D::<Point3D>::new();
+    load_pcd(
+        BufReader::new(File::open("tests/data/points_ascii.pcd").unwrap()),
+        &mut ascii,
+    )
+    .unwrap();
+    assert_eq!(expected, ascii.data);
+
+    // unknown fields before the coordinates and mixed float sizes
+    let mut binary = PointCloud3D::<Point3D>::new();
+    load_pcd(
+        BufReader::new(File::open("tests/data/points_binary.pcd").unwrap()),
+        &mut binary,
+    )
+    .unwrap();
+    assert_eq!(ascii, binary);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    match load_pcd(
+        "VERSION 0.7\nFIELDS x y\nSIZE 4 4\nTYPE F F\nPOINTS 1\nDATA ascii\n1 2\n".as_bytes(),
+        &mut pc,
+    ) {
+        Err(WithLineInfo::Line(6, _, PcdError::CoordinatesMissing)) => (),
+        _ => panic!("expected missing coordinates in line 6"),
+    }
+
+    match load_pcd(
+        "FIELDS x y z\nSIZE 4 4 4\nTYPE F F F\nPOINTS 2\nDATA ascii\n1 2 3\n1 x 3\n".as_bytes(),
+        &mut pc,
+    ) {
+        Err(WithLineInfo::Line(7, _, PcdError::Vertex)) => (),
+        _ => panic!("expected a vertex error in line 7"),
+    }
+
+    match load_pcd(
+        "FIELDS x y z\nSIZE 4 4 4\nTYPE F F F\nPOINTS 2\nDATA binary\n".as_bytes(),
+        &mut pc,
+    ) {
+        Err(WithLineInfo::None(PcdError::LoadFileEndReached)) => (),
+        _ => panic!("expected the binary data to be incomplete"),
+    }
+
+    fn load_invalid(pc: &mut PointCloud3D<Point3D>) -> rust_3d::Result<()> {
+        load_pcd("FIELDS a b\n".as_bytes(), pc)?;
+        Ok(())
+    }
+
+    match load_invalid(&mut pc) {
+        Err(e @ ErrorKind::PcdError(_)) => {
+            assert!(std::error::Error::source(&e).unwrap().is::<PcdError>())
+        }
+        _ => panic!("expected the pcd error to be converted"),
+    }
+}
+
+#[test]
+fn point_cloud_3d_las_test() {
+    let mut bytes = vec![0u8; 375];
+    bytes[0..4].copy_from_slice(b"LASF");
+    bytes[24] = 1; // version 1.2
+    bytes[25] = 2;
+    bytes[26..33].copy_from_slice(b"rust-3d");
+    bytes[58..62].copy_from_slice(b"test");
+    bytes[96..100].copy_from_slice(&375u32.to_le_bytes());
+    bytes[105..107].copy_from_slice(&20u16.to_le_bytes());
+    bytes[107..111].copy_from_slice(&1u32.to_le_bytes());
+    for offset in [131, 139, 147] {
+        bytes[offset..offset + 8].copy_from_slice(&0.01f64.to_le_bytes());
+    }
+    // a single point record
+    let mut record = [0u8; 20];
+    record[0..4].copy_from_slice(&100i32.to_le_bytes());
+    record[4..8].copy_from_slice(&200i32.to_le_bytes());
+    record[8..12].copy_from_slice(&(-300i32).to_le_bytes());
+    bytes.extend_from_slice(&record);
+
+    let header = load_las_header(bytes.as_slice()).unwrap();
+    assert_eq!("LASF", header.file_signature_string());
+    // the zero padding is stripped
+    assert_eq!("rust-3d", header.system_identifier_string());
+    assert_eq!("test", header.generating_software_string());
+    assert_eq!(32, header.generating_software.len());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    load_las(std::io::Cursor::new(&bytes), &mut pc).unwrap();
+    assert_eq!(1, pc.len());
+    let p = &pc.data[0];
+    assert!((p.x() - 1.0).abs() < 1e-9);
+    assert!((p.y() - 2.0).abs() < 1e-9);
+    assert!((p.z() + 3.0).abs() < 1e-9);
+
+    assert!(load_las_header(&bytes[..100]).is_err());
+}
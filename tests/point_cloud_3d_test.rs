@@ -22,6 +22,8 @@ OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 #![deny(warnings)]
 
+mod common;
+
 use rust_3d::*;
 
 #[test]
@@ -73,3 +75,735 @@ fn test_point_cloud_3d() {
     println!("pc: {}", pc);
     assert!(pc.to_str() == "1.1 2.2 3.3\n1.2 2.3 3.4\n");
 }
+
+#[test]
+fn test_cloud_accumulator() {
+    let mut acc = CloudAccumulator::new();
+    acc.push(&Point3D::new(0.0, 4.0, -1.0));
+    acc.push(&Point3D::new(2.0, 0.0, 1.0));
+    acc.push(&Point3D::new(1.0, 2.0, 3.0));
+    assert_eq!(3, acc.count());
+
+    let stats = acc.finish().unwrap();
+    assert_eq!(Point3D::new(0.0, 0.0, -1.0), stats.min);
+    assert_eq!(Point3D::new(2.0, 4.0, 3.0), stats.max);
+    assert_eq!(Point3D::new(1.0, 2.0, 1.0), stats.centroid);
+    assert!(stats.bounding_box().is_ok());
+
+    assert!(CloudAccumulator::new().finish().is_err());
+}
+
+#[test]
+fn test_kd_tree_knearest() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..200 {
+        let t = i as f64 * 0.37;
+        pc.push(Point3D::new(
+            3.0 * t.sin(),
+            2.0 * (1.3 * t).cos(),
+            (0.7 * t).sin(),
+        ));
+    }
+
+    let mut tree = KdTree::<Point3D>::default();
+    tree.build(pc.clone()).unwrap();
+
+    let search = Point3D::new(0.1, 0.2, 0.3);
+    let mut result = Vec::new();
+    tree.knearest(&search, 10, &mut result);
+
+    let mut expected = pc.data.clone();
+    expected.sort_by(|a, b| {
+        sqr_dist_3d(&search, a)
+            .partial_cmp(&sqr_dist_3d(&search, b))
+            .unwrap()
+    });
+    expected.truncate(10);
+
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn test_kd_tree_knearest_brute_force() {
+    let mut random = common::random_generator(7, -1.0, 1.0);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for _ in 0..300 {
+        pc.push(Point3D::new(random(), random(), random()));
+    }
+    // duplicates, resulting in equal distances
+    for i in 0..20 {
+        let p = pc.data[i].clone();
+        pc.push(p);
+    }
+
+    let mut tree = KdTree::<Point3D>::default();
+    tree.build(pc.clone()).unwrap();
+
+    let distances = |search: &Point3D, ps: &[Point3D]| {
+        ps.iter()
+            .map(|p| sqr_dist_3d(search, p))
+            .collect::<Vec<_>>()
+    };
+
+    for _ in 0..50 {
+        let search = Point3D::new(random(), random(), random());
+
+        let mut expected = distances(&search, &pc.data);
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for n in [1, 2, 3, 8, 25, 100, pc.len(), pc.len() + 10] {
+            let mut result = Vec::new();
+            tree.knearest(&search, n, &mut result);
+            // compared by distance, since positions with equal distance might be returned in any order
+            assert_eq!(
+                expected[..n.min(pc.len())].to_vec(),
+                distances(&search, &result)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_kd_tree_brute_force() {
+    let mut random = common::random_generator(42, -1.0, 1.0);
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for _ in 0..500 {
+        pc.push(Point3D::new(random(), random(), random()));
+    }
+
+    let mut tree = KdTree::<Point3D>::default();
+    tree.build(pc.clone()).unwrap();
+    assert_eq!(pc.len(), tree.size());
+
+    let sorted = |mut x: Vec<Point3D>| {
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        x
+    };
+
+    for _ in 0..50 {
+        let search = Point3D::new(random(), random(), random());
+
+        let expected = pc
+            .data
+            .iter()
+            .min_by(|a, b| {
+                sqr_dist_3d(&search, *a)
+                    .partial_cmp(&sqr_dist_3d(&search, *b))
+                    .unwrap()
+            })
+            .cloned();
+        assert_eq!(expected, tree.nearest(&search));
+
+        let radius = 0.1 + 0.5 * random().abs();
+        let sphere = Sphere {
+            center: search.clone(),
+            radius: Positive::new(radius).unwrap(),
+        };
+        let mut result = Vec::new();
+        tree.in_sphere(&sphere, &mut result);
+        let expected = pc
+            .data
+            .iter()
+            .filter(|p| dist_3d(&search, *p) <= radius)
+            .cloned()
+            .collect();
+        assert_eq!(sorted(expected), sorted(result));
+
+        let sizes = [
+            0.2 + random().abs(),
+            0.2 + random().abs(),
+            0.2 + random().abs(),
+        ];
+        let box_3d = Box3D {
+            center: search.clone(),
+            size_x: Positive::new(sizes[0]).unwrap(),
+            size_y: Positive::new(sizes[1]).unwrap(),
+            size_z: Positive::new(sizes[2]).unwrap(),
+        };
+        let mut result = Vec::new();
+        tree.in_box(&box_3d, &mut result);
+        let expected = pc
+            .data
+            .iter()
+            .filter(|p| {
+                (p.x - search.x).abs() <= 0.5 * sizes[0]
+                    && (p.y - search.y).abs() <= 0.5 * sizes[1]
+                    && (p.z - search.z).abs() <= 0.5 * sizes[2]
+            })
+            .cloned()
+            .collect();
+        assert_eq!(sorted(expected), sorted(result));
+    }
+}
+
+#[test]
+fn test_scale_nonuniform() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(-1.0, -1.0, -1.0));
+    pc.push(Point3D::new(3.0, 1.0, 1.0));
+
+    let mut matrix_scaled = pc.clone();
+
+    pc.scale_nonuniform(
+        Positive::new(2.0).unwrap(),
+        Positive::new(1.0).unwrap(),
+        Positive::new(0.5).unwrap(),
+    );
+    assert_eq!(Point3D::new(-3.0, -1.0, -0.5), pc[0]);
+    assert_eq!(Point3D::new(5.0, 1.0, 0.5), pc[1]);
+
+    matrix_scaled.transform(&Matrix4::scaling_nonuniform(
+        Positive::new(2.0).unwrap(),
+        Positive::new(1.0).unwrap(),
+        Positive::new(0.5).unwrap(),
+    ));
+    assert_eq!(Point3D::new(-2.0, -1.0, -0.5), matrix_scaled[0]);
+    assert_eq!(Point3D::new(6.0, 1.0, 0.5), matrix_scaled[1]);
+}
+
+#[test]
+fn test_poly_line_3d() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(0.0, 0.0, 0.0));
+    pc.push(Point3D::new(2.0, 0.0, 0.0));
+    pc.push(Point3D::new(2.0, 3.0, 0.0));
+    let line = PolyLine3D::new(pc);
+
+    assert_eq!(5.0, line.total_length());
+    assert_eq!(
+        Point3D::new(1.0, 0.0, 0.0),
+        line.point_at_arclength(1.0).unwrap()
+    );
+    assert_eq!(
+        Point3D::new(2.0, 1.5, 0.0),
+        line.point_at_arclength(3.5).unwrap()
+    );
+    assert_eq!(
+        Point3D::new(0.0, 0.0, 0.0),
+        line.point_at_arclength(-1.0).unwrap()
+    );
+    assert_eq!(
+        Point3D::new(2.0, 3.0, 0.0),
+        line.point_at_arclength(10.0).unwrap()
+    );
+
+    let (s, p) = line.project(&Point3D::new(3.0, 2.0, 1.0)).unwrap();
+    assert_eq!(4.0, s);
+    assert_eq!(Point3D::new(2.0, 2.0, 0.0), p);
+
+    let (s, p) = line.project(&Point3D::new(-1.0, -1.0, 0.0)).unwrap();
+    assert_eq!(0.0, s);
+    assert_eq!(Point3D::new(0.0, 0.0, 0.0), p);
+
+    let empty = PolyLine3D::<Point3D>::default();
+    assert_eq!(0.0, empty.total_length());
+    assert!(empty.point_at_arclength(1.0).is_none());
+    assert!(empty.project(&Point3D::new(0.0, 0.0, 0.0)).is_none());
+}
+
+#[test]
+fn test_split_by_plane() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for z in -2..3 {
+        pc.push(Point3D::new(1.0, 1.0, z as f64));
+    }
+
+    // plane z = 0 with normal +z
+    let plane = Plane3D::<Point3D, Norm3D>::default();
+    assert_eq!(-2.0, plane.signed_distance(&pc[0]));
+
+    let (positive, negative) = pc.split_by_plane(&plane);
+    assert_eq!(
+        vec![
+            Point3D::new(1.0, 1.0, 0.0),
+            Point3D::new(1.0, 1.0, 1.0),
+            Point3D::new(1.0, 1.0, 2.0)
+        ],
+        positive.data
+    );
+    assert_eq!(
+        vec![Point3D::new(1.0, 1.0, -2.0), Point3D::new(1.0, 1.0, -1.0)],
+        negative.data
+    );
+}
+
+#[test]
+fn test_bsp_tree_3d() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..300 {
+        let t = i as f64 * 0.37;
+        pc.push(Point3D::new(
+            10.0 * t.sin(),
+            0.5 * (1.3 * t).cos(),
+            0.1 * (0.7 * t).sin(),
+        ));
+    }
+
+    let tree = BspTree3D::new(pc.clone(), 8);
+    assert_eq!(300, tree.size());
+    assert!(tree
+        .leaves()
+        .all(|leaf| !leaf.is_empty() && leaf.len() <= 8));
+
+    for search in [
+        Point3D::new(0.1, 0.2, 0.3),
+        Point3D::new(-7.0, 1.0, 0.0),
+        Point3D::new(20.0, -3.0, 5.0),
+    ] {
+        let expected = pc
+            .data
+            .iter()
+            .min_by(|a, b| {
+                sqr_dist_3d(&search, *a)
+                    .partial_cmp(&sqr_dist_3d(&search, *b))
+                    .unwrap()
+            })
+            .unwrap();
+        assert_eq!(Some(expected), tree.nearest(&search));
+    }
+
+    let same = PointCloud3D {
+        data: vec![Point3D::new(1.0, 1.0, 1.0); 5],
+    };
+    let tree = BspTree3D::new(same, 1);
+    assert_eq!(1, tree.leaves().count());
+    assert!(BspTree3D::<Point3D>::new(PointCloud3D::new(), 4)
+        .nearest(&Point3D::default())
+        .is_none());
+}
+
+#[test]
+fn test_densify_knn() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..10 {
+        pc.push(Point3D::new(i as f64, 0.0, 0.0));
+    }
+
+    let dense = densify_knn(&pc, 2, 3);
+    assert_eq!(40, dense.len());
+    assert_eq!(pc.data[..], dense.data[..10]);
+    // neighbours of each position are at most 2 apart, all new positions lie between them
+    for p in dense.data[10..].iter() {
+        assert!(p.x >= 0.0 && p.x <= 9.0);
+        assert_eq!(0.0, p.y);
+        assert_eq!(0.0, p.z);
+    }
+    assert_eq!(dense, densify_knn(&pc, 2, 3));
+
+    assert_eq!(pc, densify_knn(&pc, 0, 3));
+    let same = PointCloud3D {
+        data: vec![Point3D::new(1.0, 2.0, 3.0); 4],
+    };
+    assert_eq!(4, densify_knn(&same, 2, 3).len());
+}
+
+#[test]
+fn test_kabsch() {
+    let source = vec![
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 2.0, 0.0),
+        Point3D::new(0.0, 0.0, 3.0),
+        Point3D::new(1.0, 1.0, 1.0),
+    ];
+    let transformation =
+        Matrix4::translation(1.0, -2.0, 3.0) * Matrix4::rotation(Rad(0.3), Rad(-1.2), Rad(2.0));
+    let target: Vec<Point3D> = source
+        .iter()
+        .map(|p| p.transformed(&transformation))
+        .collect();
+
+    let check = |source: &[Point3D], target: &[Point3D]| {
+        let m = kabsch(source, target).unwrap();
+        for (s, t) in source.iter().zip(target.iter()) {
+            assert!(dist_3d(&s.transformed(&m), t) < 1e-9);
+        }
+    };
+    check(&source, &target);
+    // coplanar positions
+    check(&source[..3], &target[..3]);
+
+    // reflections are corrected to proper rotations
+    let mirrored: Vec<Point3D> = source
+        .iter()
+        .map(|p| Point3D::new(-p.x, p.y, p.z))
+        .collect();
+    let m = kabsch(&source, &mirrored).unwrap();
+    let r = &m.data;
+    let det = r[0][0] * (r[1][1] * r[2][2] - r[1][2] * r[2][1])
+        - r[0][1] * (r[1][0] * r[2][2] - r[1][2] * r[2][0])
+        + r[0][2] * (r[1][0] * r[2][1] - r[1][1] * r[2][0]);
+    assert!((det - 1.0).abs() < 1e-9);
+
+    assert!(kabsch(&source, &target[1..]).is_err());
+    assert!(kabsch(&source[..2], &target[..2]).is_err());
+    let collinear = vec![
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 1.0, 1.0),
+        Point3D::new(2.0, 2.0, 2.0),
+    ];
+    assert!(kabsch(&collinear, &collinear).is_err());
+}
+
+#[test]
+fn test_umeyama() {
+    let source = vec![
+        Point3D::new(0.0, 0.0, 0.0),
+        Point3D::new(1.0, 0.0, 0.0),
+        Point3D::new(0.0, 2.0, 0.0),
+        Point3D::new(0.0, 0.0, 3.0),
+        Point3D::new(1.0, 1.0, 1.0),
+    ];
+    let transformation = Matrix4::translation(1.0, -2.0, 3.0)
+        * Matrix4::rotation(Rad(0.3), Rad(-1.2), Rad(2.0))
+        * Matrix4::scale(2.5, 2.5, 2.5);
+    let target: Vec<Point3D> = source
+        .iter()
+        .map(|p| p.transformed(&transformation))
+        .collect();
+
+    let (m, scale) = umeyama(&source, &target, true).unwrap();
+    assert!((scale - 2.5).abs() < 1e-9);
+    for (s, t) in source.iter().zip(target.iter()) {
+        assert!(dist_3d(&s.transformed(&m), t) < 1e-9);
+    }
+
+    let (m, scale) = umeyama(&source, &target, false).unwrap();
+    assert_eq!(1.0, scale);
+    assert_eq!(kabsch(&source, &target).unwrap(), m);
+
+    assert!(umeyama(&source, &target[1..], true).is_err());
+}
+
+#[test]
+fn test_kd_tree_knearest_limits() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..5 {
+        pc.push(Point3D::new(i as f64, 0.0, 0.0));
+    }
+
+    let mut tree = KdTree::<Point3D>::default();
+    tree.build(pc.clone()).unwrap();
+
+    let search = Point3D::new(3.9, 0.0, 0.0);
+    let mut result = Vec::new();
+    tree.knearest(&search, 10, &mut result);
+    assert_eq!(
+        vec![4.0, 3.0, 2.0, 1.0, 0.0],
+        result.iter().map(|p| p.x).collect::<Vec<_>>()
+    );
+
+    let mut result = Vec::new();
+    tree.knearest(&search, 2, &mut result);
+    assert_eq!(
+        vec![4.0, 3.0],
+        result.iter().map(|p| p.x).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_remove_statistical_outliers() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for x in 0..10 {
+        for y in 0..10 {
+            pc.push(Point3D::new(x as f64, y as f64, 0.0));
+        }
+    }
+    pc.push(Point3D::new(50.0, 50.0, 50.0));
+    pc.push(Point3D::new(-40.0, 5.0, 10.0));
+
+    let filtered = remove_statistical_outliers(&pc, 4, 1.0);
+    assert_eq!(100, filtered.len());
+    assert!(filtered.data.iter().all(|p| p.z == 0.0));
+
+    assert_eq!(pc, remove_statistical_outliers(&pc, 0, 1.0));
+}
+
+#[test]
+fn test_approximate_knn() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for x in 0..10 {
+        for y in 0..10 {
+            pc.push(Point3D::new(x as f64, y as f64, 0.0));
+        }
+    }
+    let grid = SpatialHashGrid3D::new(&pc, Positive::new(1.5).unwrap());
+
+    let search = Point3D::new(4.1, 4.2, 0.0);
+    let result = pc.approximate_knn(&search, 3, &grid);
+    assert_eq!(
+        vec![
+            Point3D::new(4.0, 4.0, 0.0),
+            Point3D::new(4.0, 5.0, 0.0),
+            Point3D::new(5.0, 4.0, 0.0),
+        ],
+        result
+            .into_iter()
+            .map(|i| pc.data[i].clone())
+            .collect::<Vec<_>>()
+    );
+
+    // only the neighbouring cells are considered
+    let far = Point3D::new(100.0, 100.0, 0.0);
+    assert!(pc.approximate_knn(&far, 3, &grid).is_empty());
+    assert!(pc.approximate_knn(&search, 0, &grid).is_empty());
+    assert_eq!(
+        4,
+        grid.indices_in_cell(grid.cell_of(&Point3D::new(0.0, 0.0, 0.0)))
+            .len()
+    );
+}
+
+#[test]
+fn test_center_to_origin() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    assert_eq!(Point3D::default(), pc.center_to_origin());
+
+    pc.push(Point3D::new(1.0, 2.0, 3.0));
+    pc.push(Point3D::new(3.0, 4.0, 5.0));
+    let original = pc.clone();
+
+    let offset = pc.center_to_origin();
+    assert_eq!(Point3D::new(-2.0, -3.0, -4.0), offset);
+    assert_eq!(Point3D::new(-1.0, -1.0, -1.0), pc.data[0]);
+    assert_eq!(Point3D::new(1.0, 1.0, 1.0), pc.data[1]);
+    assert_eq!(Point3D::default(), pc.center_of_gravity().unwrap());
+
+    pc.move_by(-offset.x, -offset.y, -offset.z);
+    assert_eq!(original.data, pc.data);
+}
+
+#[test]
+fn test_farthest_point_sample() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..10 {
+        pc.push(Point3D::new(i as f64, 0.0, 0.0));
+    }
+
+    assert_eq!(10, farthest_point_sample(&pc, 20, 1).len());
+    assert_eq!(0, farthest_point_sample(&pc, 0, 1).len());
+
+    let sampled = farthest_point_sample(&pc, 3, 7);
+    assert_eq!(3, sampled.len());
+    assert_eq!(sampled.data, farthest_point_sample(&pc, 3, 7).data);
+
+    // the second pick is always the end of the line farthest from the first one
+    let expected = if sampled.data[0].x < 4.5 { 9.0 } else { 0.0 };
+    assert_eq!(expected, sampled.data[1].x);
+
+    // picking all positions picks each once
+    let mut all: Vec<f64> = farthest_point_sample(&pc, 9, 3)
+        .data
+        .iter()
+        .map(|p| p.x)
+        .collect();
+    all.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    all.dedup();
+    assert_eq!(9, all.len());
+}
+
+#[test]
+fn test_mls_smooth() {
+    let mut noisy = PointCloud3D::<Point3D>::new();
+    let mut paraboloid = PointCloud3D::<Point3D>::new();
+    for i in 0..11 {
+        for j in 0..11 {
+            let x = -1.0 + 0.2 * i as f64;
+            let y = -1.0 + 0.2 * j as f64;
+            let noise = if (i + j) % 2 == 0 { 0.05 } else { -0.05 };
+            noisy.push(Point3D::new(x, y, noise));
+            paraboloid.push(Point3D::new(x, y, 0.5 * (x * x + y * y)));
+        }
+    }
+
+    let max_abs_z =
+        |pc: &PointCloud3D<Point3D>| pc.data.iter().map(|p| p.z.abs()).fold(0.0, f64::max);
+
+    let smoothed = mls_smooth(&noisy, 9, 0);
+    assert_eq!(noisy.len(), smoothed.len());
+    assert!(max_abs_z(&smoothed) < 0.5 * max_abs_z(&noisy));
+
+    // a quadratic fit follows the paraboloid, while a plane fit flattens it
+    let max_error = |pc: &PointCloud3D<Point3D>| {
+        pc.data
+            .iter()
+            .map(|p| (p.z - 0.5 * (p.x * p.x + p.y * p.y)).abs())
+            .fold(0.0, f64::max)
+    };
+    let quadratic_error = max_error(&mls_smooth(&paraboloid, 12, 2));
+    let planar_error = max_error(&mls_smooth(&paraboloid, 12, 0));
+    assert!(quadratic_error < 0.1 * planar_error);
+
+    assert_eq!(noisy.data, mls_smooth(&noisy, 2, 0).data);
+}
+
+#[test]
+fn test_estimate_normals() {
+    // tilted plane x + 2y + 3z = 1
+    let plane_normal = Norm3D::new(Point3D::new(1.0, 2.0, 3.0)).unwrap();
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..15 {
+        for j in 0..15 {
+            let (x, y) = (0.1 * i as f64, 0.13 * j as f64 + 0.01 * i as f64);
+            pc.push(Point3D::new(x, y, (1.0 - x - 2.0 * y) / 3.0));
+        }
+    }
+
+    let normals = estimate_normals(&pc, 8).unwrap();
+    assert_eq!(pc.len(), normals.len());
+    for n in normals.iter() {
+        assert!((n.dot(&plane_normal).abs() - 1.0).abs() < 1e-9);
+    }
+
+    // degenerate neighbourhoods
+    let mut pc = PointCloud3D::<Point3D>::new();
+    pc.push(Point3D::new(1.0, 0.0, 0.0));
+    pc.push(Point3D::new(0.0, 1.0, 0.0));
+    assert_eq!(vec![Norm3D::norm_z(); 2], estimate_normals(&pc, 5).unwrap());
+
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for _ in 0..4 {
+        pc.push(Point3D::new(1.0, 1.0, 0.0));
+        pc.push(Point3D::new(0.0, 1.0, 1.0));
+    }
+    assert_eq!(vec![Norm3D::norm_z(); 8], estimate_normals(&pc, 5).unwrap());
+
+    assert!(estimate_normals(&PointCloud3D::<Point3D>::new(), 5).is_err());
+}
+
+#[test]
+fn test_centroid_bounding_sphere() {
+    let mut cube = PointCloud3D::<Point3D>::new();
+    for x in [-1.0, 1.0] {
+        for y in [-1.0, 1.0] {
+            for z in [-1.0, 1.0] {
+                cube.push(Point3D::new(x, y, z));
+            }
+        }
+    }
+
+    let c = centroid(&cube).unwrap();
+    assert!(c.x().abs() < 1e-12 && c.y().abs() < 1e-12 && c.z().abs() < 1e-12);
+
+    let sphere = bounding_sphere(&cube).unwrap();
+    for p in cube.data.iter() {
+        assert!(dist_3d(&sphere.center, p) <= *sphere.radius);
+    }
+    // Ritter's algorithm isn't optimal, but should be close
+    assert!(*sphere.radius < 1.2 * 3.0f64.sqrt());
+
+    let mut random = common::random_generator(12345, 0.0, 1.0);
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for _ in 0..1000 {
+        pc.push(Point3D::new(
+            10.0 * random() - 3.0,
+            2.0 * random(),
+            -5.0 * random(),
+        ));
+    }
+    let sphere = bounding_sphere(&pc).unwrap();
+    for p in pc.data.iter() {
+        assert!(dist_3d(&sphere.center, p) <= *sphere.radius);
+    }
+    let c = centroid(&pc).unwrap();
+    assert!((c.x() - 2.0).abs() < 0.5);
+
+    let empty = PointCloud3D::<Point3D>::new();
+    assert!(centroid(&empty).is_none());
+    assert!(bounding_sphere(&empty).is_none());
+
+    let mut single = PointCloud3D::<Point3D>::new();
+    single.push(Point3D::new(1.0, 2.0, 3.0));
+    assert_eq!(Some(Point3D::new(1.0, 2.0, 3.0)), centroid(&single));
+    assert!(bounding_sphere(&single).is_none());
+}
+
+#[test]
+fn test_fit_plane() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..20 {
+        let f = i as f64;
+        pc.push(Point3D::new(
+            (f * 1.7).sin() * 5.0 + 3.0,
+            (f * 0.9).cos() * 2.0 - 1.0,
+            0.0,
+        ));
+    }
+
+    let plane: Plane3D<Point3D, Norm3D> = fit_plane(&pc).unwrap();
+    let centroid = pc.center_of_gravity().unwrap();
+    assert!(dist_3d(&centroid, &plane.origin()) < 1e-9);
+    assert!(plane.u().z().abs() < 1e-9);
+    assert!(plane.v().z().abs() < 1e-9);
+    assert!((1.0 - plane.normal().z().abs()).abs() < 1e-9);
+
+    // tilted plane z = 0.5 * x + 1
+    let mut tilted = PointCloud3D::<Point3D>::new();
+    for p in pc.data.iter() {
+        tilted.push(Point3D::new(p.x(), p.y(), 0.5 * p.x() + 1.0));
+    }
+    let plane: Plane3D<Point3D, Norm3D> = fit_plane(&tilted).unwrap();
+    let expected = Norm3D::new(Point3D::new(-0.5, 0.0, 1.0)).unwrap();
+    assert!((1.0 - plane.normal().dot(&expected).abs()).abs() < 1e-9);
+    for p in tilted.data.iter() {
+        assert!(conn(&plane.origin(), p).dot(&plane.normal()).abs() < 1e-9);
+    }
+
+    let mut two = PointCloud3D::<Point3D>::new();
+    two.push(Point3D::new(0.0, 0.0, 0.0));
+    two.push(Point3D::new(1.0, 0.0, 0.0));
+    assert!(matches!(
+        fit_plane::<_, Plane3D<Point3D, Norm3D>, _>(&two),
+        Err(ErrorKind::TooFewPoints)
+    ));
+
+    let mut collinear = PointCloud3D::<Point3D>::new();
+    for i in 0..10 {
+        let f = i as f64;
+        collinear.push(Point3D::new(f, 2.0 * f, -f));
+    }
+    assert!(matches!(
+        fit_plane::<_, Plane3D<Point3D, Norm3D>, _>(&collinear),
+        Err(ErrorKind::PointsCollinear)
+    ));
+
+    let mut equal = PointCloud3D::<Point3D>::new();
+    for _ in 0..5 {
+        equal.push(Point3D::new(1.0, 1.0, 1.0));
+    }
+    assert!(matches!(
+        fit_plane::<_, Plane3D<Point3D, Norm3D>, _>(&equal),
+        Err(ErrorKind::PointsCollinear)
+    ));
+}
+
+#[test]
+fn test_point_cloud_3d_append_subtract() {
+    let mut pc = PointCloud3D::<Point3D>::new();
+    for i in 0..10 {
+        pc.push(Point3D::new(i as f64, 0.0, 0.0));
+    }
+
+    let mut other = PointCloud3D::<Point3D>::new();
+    for i in 0..5 {
+        other.push(Point3D::new(i as f64, 0.05, 0.0));
+    }
+
+    let mut appended = pc.clone();
+    appended.append(other.clone());
+    assert_eq!(15, appended.len());
+    assert_eq!(other.data[..], appended.data[10..]);
+
+    let tolerance = Positive::new(0.1).unwrap();
+    assert_eq!(0, pc.subtract_within(&pc, tolerance).len());
+    assert_eq!(pc, pc.subtract_within(&PointCloud3D::new(), tolerance));
+
+    let remaining = pc.subtract_within(&other, tolerance);
+    assert_eq!(pc.data[5..], remaining.data[..]);
+
+    // neighbours further away than the tolerance are kept
+    let remaining = pc.subtract_within(&other, Positive::new(0.01).unwrap());
+    assert_eq!(pc, remaining);
+}
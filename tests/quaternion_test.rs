@@ -0,0 +1,134 @@
+/*
+Copyright 2020 Martin Buck
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"),
+to deal in the Software without restriction, including without limitation the
+rights to use, copy, modify, merge, publish, distribute, sublicense,
+and/or sell copies of the Software, and to permit persons to whom the Software
+is furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall
+be included all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT,
+TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE
+OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+*/
+
+#![deny(warnings)]
+
+use rust_3d::*;
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+fn assert_close(expected: &Point3D, actual: &Point3D) {
+    assert!(
+        dist_3d(expected, actual) < 1e-9,
+        "{} != {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn quaternion_rotate_point_test() {
+    let q = Quaternion::from_axis_angle(&Point3D::new(0.0, 0.0, 2.0), Rad(FRAC_PI_2)).unwrap();
+    assert!((q.abs() - 1.0).abs() < 1e-12);
+
+    assert_close(
+        &Point3D::new(0.0, 1.0, 0.0),
+        &q.rotate_point(&Point3D::new(1.0, 0.0, 0.0)),
+    );
+    assert_close(
+        &Point3D::new(-1.0, 0.0, 3.0),
+        &q.rotate_point(&Point3D::new(0.0, 1.0, 3.0)),
+    );
+    assert_close(
+        &Point3D::new(1.0, 0.0, 0.0),
+        &q.conjugate().rotate_point(&Point3D::new(0.0, 1.0, 0.0)),
+    );
+
+    let p = Point3D::new(1.0, 2.0, 3.0);
+    assert_close(&p, &Quaternion::identity().rotate_point(&p));
+}
+
+#[test]
+fn quaternion_multiply_test() {
+    let z = Quaternion::from_axis_angle(&Norm3D::norm_z(), Rad(FRAC_PI_2)).unwrap();
+    let x = Quaternion::from_axis_angle(&Norm3D::norm_x(), Rad(FRAC_PI_2)).unwrap();
+    let p = Point3D::new(1.0, 0.0, 0.0);
+
+    // rotate around z first, then around x
+    let xz = x.multiply(&z);
+    assert_close(&Point3D::new(0.0, 0.0, 1.0), &xz.rotate_point(&p));
+    assert_close(&x.rotate_point(&z.rotate_point(&p)), &xz.rotate_point(&p));
+    assert_eq!(xz, &x * &z);
+
+    let mut full = Quaternion::identity();
+    for _ in 0..4 {
+        full = full.multiply(&z);
+    }
+    assert_close(&p, &full.rotate_point(&p));
+
+    let half = Quaternion::from_axis_angle(&Norm3D::norm_z(), Rad(PI)).unwrap();
+    assert_close(&Point3D::new(-1.0, 0.0, 0.0), &(&z * &z).rotate_point(&p));
+    assert_close(&half.rotate_point(&p), &(&z * &z).rotate_point(&p));
+}
+
+#[test]
+fn quaternion_to_matrix4_test() {
+    let axis = Norm3D::new(Point3D::new(1.0, -2.0, 0.5)).unwrap();
+    let q = Quaternion::from_axis_angle(&axis, Rad(0.7)).unwrap();
+    let m = q.to_matrix4();
+    let expected = Matrix4::rotation_axis(&axis, Rad(0.7));
+
+    for i in 0..4 {
+        for j in 0..4 {
+            assert!((expected.data[i][j] - m.data[i][j]).abs() < 1e-12);
+        }
+    }
+
+    let p = Point3D::new(-3.0, 0.5, 2.0);
+    assert_close(&q.rotate_point(&p), &m.transform(&p));
+}
+
+#[test]
+fn quaternion_normalize_test() {
+    let q = Quaternion {
+        w: 2.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    assert_eq!(Quaternion::identity(), q.normalize().unwrap());
+
+    let q = Quaternion {
+        w: 1.0,
+        x: 1.0,
+        y: 1.0,
+        z: 1.0,
+    }
+    .normalize()
+    .unwrap();
+    assert!((q.abs() - 1.0).abs() < 1e-12);
+
+    let zero = Quaternion {
+        w: 0.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    assert!(matches!(
+        zero.normalize(),
+        Err(ErrorKind::NormalizeVecWithoutLength)
+    ));
+    assert!(matches!(
+        Quaternion::from_axis_angle(&Point3D::new(0.0, 0.0, 0.0), Rad(1.0)),
+        Err(ErrorKind::NormalizeVecWithoutLength)
+    ));
+}